@@ -0,0 +1,209 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+};
+use super::{
+    config::Config,
+    error::{Error, Result},
+    file::Source,
+    parallel::{self, Finished, Job},
+    progress::Budget,
+    solver::Part,
+    get_day,
+};
+
+// Inputs of one person, e.g. inputs/alice/day7.txt, by day
+#[derive(Debug, Clone)]
+pub struct InputSet {
+    pub name: String,
+    pub inputs: BTreeMap<usize, PathBuf>,
+}
+
+// Puzzle examples are kept next to the inputs, but they are too small for the assumptions of some real inputs
+// and need parameters of their own, e.g. a preamble of 5 on day 9
+const EXAMPLES_DIR: &str = "examples";
+
+// Every subdirectory of `dir` with an input for one of the days is an input set, except the examples, and so
+// is `dir` itself, named after the directory. Sets are sorted by name, with `dir` first
+pub fn find_input_sets<P: AsRef<Path>>(dir: P, days: &[usize]) -> Result<Vec<InputSet>> {
+    let dir = dir.as_ref();
+    let mut sets = Vec::new();
+    let root_name = dir.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_else(|| dir.display().to_string());
+    sets.extend(read_input_set(dir, &root_name, days)?);
+    let mut subdirs = Vec::new();
+    for entry in read_dir(dir)? {
+        if entry.is_dir() && entry.file_name() != Some(EXAMPLES_DIR.as_ref()) {
+            subdirs.push(entry);
+        }
+    }
+    subdirs.sort();
+    for subdir in subdirs {
+        let name = subdir.file_name().expect("directory entries have a name").to_string_lossy().into_owned();
+        sets.extend(read_input_set(&subdir, &name, days)?);
+    }
+    debug!("Found {} input sets in {}", sets.len(), dir.display());
+    Ok(sets)
+}
+
+// None if there is no input for any of the days
+fn read_input_set(dir: &Path, name: &str, days: &[usize]) -> Result<Option<InputSet>> {
+    let mut inputs = BTreeMap::new();
+    for path in read_dir(dir)? {
+        let day = path.file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day")?.strip_suffix(".txt")?.parse::<usize>().ok());
+        match day {
+            Some(day) if path.is_file() && days.contains(&day) => { inputs.insert(day, path); },
+            _ => (),
+        }
+    }
+    Ok(if inputs.is_empty() { None } else { Some(InputSet { name: String::from(name), inputs }) })
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+    let io_error = |e: std::io::Error| Error::Io { source: dir.display().to_string(), reason: e.to_string() };
+    fs::read_dir(dir).map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(io_error))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell {
+    // The input set has no input for the day
+    Missing,
+    Answer(String),
+    // Reading, parsing or solving failed
+    Failed(String),
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cell::Missing => write!(f, "-"),
+            Cell::Answer(answer) => write!(f, "{}", answer),
+            Cell::Failed(_) => write!(f, "ERROR"),
+        }
+    }
+}
+
+// A row per day and part, with a column per input set
+#[derive(Debug, Clone)]
+pub struct Matrix {
+    pub sets: Vec<String>,
+    pub rows: Vec<(usize, Part, Vec<Cell>)>,
+}
+
+impl Matrix {
+    pub fn get(&self, day: usize, part: Part, set: &str) -> Option<&Cell> {
+        let column = self.sets.iter().position(|name| name == set)?;
+        self.rows.iter().find(|(d, p, _)| *d == day && *p == part).map(|(_, _, cells)| &cells[column])
+    }
+
+    // Every failure with its day, part and input set, in the order of the matrix
+    pub fn failures(&self) -> Vec<(usize, Part, &str, &str)> {
+        let mut failures = Vec::new();
+        for (day, part, cells) in self.rows.iter() {
+            for (set, cell) in self.sets.iter().zip(cells) {
+                if let Cell::Failed(e) = cell {
+                    failures.push((*day, *part, set.as_str(), e.as_str()));
+                }
+            }
+        }
+        failures
+    }
+
+    // The table, followed by the error of each failure
+    pub fn report(&self) -> String {
+        let mut report = self.to_string();
+        for (day, part, set, e) in self.failures() {
+            report += &format!("\nDay {}, part {} ({}):\n{}\n", day, part.number(), set, e);
+        }
+        report
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let widths: Vec<usize> = self.sets.iter().enumerate().map(|(i, set)| {
+            self.rows.iter().map(|(_, _, cells)| cells[i].to_string().len()).max().unwrap_or(0).max(set.len())
+        }).collect();
+        let mut header = String::from("Day  Part");
+        for (set, width) in self.sets.iter().zip(widths.iter()) {
+            header += &format!("  {:<width$}", set, width = width);
+        }
+        writeln!(f, "{}", header.trim_end())?;
+        for (day, part, cells) in self.rows.iter() {
+            let mut row = format!("{:>3}  {:>4}", day, part.number());
+            for (cell, width) in cells.iter().zip(widths.iter()) {
+                row += &format!("  {:<width$}", cell.to_string(), width = width);
+            }
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+// Runs both parts of the days over every input set, using the parameters of each day.
+// `on_finish` is called as soon as each input is done, like with parallel::run_jobs
+pub fn run<F: FnMut(&Finished)>(
+    sets: &[InputSet],
+    days: &[usize],
+    config: &Config,
+    workers: usize,
+    budget: &Budget,
+    on_finish: F,
+) -> Matrix {
+    let parts = [Part::One, Part::Two];
+    let mut jobs = Vec::new();
+    let mut columns = Vec::new();
+    for (column, set) in sets.iter().enumerate() {
+        for (day, path) in set.inputs.iter() {
+            let puzzle = match get_day(*day) {
+                Some(puzzle) if days.contains(day) => puzzle,
+                _ => continue,
+            };
+            jobs.push(Job { day: *day, puzzle, source: Source::File(path.clone()), params: config.params(*day) });
+            columns.push(column);
+        }
+    }
+    let run = parallel::run_jobs(jobs, workers, budget, on_finish);
+
+    let mut by_day: BTreeMap<(usize, Part), Vec<Cell>> = BTreeMap::new();
+    for (finished, column) in run.finished.into_iter().zip(columns) {
+        let results: Vec<(Part, Cell)> = match finished.solution {
+            Ok(solution) => solution.answers.into_iter().map(|answer| {
+                let cell = match answer.result {
+                    Ok(result) => Cell::Answer(result),
+                    Err(e) => Cell::Failed(e.to_string()),
+                };
+                (answer.part, cell)
+            }).collect(),
+            Err(e) => parts.iter().map(|part| (*part, Cell::Failed(e.to_string()))).collect(),
+        };
+        for (part, cell) in results {
+            by_day.entry((finished.day, part)).or_insert_with(|| vec![Cell::Missing; sets.len()])[column] = cell;
+        }
+    }
+    Matrix {
+        sets: sets.iter().map(|set| set.name.clone()).collect(),
+        rows: by_day.into_iter().map(|((day, part), cells)| (day, part, cells)).collect(),
+    }
+}
+
+// Finds the input sets of a directory and runs them, which is an error when none of them has an input for the days
+pub fn run_dir<P: AsRef<Path>, F: FnMut(&Finished)>(
+    dir: P,
+    days: &[usize],
+    config: &Config,
+    workers: usize,
+    budget: &Budget,
+    on_finish: F,
+) -> Result<Matrix> {
+    let sets = find_input_sets(&dir, days)?;
+    if sets.is_empty() {
+        return Err(Error::solve(format!("no inputs for the given days in {}", dir.as_ref().display())))
+    }
+    Ok(run(&sets, days, config, workers, budget, on_finish))
+}
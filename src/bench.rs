@@ -0,0 +1,95 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    time::Duration,
+};
+use toml::value::Table;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    progress::Budget,
+    solver::{Day, Part},
+};
+
+// Summary of the time a phase took over every iteration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len();
+        Stats {
+            min: samples[0],
+            median: (samples[(n - 1) / 2] + samples[n / 2]) / 2,
+            max: samples[n - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayBench {
+    pub day: usize,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub parts: Vec<(Part, Stats)>,
+}
+
+// Parses and solves both parts the given number of times, with the same parameters as `run`; the same lines
+// are reused for every iteration, so that reading the input isn't measured
+pub fn bench_day(day: usize, puzzle: &dyn Day, lines: &Lines, params: &Table, iterations: usize) -> Result<DayBench> {
+    let parts = [Part::One, Part::Two];
+    let iterations = iterations.max(1);
+    let mut parse_samples: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut part_samples: Vec<Vec<Duration>> = vec![Vec::with_capacity(iterations); parts.len()];
+    for _ in 0..iterations {
+        let solution = puzzle.solve_within(lines.clone(), &parts, params, &Budget::unlimited())?;
+        parse_samples.push(solution.parse_time);
+        for (samples, answer) in part_samples.iter_mut().zip(solution.answers) {
+            answer.result?;
+            samples.push(answer.time);
+        }
+    }
+    Ok(DayBench {
+        day,
+        iterations,
+        parse: Stats::from_samples(parse_samples),
+        parts: parts.iter().copied().zip(part_samples.into_iter().map(Stats::from_samples)).collect(),
+    })
+}
+
+impl DayBench {
+    // Phase names and their stats, in the order they run
+    pub fn phases(&self) -> Vec<(String, Stats)> {
+        let mut phases = vec![(String::from("parse"), self.parse)];
+        phases.extend(self.parts.iter().map(|(part, stats)| (format!("part{}", part.number()), *stats)));
+        phases
+    }
+}
+
+// Tab-separated, with times in microseconds, so that runs can be compared with any diff or spreadsheet tool
+pub fn write_results<P: AsRef<Path>>(path: P, results: &[DayBench]) -> Result<()> {
+    let io_error = |e: io::Error| Error::Io { source: path.as_ref().display().to_string(), reason: e.to_string() };
+    let mut file = File::create(path.as_ref()).map_err(io_error)?;
+    writeln!(file, "day\tphase\titerations\tmin_us\tmedian_us\tmax_us").map_err(io_error)?;
+    for result in results.iter() {
+        for (phase, stats) in result.phases() {
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                result.day,
+                phase,
+                result.iterations,
+                stats.min.as_micros(),
+                stats.median.as_micros(),
+                stats.max.as_micros(),
+            ).map_err(io_error)?;
+        }
+    }
+    Ok(())
+}
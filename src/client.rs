@@ -0,0 +1,199 @@
+use std::{
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+};
+use regex::Regex;
+use super::{
+    error::{Error, Result},
+    ledger::Ledger,
+    solver::Part,
+};
+
+pub const YEAR: usize = 2020;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/EpicEric/rust-aoc-2020";
+
+// Talks to the Advent of Code website, or to any server with the same endpoints
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: String::from(base_url.trim_end_matches('/')),
+            session: String::from(session),
+        }
+    }
+
+    pub fn input_url(&self, day: usize) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, YEAR, day)
+    }
+
+    pub fn fetch_input(&self, day: usize) -> Result<String> {
+        let url = self.input_url(day);
+        let http_error = |reason: String| Error::Http { url: url.clone(), reason };
+        info!("Fetching the input of day {} from {}", day, url);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+        match response {
+            Ok(response) => response.into_string().map_err(|e| http_error(e.to_string())),
+            Err(ureq::Error::Status(404, _)) => Err(http_error(format!("the input for day {} is not available", day))),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(http_error(format!("status {}: {}", status, body.trim())))
+            },
+            Err(e) => Err(http_error(e.to_string())),
+        }
+    }
+
+    pub fn answer_url(&self, day: usize) -> String {
+        format!("{}/{}/day/{}/answer", self.base_url, YEAR, day)
+    }
+
+    pub fn submit_answer(&self, day: usize, part: Part, answer: &str) -> Result<Verdict> {
+        let url = self.answer_url(day);
+        let http_error = |reason: String| Error::Http { url: url.clone(), reason };
+        let level = part.number().to_string();
+        info!("Submitting {:?} for day {} part {} to {}", answer, day, level, url);
+        let response = ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &level), ("answer", answer)]);
+        match response {
+            Ok(response) => {
+                let body = response.into_string().map_err(|e| http_error(e.to_string()))?;
+                let verdict = parse_verdict(&body);
+                if let Verdict::Unrecognized(_) = verdict {
+                    warn!("Couldn't recognize the verdict in the response from {}", url);
+                    debug!("Response body: {}", body);
+                }
+                Ok(verdict)
+            },
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(http_error(format!("status {}: {}", status, response_text(&body))))
+            },
+            Err(e) => Err(http_error(e.to_string())),
+        }
+    }
+}
+
+// How the server judged a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // Wrong answers make the server refuse submissions for a while
+    RateLimited { wait: Option<String> },
+    // The part was already solved, or part 2 isn't unlocked yet
+    WrongLevel,
+    // Anything else, with the text of the response
+    Unrecognized(String),
+}
+
+impl Verdict {
+    pub fn is_right(&self) -> bool {
+        *self == Verdict::Right
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right answer"),
+            Verdict::Wrong => write!(f, "wrong answer"),
+            Verdict::TooHigh => write!(f, "wrong answer, too high"),
+            Verdict::TooLow => write!(f, "wrong answer, too low"),
+            Verdict::RateLimited { wait: Some(wait) } => write!(f, "answered too recently, {} left to wait", wait),
+            Verdict::RateLimited { wait: None } => write!(f, "answered too recently"),
+            Verdict::WrongLevel => write!(f, "not solving the right level; already completed?"),
+            Verdict::Unrecognized(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+// Only the <article> of the response has the message, but the whole body is used if there's none
+fn response_text(body: &str) -> String {
+    lazy_static! {
+        static ref ARTICLE_RE: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").expect("invalid article regex");
+        static ref TAG_RE: Regex = Regex::new(r"<[^>]*>").expect("invalid tag regex");
+        static ref SPACE_RE: Regex = Regex::new(r"\s+").expect("invalid whitespace regex");
+    }
+    let article = ARTICLE_RE.captures(body).map_or(body, |caps| caps.get(1).unwrap().as_str());
+    let text = TAG_RE.replace_all(article, "");
+    SPACE_RE.replace_all(&text, " ").trim().to_string()
+}
+
+pub fn parse_verdict(body: &str) -> Verdict {
+    lazy_static! {
+        static ref WAIT_RE: Regex = Regex::new(r"You have (.+?) left to wait").expect("invalid wait regex");
+    }
+    let text = response_text(body);
+    if text.contains("That's the right answer") {
+        Verdict::Right
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited { wait: WAIT_RE.captures(&text).map(|caps| caps[1].to_string()) }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unrecognized(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    // The ledger already has an answer for this day, part and input, so nothing was sent
+    AlreadyConfirmed(String),
+    Judged(Verdict),
+}
+
+// Right answers are recorded in the ledger, which is saved right away
+pub fn submit_and_record(client: &Client, ledger: &mut Ledger, day: usize, part: Part, input: &str, answer: &str) -> Result<Submission> {
+    if let Some(recorded) = ledger.get(day, part, input) {
+        return Ok(Submission::AlreadyConfirmed(String::from(recorded)))
+    }
+    let verdict = client.submit_answer(day, part, answer)?;
+    if verdict.is_right() {
+        ledger.record(day, part, input, answer);
+        ledger.save()?;
+    }
+    Ok(Submission::Judged(verdict))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+// Inputs are cached as "{cache_dir}/dayN.txt", which is where `run` looks for them; a cached day is never downloaded again
+pub fn fetch_input_cached<P: AsRef<Path>>(client: &Client, day: usize, cache_dir: P) -> Result<Fetched> {
+    let path = cache_dir.as_ref().join(format!("day{}.txt", day));
+    if path.exists() {
+        return Ok(Fetched::Cached(path))
+    }
+    let input = client.fetch_input(day)?;
+    let io_error = |e: std::io::Error| Error::Io { source: path.display().to_string(), reason: e.to_string() };
+    // Written to a temporary file first, so that an interrupted download never looks cached
+    let partial_path = path.with_extension("txt.partial");
+    fs::create_dir_all(cache_dir.as_ref()).map_err(io_error)?;
+    fs::write(&partial_path, input).map_err(io_error)?;
+    fs::rename(&partial_path, &path).map_err(io_error)?;
+    Ok(Fetched::Downloaded(path))
+}
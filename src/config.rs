@@ -0,0 +1,81 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    result,
+};
+use toml::value::{Table, Value};
+use super::{
+    error::{Error, Result},
+    get_day,
+};
+
+// Parameters of each day, replacing the values from the puzzle text; days and keys that are left out
+// keep their defaults:
+//
+// [day9]
+// preamble = 5
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    path: PathBuf,
+    days: BTreeMap<usize, Table>,
+}
+
+impl Config {
+    // A missing file leaves every day with its defaults
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let config_error = |reason: String| Error::Config { source: path.display().to_string(), reason };
+        let by_key: BTreeMap<String, Table> = match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| config_error(e.to_string()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(Error::Io { source: path.display().to_string(), reason: e.to_string() }),
+        };
+        let mut config = Config { path: path.to_path_buf(), days: BTreeMap::new() };
+        for (key, params) in by_key {
+            let day = parse_day(&key).map_err(config_error)?;
+            config.days.insert(day, params);
+            config.check(day).map_err(config_error)?;
+        }
+        Ok(config)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Overrides a single parameter from the command line, e.g. "day9.preamble=5". Values are read as TOML,
+    // or as a string if they aren't valid TOML, so that "day7.color=shiny gold" needs no extra quotes
+    pub fn set(&mut self, assignment: &str) -> Result<()> {
+        let config_error = |reason: String| Error::Config { source: format!("--set {}", assignment), reason };
+        let (key, value) = assignment.split_once('=')
+            .ok_or_else(|| config_error(String::from("expected an assignment like \"day9.preamble=5\"")))?;
+        let (day, name) = key.trim().split_once('.')
+            .ok_or_else(|| config_error(format!("expected a key like \"day9.preamble\", found {:?}", key)))?;
+        let day = parse_day(day).map_err(config_error)?;
+        let value = match toml::from_str::<Table>(&format!("value = {}", value)) {
+            Ok(mut table) => table.remove("value").expect("the value was just parsed"),
+            Err(_) => Value::String(String::from(value)),
+        };
+        self.days.entry(day).or_default().insert(String::from(name), value);
+        self.check(day).map_err(config_error)
+    }
+
+    // Parameters of a day, empty if it only uses its defaults
+    pub fn params(&self, day: usize) -> Table {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+
+    // Parameters are checked as soon as they're set, rather than when the day runs
+    fn check(&self, day: usize) -> result::Result<(), String> {
+        let puzzle = get_day(day).ok_or_else(|| format!("there is no day {}", day))?;
+        puzzle.check_params(&self.params(day)).map_err(|reason| format!("day{}: {}", day, reason))
+    }
+}
+
+fn parse_day(key: &str) -> result::Result<usize, String> {
+    key.strip_prefix("day")
+        .and_then(|day| day.parse::<usize>().ok())
+        .ok_or_else(|| format!("invalid table [{}], expected a day like [day1]", key))
+}
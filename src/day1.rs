@@ -1,37 +1,66 @@
+use serde::Deserialize;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    progress::Progress,
+    solver::Solver,
+};
 
-fn get_data() -> Vec<i32> {
-    super::file::read_file("./inputs/day1.txt").collect::<Vec<String>>()
-        .iter().map(|l| l.parse::<i32>().expect("line is not an int"))
-        .collect()
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    // Sum that the entries must add up to
+    pub target: i32,
 }
 
-pub fn main() {
-    let list = get_data();
-    // Part 1
-    // for j in 0..(list.len()-1) {
-    //     let second = list[j];
-    //     let third = 2020 - second;
-    //     for k in j+1..(list.len()) {
-    //         if list[k] == third {
-    //             println!("{} * {} = {}", second, third, second * third);
-    //             return;
-    //         }
-    //     }
-    // }
-    // Part 2
-    for i in 0..(list.len()-2) {
-        let first = list[i];
-        for j in i..(list.len()-1) {
-            let second = list[j];
-            let third = 2020 - first - second;
-            for k in j+1..(list.len()) {
-                if list[k] == third {
-                    println!("{} * {} * {} = {}", first, second, third, first * second * third);
-                    return;
+impl Default for Params {
+    fn default() -> Self {
+        Params { target: 2020 }
+    }
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = Vec<i32>;
+    type Params = Params;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(lines: Lines) -> Result<Vec<i32>> {
+        lines.map(|l| l.parse_at::<i32>(&l, "entry"))
+            .collect()
+    }
+
+    fn part1(list: &Vec<i32>) -> Result<i32> {
+        Self::part1_with(list, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part2(list: &Vec<i32>) -> Result<i32> {
+        Self::part2_with(list, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part1_with(list: &Vec<i32>, params: &Params, _progress: &Progress) -> Result<i32> {
+        for (j, &second) in list.iter().enumerate() {
+            let third = params.target - second;
+            if list[j+1..].contains(&third) {
+                debug!("{} + {} = {}", second, third, params.target);
+                return Ok(second * third);
+            }
+        }
+        Err(Error::solve(format!("no two entries sum to {}", params.target)))
+    }
+
+    fn part2_with(list: &Vec<i32>, params: &Params, _progress: &Progress) -> Result<i32> {
+        for (i, &first) in list.iter().enumerate() {
+            for (j, &second) in list.iter().enumerate().skip(i+1) {
+                let third = params.target - first - second;
+                if list[j+1..].contains(&third) {
+                    debug!("{} + {} + {} = {}", first, second, third, params.target);
+                    return Ok(first * second * third);
                 }
             }
         }
+        Err(Error::solve(format!("no three entries sum to {}", params.target)))
     }
-    println!("Not found!")
 }
-
@@ -1,26 +1,16 @@
 use std::{
-    collections::{HashMap},
     cmp::min,
 };
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    graph::Graph,
+    solver::{NoParams, Solver},
+};
 
-fn get_sorted_joltage_vec() -> Vec<usize> {
-    let mut adapter_list: Vec<usize> = super::file::read_file("./inputs/day10.txt")
-            .map(|n| n.parse::<usize>().expect("line is not an usize"))
-            .collect();
-    adapter_list.sort_unstable();
-    adapter_list.insert(0, 0); // Add plug's joltage
-    adapter_list.push(adapter_list.last().unwrap() + 3); // Add built-in adapter's joltage
-    adapter_list
-}
 
 // Part 1
-#[derive(Debug)]
-enum JoltageDifferencesError {
-    UnexpectedJoltageDifference(usize)
-}
-
-fn find_joltage_differences() -> Result<(usize, usize, usize), JoltageDifferencesError> {
-    let adapter_list = get_sorted_joltage_vec();
+pub fn find_joltage_differences(adapter_list: &[usize]) -> Result<(usize, usize, usize)> {
     let mut joltage_differences = (0usize, 0usize, 0usize);
     let mut iter = adapter_list.iter();
     let mut prev_adapter = iter.next().unwrap();
@@ -29,55 +19,64 @@ fn find_joltage_differences() -> Result<(usize, usize, usize), JoltageDifference
             1 => { joltage_differences.0 += 1; },
             2 => { joltage_differences.1 += 1; },
             3 => { joltage_differences.2 += 1; },
-            i => return Err(JoltageDifferencesError::UnexpectedJoltageDifference(i)),
+            i => return Err(Error::solve(format!("unexpected joltage difference of {} after {}", i, prev_adapter))),
         }
         prev_adapter = adapter;
     }
+    debug!("Differences of 1, 2 and 3 jolts: {:?}", joltage_differences);
     Ok(joltage_differences)
 }
 
 // Part 2
-fn get_reachability_tree(adapter_list: &Vec<usize>) -> Option<HashMap<usize, Vec<usize>>> {
-    let mut reachability_tree: HashMap<usize, Vec<usize>> = HashMap::with_capacity(adapter_list.len());
-    for i in 0..adapter_list.len() - 1 {
-        let adapter = adapter_list[i];
-        let mut reachability_vec: Vec<usize> = Vec::new();
-        for j in (i + 1)..min(i + 4, adapter_list.len()) {
-            if adapter_list[j] - adapter > 3 {
-                break
-            } else if adapter_list[j] <= adapter {
-                return None
-            }
-            reachability_vec.push(adapter_list[j]);
+// Each adapter has an edge to every adapter that can be plugged into it
+fn get_reachability_graph(adapter_list: &[usize]) -> Graph<usize> {
+    let mut graph: Graph<usize> = Graph::new();
+    for (i, adapter) in adapter_list.iter().enumerate() {
+        graph.intern(*adapter);
+        for next_adapter in adapter_list[(i + 1)..min(i + 4, adapter_list.len())].iter().take_while(|a| *a - adapter <= 3) {
+            graph.add_labeled_edge(*adapter, *next_adapter, ());
         }
-        reachability_tree.insert(adapter, reachability_vec);
     }
-    Some(reachability_tree)
+    graph
 }
 
-fn find_possible_combinations() -> Option<usize> {
-    let adapter_list = get_sorted_joltage_vec();
-    let reachability_tree = get_reachability_tree(&adapter_list).unwrap();
-    let mut reachability_count: HashMap<usize, usize> = HashMap::with_capacity(reachability_tree.len());
-    let mut iter = adapter_list.iter().rev();
-    reachability_count.insert(*iter.next().unwrap(), 1);
-    for adapter in iter {
-        let possibilities = reachability_tree.get(adapter).unwrap().iter()
-            .fold(0usize, |acc, a| acc + reachability_count.get(a).unwrap());
-        reachability_count.insert(*adapter, possibilities);
+pub fn find_possible_combinations(adapter_list: &[usize]) -> Result<usize> {
+    if adapter_list.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(Error::solve("adapters must have distinct joltages"))
     }
-    Some(*reachability_count.get(&0).unwrap())
+    let graph = get_reachability_graph(adapter_list);
+    debug!("{} adapters, with {} ways to plug one into another", graph.len(), graph.nodes().map(|id| graph.successors(id).len()).sum::<usize>());
+    let plug = graph.id(&adapter_list[0]).unwrap();
+    let device = graph.id(adapter_list.last().unwrap()).unwrap();
+    // Joltages only increase along the edges, so there can't be a cycle
+    Ok(graph.count_paths(plug, device).expect("adapter graphs are acyclic"))
 }
 
-pub fn main () {
-    // Part 1
-    // let (diff_one, diff_two, diff_three) = find_joltage_differences().unwrap();
-    // println!("Diff by 1: {}", diff_one);
-    // println!("Diff by 2: {}", diff_two);
-    // println!("Diff by 3: {}", diff_three);
-    // println!("  (diff by 1) * (diff by 3) = {}", diff_one * diff_three);
+pub struct Day10;
+
+impl Solver for Day10 {
+    // Sorted joltages, including the plug and the built-in adapter
+    type Input = Vec<usize>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
 
-    // Part 2
-    let possibilities = find_possible_combinations().unwrap();
-    println!("Possible arrangements: {}", possibilities);
+    fn parse(lines: Lines) -> Result<Vec<usize>> {
+        let mut adapter_list: Vec<usize> = lines
+                .map(|n| n.parse_at::<usize>(&n, "joltage"))
+                .collect::<Result<_>>()?;
+        adapter_list.sort_unstable();
+        adapter_list.insert(0, 0); // Add plug's joltage
+        adapter_list.push(adapter_list.last().unwrap() + 3); // Add built-in adapter's joltage
+        Ok(adapter_list)
+    }
+
+    fn part1(adapter_list: &Vec<usize>) -> Result<usize> {
+        let (diff_one, _, diff_three) = find_joltage_differences(adapter_list)?;
+        Ok(diff_one * diff_three)
+    }
+
+    fn part2(adapter_list: &Vec<usize>) -> Result<usize> {
+        find_possible_combinations(adapter_list)
+    }
 }
@@ -1,324 +1,113 @@
-use std::{
-    iter::from_fn,
-    mem
+use super::{
+    error::Result,
+    file::Lines,
+    geometry::{Direction, Turn, Vec2},
+    solver::{NoParams, Solver},
 };
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-enum DirectionX {
-    East,
-    West,
-}
-
-impl DirectionX {
-    fn get_direction(& self) -> Direction {
-        match self {
-            DirectionX::East => Direction::East,
-            DirectionX::West => Direction::West,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum DirectionY {
-    North,
-    South,
-}
-
-impl DirectionY {
-    fn get_direction(& self) -> Direction {
-        match self {
-            DirectionY::North => Direction::North,
-            DirectionY::South => Direction::South,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum Direction {
-    East,
-    South,
-    West,
-    North,
-}
-
-impl Direction {
-    fn get_direction_x(& self) -> DirectionX {
-        match self {
-            Direction::East => DirectionX::East,
-            Direction::West => DirectionX::West,
-            _ => panic!("Not DirectionX"),
-        }
-    }
-
-    fn get_direction_y(& self) -> DirectionY {
-        match self {
-            Direction::North => DirectionY::North,
-            Direction::South => DirectionY::South,
-            _ => panic!("Not DirectionY"),
-        }
-    }
-
-    fn rotate(& self, rotation: &Rotation, value: &usize) -> Direction {
-        match self {
-            Direction::East => {
-                match rotation {
-                    Rotation::Left => {
-                        match value {
-                            90 => Direction::North,
-                            180 => Direction::West,
-                            270 => Direction::South,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                    Rotation::Right => {
-                        match value {
-                            90 => Direction::South,
-                            180 => Direction::West,
-                            270 => Direction::North,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                }
-            },
-            Direction::South => {
-                match rotation {
-                    Rotation::Left => {
-                        match value {
-                            90 => Direction::East,
-                            180 => Direction::North,
-                            270 => Direction::West,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                    Rotation::Right => {
-                        match value {
-                            90 => Direction::West,
-                            180 => Direction::North,
-                            270 => Direction::East,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                }
-            },
-            Direction::West => {
-                match rotation {
-                    Rotation::Left => {
-                        match value {
-                            90 => Direction::South,
-                            180 => Direction::East,
-                            270 => Direction::North,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                    Rotation::Right => {
-                        match value {
-                            90 => Direction::North,
-                            180 => Direction::East,
-                            270 => Direction::South,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                }
-            },
-            Direction::North => {
-                match rotation {
-                    Rotation::Left => {
-                        match value {
-                            90 => Direction::West,
-                            180 => Direction::South,
-                            270 => Direction::East,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                    Rotation::Right => {
-                        match value {
-                            90 => Direction::East,
-                            180 => Direction::South,
-                            270 => Direction::West,
-                            _ => panic!("Invalid rotation value")
-                        }
-                    }
-                }
-            },
-        }
-    }
-}
-
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum Rotation {
-    Left,
-    Right,
-}
-
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum NavigationInstruction {
+pub enum NavigationInstruction {
     Move(Direction, usize),
-    Turn(Rotation, usize),
+    // Turns are in quarter turns, i.e. 90 degrees each
+    Turn(Turn, usize),
     GoForward(usize),
 }
 
-fn get_instructions() -> Vec<NavigationInstruction> {
-    super::file::read_file("./inputs/day12.txt")
-        .map(|l| -> NavigationInstruction {
-            match l.split_at(1) {
-                ("N", value) => NavigationInstruction::Move(Direction::North, value.parse::<usize>().unwrap()),
-                ("S", value) => NavigationInstruction::Move(Direction::South, value.parse::<usize>().unwrap()),
-                ("E", value) => NavigationInstruction::Move(Direction::East, value.parse::<usize>().unwrap()),
-                ("W", value) => NavigationInstruction::Move(Direction::West, value.parse::<usize>().unwrap()),
-                ("L", value) => NavigationInstruction::Turn(Rotation::Left, value.parse::<usize>().unwrap()),
-                ("R", value) => NavigationInstruction::Turn(Rotation::Right, value.parse::<usize>().unwrap()),
-                ("F", value) => NavigationInstruction::GoForward(value.parse::<usize>().unwrap()),
-                _ => panic!("Invalid instruction")
-            }
-        }).collect()
-}
-
 #[derive(Debug)]
 struct ShipPart1 {
-    pos_x: (DirectionX, usize),
-    pos_y: (DirectionY, usize),
+    position: Vec2,
     direction: Direction,
 }
 
-fn move_object(pos_x: (DirectionX, usize), pos_y: (DirectionY, usize), direction: &Direction, value: &usize) -> ((DirectionX, usize), (DirectionY, usize)) {
-    match direction {
-        Direction::East => {
-            if pos_x.0 == DirectionX::West {
-                if pos_x.1 < *value {
-                    return ((DirectionX::East, value - pos_x.1), pos_y)
-                } else {
-                    return ((DirectionX::West, pos_x.1 - value), pos_y)
-                }
-            } else {
-                return ((DirectionX::East, value + pos_x.1), pos_y)
-            }
-        },
-        Direction::West => {
-            if pos_x.0 == DirectionX::East {
-                if pos_x.1 < *value {
-                    return ((DirectionX::West, value - pos_x.1), pos_y)
-                } else {
-                    return ((DirectionX::East, pos_x.1 - value), pos_y)
-                }
-            } else {
-                return ((DirectionX::West, value + pos_x.1), pos_y)
-            }
-        },
-        Direction::North => {
-            if pos_y.0 == DirectionY::South {
-                if pos_y.1 < *value {
-                    return (pos_x, (DirectionY::North, value - pos_y.1))
-                } else {
-                    return (pos_x, (DirectionY::South, pos_y.1 - value))
-                }
-            } else {
-                return (pos_x, (DirectionY::North, value + pos_y.1))
-            }
-        },
-        Direction::South => {
-            if pos_y.0 == DirectionY::North {
-                if pos_y.1 < *value {
-                    return (pos_x, (DirectionY::South, value - pos_y.1))
-                } else {
-                    return (pos_x, (DirectionY::North, pos_y.1 - value))
-                }
-            } else {
-                return (pos_x, (DirectionY::South, value + pos_y.1))
-            }
-        },
-    }
-}
-
 impl ShipPart1 {
     fn run_instruction(&mut self, instruction: &NavigationInstruction) {
         match instruction {
             NavigationInstruction::Move(direction, value) => {
-                let pos_x = mem::replace(&mut self.pos_x, (DirectionX::East, 0));
-                let pos_y = mem::replace(&mut self.pos_y, (DirectionY::North, 0));
-                let (pos_x, pos_y) = move_object(pos_x, pos_y, direction, value);
-                self.pos_x = pos_x;
-                self.pos_y = pos_y;
+                self.position += direction.vector() * *value as isize
             },
-            NavigationInstruction::Turn(rotation, value) => {
-                self.direction = self.direction.rotate(rotation, value)
+            NavigationInstruction::Turn(turn, quarter_turns) => {
+                self.direction = self.direction.turn(*turn, *quarter_turns)
             },
             NavigationInstruction::GoForward(value) => {
-                self.run_instruction(&NavigationInstruction::Move(self.direction.clone(), *value))
+                self.position += self.direction.vector() * *value as isize
             }
         }
     }
 }
 
+// The waypoint is relative to the ship, so it rotates about the origin
 #[derive(Debug)]
 struct ShipPart2 {
-    pos_x: (DirectionX, usize),
-    pos_y: (DirectionY, usize),
-    waypoint: ((DirectionX, usize), (DirectionY, usize)),
+    position: Vec2,
+    waypoint: Vec2,
 }
 
 impl ShipPart2 {
-    fn move_ship(&mut self, direction: &Direction, value: &usize) {
-        let pos_x = mem::replace(&mut self.pos_x, (DirectionX::East, 0));
-        let pos_y = mem::replace(&mut self.pos_y, (DirectionY::North, 0));
-        let (pos_x, pos_y) = move_object(pos_x, pos_y, direction, value);
-        self.pos_x = pos_x;
-        self.pos_y = pos_y;
-    }
-
     fn run_instruction(&mut self, instruction: &NavigationInstruction) {
         match instruction {
             NavigationInstruction::Move(direction, value) => {
-                let (pos_x, pos_y) = mem::replace(&mut self.waypoint, ((DirectionX::East, 0), (DirectionY::North, 0)));
-                self.waypoint = move_object(pos_x, pos_y, direction, value);
+                self.waypoint += direction.vector() * *value as isize
             },
-            NavigationInstruction::Turn(rotation, value) => {
-                if *value == 90 || *value == 270 {
-                    let waypoint_dir_y = self.waypoint.0.0.get_direction().rotate(rotation, value).get_direction_y();
-                    let waypoint_dir_x = self.waypoint.1.0.get_direction().rotate(rotation, value).get_direction_x();
-                    self.waypoint = (
-                        (waypoint_dir_x, self.waypoint.1.1),
-                        (waypoint_dir_y, self.waypoint.0.1),
-                    );
-                } else {
-                    let waypoint_dir_x = self.waypoint.0.0.get_direction().rotate(rotation, value).get_direction_x();
-                    let waypoint_dir_y = self.waypoint.1.0.get_direction().rotate(rotation, value).get_direction_y();
-                    self.waypoint = (
-                        (waypoint_dir_x, self.waypoint.0.1),
-                        (waypoint_dir_y, self.waypoint.1.1),
-                    );
-                }
+            NavigationInstruction::Turn(turn, quarter_turns) => {
+                self.waypoint = self.waypoint.rotate(*turn, *quarter_turns)
             },
             NavigationInstruction::GoForward(value) => {
-                match self.waypoint.0.0 {
-                    DirectionX::East => self.move_ship(&Direction::East, &(value * self.waypoint.0.1)),
-                    DirectionX::West => self.move_ship(&Direction::West, &(value * self.waypoint.0.1)),
-                }
-                match self.waypoint.1.0 {
-                    DirectionY::North => self.move_ship(&Direction::North, &(value * self.waypoint.1.1)),
-                    DirectionY::South => self.move_ship(&Direction::South, &(value * self.waypoint.1.1)),
-                }
+                self.position += self.waypoint * *value as isize
             }
         }
     }
 }
 
-pub fn main () {
-    let instructions = get_instructions();
-    // println!("{:?}", instructions);
-    // let mut ship = ShipPart1 {
-    //     pos_x: (DirectionX::East, 0),
-    //     pos_y: (DirectionY::North, 0),
-    //     direction: Direction::East
-    // };
-    let mut ship = ShipPart2 {
-        pos_x: (DirectionX::East, 0),
-        pos_y: (DirectionY::North, 0),
-        waypoint: ((DirectionX::East, 10), (DirectionY::North, 1)),
-    };
-    for i in instructions.iter() {
-        ship.run_instruction(i);
-        // println!("{:?} -> {:?}", i, ship)
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input = Vec<NavigationInstruction>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(lines: Lines) -> Result<Vec<NavigationInstruction>> {
+        lines.map(|l| -> Result<NavigationInstruction> {
+            let action_length = l.chars().next().ok_or_else(|| l.error(1, "expected a navigation instruction"))?.len_utf8();
+            let (action, value) = l.split_at(action_length);
+            let amount = l.parse_at::<usize>(value, "instruction value")?;
+            if (action == "L" || action == "R") && ![90, 180, 270].contains(&amount) {
+                return Err(l.error_at(value, "turns must be 90, 180 or 270 degrees"))
+            }
+            match action {
+                "N" => Ok(NavigationInstruction::Move(Direction::North, amount)),
+                "S" => Ok(NavigationInstruction::Move(Direction::South, amount)),
+                "E" => Ok(NavigationInstruction::Move(Direction::East, amount)),
+                "W" => Ok(NavigationInstruction::Move(Direction::West, amount)),
+                "L" => Ok(NavigationInstruction::Turn(Turn::Left, amount / 90)),
+                "R" => Ok(NavigationInstruction::Turn(Turn::Right, amount / 90)),
+                "F" => Ok(NavigationInstruction::GoForward(amount)),
+                _ => Err(l.error(1, format!("invalid action {:?}", action))),
+            }
+        }).collect()
+    }
+
+    fn part1(instructions: &Vec<NavigationInstruction>) -> Result<usize> {
+        let mut ship = ShipPart1 {
+            position: Vec2::ZERO,
+            direction: Direction::East,
+        };
+        for i in instructions.iter() {
+            ship.run_instruction(i);
+            trace!("{:?} -> {:?}", i, ship);
+        }
+        Ok(ship.position.manhattan())
+    }
+
+    fn part2(instructions: &Vec<NavigationInstruction>) -> Result<usize> {
+        let mut ship = ShipPart2 {
+            position: Vec2::ZERO,
+            waypoint: Direction::East.vector() * 10 + Direction::North.vector(),
+        };
+        for i in instructions.iter() {
+            ship.run_instruction(i);
+            trace!("{:?} -> {:?}", i, ship);
+        }
+        Ok(ship.position.manhattan())
     }
-    println!("Final position: {:?} ; {:?}", ship.pos_x, ship.pos_y);
-    println!("  Manhattan distance: {}", ship.pos_x.1 + ship.pos_y.1)
 }
@@ -1,25 +1,13 @@
-use std::{
-    iter::from_fn,
-    mem
-};
 use num_integer::lcm;
+use serde::Deserialize;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    progress::Progress,
+    solver::Solver,
+};
 
-fn get_bus_schedule() -> (usize, Vec<Option<usize>>) {
-    let mut file_iter = super::file::read_file("./inputs/day13.txt");
-    let timestamp = file_iter.next().unwrap().parse::<usize>().expect("Invalid timestamp");
-    let bus_id_list = file_iter.next().unwrap()
-        .split(',')
-        .map(|s| {
-            match s {
-                "x" => None,
-                id => Some(id.parse::<usize>().expect("Invalid bus ID")),
-            }
-        })
-        .collect();
-    (timestamp, bus_id_list)
-}
-
-fn get_departure_time(timestamp: usize, bus_id: usize) -> usize {
+pub fn get_departure_time(timestamp: usize, bus_id: usize) -> usize {
     match timestamp % bus_id {
         0 => timestamp,
         x => timestamp + bus_id - x,
@@ -27,41 +15,48 @@ fn get_departure_time(timestamp: usize, bus_id: usize) -> usize {
 }
 
 // Part 1
-fn find_smallest_wait_time(schedule: &(usize, Vec<usize>)) -> (usize, usize) {
+pub fn find_smallest_wait_time(schedule: &(usize, Vec<usize>)) -> Result<(usize, usize)> {
     let (timestamp, bus_id_list) = schedule;
-    let result = bus_id_list.iter().map(|id| (get_departure_time(*timestamp, *id), id)).min_by_key(|x| x.0).expect("");
-    (result.0 - timestamp, result.1.clone())
+    let result = bus_id_list.iter().map(|id| (get_departure_time(*timestamp, *id), id)).min_by_key(|x| x.0)
+        .ok_or_else(|| Error::solve("there are no buses in service"))?;
+    Ok((result.0 - timestamp, *result.1))
 }
 
 // Part 2
-fn find_sequential_bus_offsets(schedule: &Vec<Option<usize>>) -> usize {
-    find_sequential_bus_offsets_with_initial_offset(schedule, &0)
+pub fn find_sequential_bus_offsets(schedule: &[Option<usize>]) -> Result<usize> {
+    find_sequential_bus_offsets_with_initial_offset(schedule, &0, &Progress::unlimited())
 }
 
-fn find_sequential_bus_offsets_with_initial_offset(schedule: &Vec<Option<usize>>, offset: &usize) -> usize {
+// Progress is checked every this many offsets; IDs that aren't coprime may never line up, so this can run forever
+const OFFSETS_PER_CHECK: usize = 1 << 12;
+
+pub fn find_sequential_bus_offsets_with_initial_offset(schedule: &[Option<usize>], offset: &usize, progress: &Progress) -> Result<usize> {
     let enumerated_valid_bus_ids: Vec<(usize, usize)> =
         schedule.iter()
             .enumerate()
             .filter(|(_, id)| id.is_some())
             .map(|(i, id)| (i, id.expect("Invalid bus ID")))
             .collect();
-    // println!("{:?}", enumerated_valid_bus_ids);
-    let (step_pos, mut step) = enumerated_valid_bus_ids.iter().max_by_key(|(_, id)| id).expect("No max bus ID");
-    let mut curr_offset = offset - (offset % step) + step - step_pos; // TODO: Improve this? In worst case, it only does one extra check...
-    // println!("{}", curr_offset);
+    debug!("Buses by offset: {:?}", enumerated_valid_bus_ids);
+    let (step_pos, mut step) = enumerated_valid_bus_ids.iter().max_by_key(|(_, id)| id)
+        .ok_or_else(|| Error::solve("there are no buses in service"))?;
+    // The first offset from the given one where the bus with the largest ID lines up
+    let residue = (step - step_pos % step) % step;
+    let mut curr_offset = offset + (residue + step - offset % step) % step;
+    debug!("Starting from offset {}, in steps of {}", curr_offset, step);
+    let mut checked_offsets = 0usize;
     loop {
-        // First attempt: Naïve checking. Too slow!
-        // if enumerated_valid_bus_ids.iter().all(|(i, id)| (curr_offset + i) % id == 0) {
-        //     break
-        // } else {
-        //     curr_offset += step;
-        // }
+        if checked_offsets.is_multiple_of(OFFSETS_PER_CHECK) {
+            progress.check(checked_offsets, None)?;
+        }
+        checked_offsets += 1;
 
-        // Second attempt: Increase step with Lowest Common Multiple to minimize search field
+        // Increase step with Lowest Common Multiple to minimize search field
         let mut found = true;
         for (i, bus_id) in enumerated_valid_bus_ids.iter() {
-            if (curr_offset + i) % bus_id == 0 {
+            if (curr_offset + i).is_multiple_of(*bus_id) {
                 step = lcm(step, *bus_id);
+                trace!("Bus {} departs at offset {} + {}; now in steps of {}", bus_id, curr_offset, i, step);
             } else {
                 found = false;
                 break
@@ -73,19 +68,60 @@ fn find_sequential_bus_offsets_with_initial_offset(schedule: &Vec<Option<usize>>
             curr_offset += step;
         }
     }
-    curr_offset
+    Ok(curr_offset)
 }
 
-pub fn main () {
-    let schedule = get_bus_schedule();
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    // Part 2 only looks for timestamps from here on, since the puzzle says the answer is larger
+    pub start: usize,
+}
 
-    // Part 1
-    // let (time_to_leave, bus_id) = find_smallest_wait_time(&(schedule.0, schedule.1.iter().flatten().collect()));
-    // println!("Bus ID {} leaving in {} minutes", bus_id, time_to_leave);
-    // println!(" - Product is {}", bus_id * time_to_leave)
+impl Default for Params {
+    fn default() -> Self {
+        Params { start: 100000000000000 }
+    }
+}
 
-    // Part 2
-    // let offset = find_sequential_bus_offsets(&schedule.1);
-    let offset = find_sequential_bus_offsets_with_initial_offset(&schedule.1, &100000000000000);
-    println!("Found offset: {}", offset)
+pub struct Day13;
+
+impl Solver for Day13 {
+    // Earliest timestamp, and bus IDs where "x" is None
+    type Input = (usize, Vec<Option<usize>>);
+    type Params = Params;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(mut lines: Lines) -> Result<(usize, Vec<Option<usize>>)> {
+        let timestamp_line = lines.expect_line("the earliest timestamp")?;
+        let timestamp = timestamp_line.parse_at::<usize>(&timestamp_line, "timestamp")?;
+        let bus_id_line = lines.expect_line("the list of bus IDs")?;
+        let bus_id_list = bus_id_line
+            .split(',')
+            .map(|s| {
+                match s {
+                    "x" => Ok(None),
+                    id => match bus_id_line.parse_at::<usize>(id, "bus ID")? {
+                        0 => Err(bus_id_line.error_at(id, "bus IDs must be greater than zero")),
+                        id => Ok(Some(id)),
+                    },
+                }
+            })
+            .collect::<Result<_>>()?;
+        Ok((timestamp, bus_id_list))
+    }
+
+    fn part1(schedule: &(usize, Vec<Option<usize>>)) -> Result<usize> {
+        let (time_to_leave, bus_id) = find_smallest_wait_time(&(schedule.0, schedule.1.iter().flatten().copied().collect()))?;
+        Ok(bus_id * time_to_leave)
+    }
+
+    fn part2(schedule: &(usize, Vec<Option<usize>>)) -> Result<usize> {
+        Self::part2_with(schedule, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part2_with(schedule: &(usize, Vec<Option<usize>>), params: &Params, progress: &Progress) -> Result<usize> {
+        find_sequential_bus_offsets_with_initial_offset(&schedule.1, &params.start, progress)
+    }
 }
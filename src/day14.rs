@@ -4,28 +4,27 @@ use std::{
     iter::from_fn,
 };
 use regex::Regex;
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    solver::{NoParams, Solver},
+};
 
 #[derive(Debug, Clone)]
-struct MaskDataPart1 {
+pub struct MaskData {
     and_mask: usize,
     or_mask: usize,
+    floating_bits: Vec<usize>
 }
 
-impl MaskDataPart1 {
-    fn apply_to(&self, value: &usize) -> usize {
+impl MaskData {
+    // Part 1
+    pub fn apply_to_value(&self, value: &usize) -> usize {
         (value & self.and_mask) | self.or_mask
     }
-}
-
-#[derive(Debug, Clone)]
-struct MaskDataPart2 {
-    or_mask: usize,
-    floating_bits: Vec<usize>
-}
 
-impl MaskDataPart2 {
-    fn apply_to<'a>(&'a self, address: &'a usize) -> impl Iterator<Item=usize> + 'a {
-        // TODO
+    // Part 2
+    pub fn apply_to_address<'a>(&'a self, address: &'a usize) -> impl Iterator<Item=usize> + 'a {
         let mut combination = 0usize;
         let base_address = *address | self.or_mask;
         from_fn(move || {
@@ -48,87 +47,96 @@ impl MaskDataPart2 {
 }
 
 #[derive(Debug, Clone)]
-enum Instruction {
-    MaskPart1(MaskDataPart1),
-    MaskPart2(MaskDataPart2),
+pub enum Instruction {
+    Mask(MaskData),
     Mem(usize, usize),
 }
 
-impl TryFrom<String> for Instruction {
-    type Error = ();
+impl TryFrom<&Line> for Instruction {
+    type Error = Error;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let mut split = value.splitn(2, " = ");
-        let operation = split.next().expect("missing operation in instruction");
-        let argument = split.next().expect("missing argument in instruction");
+    fn try_from(line: &Line) -> Result<Self> {
+        let mut split = line.splitn(2, " = ");
+        let operation = split.next().expect("splitn always returns at least one item");
+        let argument = split.next().ok_or_else(|| line.error(line.len() + 1, "expected \" = \" followed by an argument"))?;
         match operation {
             "mask" => {
-                // Part 1
-                // match (usize::from_str_radix(&argument.replace('X', "1"), 2), usize::from_str_radix(&argument.replace('X', "0"), 2)) {
-                //     (Ok(and_mask), Ok(or_mask)) => Ok(Instruction::MaskPart1(MaskDataPart1{
-                //         and_mask: and_mask | ((-1isize as usize) << &argument.len()),
-                //         or_mask: or_mask,
-                //     })),
-                //     _ => Err(())
-                // }
-                match usize::from_str_radix(&argument.replace('X', "0"), 2) {
-                    Ok(or_mask) => {
-                        let floating_bits = (&argument).chars().rev().enumerate().filter(|(_, c)| c == &'X').map(|(i, _)| i).collect();
-                        Ok(Instruction::MaskPart2(MaskDataPart2{
-                            or_mask: or_mask,
-                            floating_bits: floating_bits,
-                        }))
-                    },
-                    _ => Err(())
+                if let Some((i, c)) = argument.chars().enumerate().find(|(_, c)| !"X01".contains(*c)) {
+                    return Err(line.error(line.chars().count() - argument.chars().count() + i + 1, format!("invalid mask bit {:?}", c)))
+                }
+                if argument.len() != 36 {
+                    return Err(line.error_at(argument, format!("expected a 36-bit mask, found {} bits", argument.len())))
                 }
+                let and_mask = usize::from_str_radix(&argument.replace('X', "1"), 2).expect("mask was already validated");
+                let or_mask = usize::from_str_radix(&argument.replace('X', "0"), 2).expect("mask was already validated");
+                let floating_bits = argument.chars().rev().enumerate().filter(|(_, c)| c == &'X').map(|(i, _)| i).collect();
+                Ok(Instruction::Mask(MaskData {
+                    and_mask: and_mask | ((-1isize as usize) << argument.len()),
+                    or_mask,
+                    floating_bits,
+                }))
             },
             mem_op => {
                 lazy_static! {
                     static ref RE: Regex = Regex::new(r"^mem\[(\d+)\]$").expect("invalid mem regex");
                 }
-                match RE.captures(&mem_op) {
-                    Some(caps) => {
-                        match (caps[1].parse(), argument.parse()) {
-                            (Ok(mem_address), Ok(mem_value)) => Ok(Instruction::Mem(mem_address, mem_value)),
-                            _ => Err(())
-                        }
-                    },
-                    _ => Err(()),
-                }
+                let caps = RE.captures(mem_op)
+                    .ok_or_else(|| line.error(1, format!("invalid operation {:?}, expected \"mask\" or \"mem[N]\"", mem_op)))?;
+                let mem_address = line.parse_at(caps.get(1).unwrap().as_str(), "memory address")?;
+                let mem_value = line.parse_at(argument, "memory value")?;
+                Ok(Instruction::Mem(mem_address, mem_value))
             }
         }
     }
 }
 
-fn run_program() -> HashMap<usize, usize> {
+pub fn run_program(program: &[Instruction], write: fn(&mut HashMap<usize, usize>, &MaskData, usize, usize)) -> Result<HashMap<usize, usize>> {
     let mut memory: HashMap<usize, usize> = HashMap::new();
-    let mut program = super::file::read_file("./inputs/day14.txt").map(|l| Instruction::try_from(l).expect("invalid instruction"));
-    let first_instruction = program.next();
-    // println!("{:?}", first_instruction);
-    let mut current_mask = match first_instruction {
-        // Some(Instruction::MaskPart1(data)) => data,
-        Some(Instruction::MaskPart2(data)) => data,
-        _ => panic!("First instruction is not a valid mask"),
+    let mut program = program.iter();
+    let mut current_mask = match program.next() {
+        Some(Instruction::Mask(data)) => data,
+        _ => return Err(Error::solve("the program must start by setting a mask")),
     };
     for instruction in program {
-        // println!("{:?}", instruction);
+        trace!("{:?}", instruction);
         match instruction {
-            // Instruction::MaskPart1(mask_data) => current_mask = mask_data,
-            Instruction::MaskPart2(mask_data) => current_mask = mask_data,
-            Instruction::Mem(address, value) => {
-                // memory.insert(address, current_mask.apply_to(&value));
-                for addr in current_mask.apply_to(&address) {
-                    memory.insert(addr, value);
-                }
-            }
-            _ => panic!("Unexpected instruction"),
+            Instruction::Mask(mask_data) => current_mask = mask_data,
+            Instruction::Mem(address, value) => write(&mut memory, current_mask, *address, *value),
         }
     }
-    memory
+    debug!("{} memory addresses written", memory.len());
+    Ok(memory)
 }
 
-pub fn main () {
-    let memory = run_program();
-    // println!("{:?}", memory);
-    println!("Sum of values in memory: {}", memory.values().sum::<usize>())
+// Part 1
+pub fn write_masked_value(memory: &mut HashMap<usize, usize>, mask: &MaskData, address: usize, value: usize) {
+    memory.insert(address, mask.apply_to_value(&value));
+}
+
+// Part 2
+pub fn write_to_masked_addresses(memory: &mut HashMap<usize, usize>, mask: &MaskData, address: usize, value: usize) {
+    for addr in mask.apply_to_address(&address) {
+        memory.insert(addr, value);
+    }
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input = Vec<Instruction>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(lines: Lines) -> Result<Vec<Instruction>> {
+        lines.map(|l| Instruction::try_from(&l)).collect()
+    }
+
+    fn part1(program: &Vec<Instruction>) -> Result<usize> {
+        Ok(run_program(program, write_masked_value)?.values().sum())
+    }
+
+    fn part2(program: &Vec<Instruction>) -> Result<usize> {
+        Ok(run_program(program, write_to_masked_addresses)?.values().sum())
+    }
 }
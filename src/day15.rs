@@ -1,23 +1,38 @@
 use std::{
     collections::HashMap,
 };
+use log::Level;
+use serde::Deserialize;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    progress::Progress,
+    solver::Solver,
+};
 
-fn get_starting_numbers() -> Vec<usize> {
-    super::file::read_file("./inputs/day15.txt").next().unwrap()
-        .split(',')
-        .map(|s| s.parse::<usize>().expect("not a valid number"))
-        .collect()
-}
+// Progress is checked every this many turns, since reading the clock each turn would slow the game down
+const TURNS_PER_CHECK: usize = 1 << 16;
 
-fn run_memory_game(last_turn: usize) -> usize {
+pub fn run_memory_game(starting_numbers: &[usize], last_turn: usize, progress: &Progress) -> Result<usize> {
+    // Only reachable with other turns in the config file
+    if last_turn == 0 {
+        return Err(Error::solve("turns start at 1"))
+    }
+    if last_turn <= starting_numbers.len() {
+        return Ok(starting_numbers[last_turn - 1])
+    }
     let mut last_spoken: HashMap<usize, usize> = HashMap::new();
-    let starting_numbers = get_starting_numbers();
     for (i, starting_number) in starting_numbers.iter().enumerate() {
         last_spoken.insert(*starting_number, i + 1);
     }
     let mut curr_turn = starting_numbers.len() + 1;
     let mut curr_value = 0usize;
+    // Checked once, since this loop runs millions of times
+    let tracing = log_enabled!(Level::Trace);
     while curr_turn < last_turn {
+        if curr_turn.is_multiple_of(TURNS_PER_CHECK) {
+            progress.check(curr_turn, Some(last_turn))?;
+        }
         let entry = last_spoken.entry(curr_value).or_insert(0);
         curr_value = match &entry {
             0 => 0,
@@ -25,14 +40,56 @@ fn run_memory_game(last_turn: usize) -> usize {
         };
         *entry = curr_turn;
         curr_turn += 1;
-        // println!("{}) {}! {:?}", curr_turn, curr_value, last_spoken);
+        if tracing {
+            trace!("{}) {}!", curr_turn, curr_value);
+        }
+    }
+    Ok(curr_value)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    // Turn whose number is the answer of each part
+    pub part1_turns: usize,
+    pub part2_turns: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { part1_turns: 2020, part2_turns: 30000000 }
     }
-    curr_value
 }
 
-pub fn main () {
-    // let turns: usize = 2020; // Part 1
-    let turns: usize = 30000000; // Part 2; a bit slow, but still under 30 seconds
-    let final_value = run_memory_game(turns);
-    println!("The {}th value is: {}", turns, final_value);
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input = Vec<usize>;
+    type Params = Params;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(mut lines: Lines) -> Result<Vec<usize>> {
+        let line = lines.expect_line("the starting numbers")?;
+        line.split(',')
+            .map(|s| line.parse_at::<usize>(s, "starting number"))
+            .collect()
+    }
+
+    fn part1(starting_numbers: &Vec<usize>) -> Result<usize> {
+        Self::part1_with(starting_numbers, &Params::default(), &Progress::unlimited())
+    }
+
+    // A bit slow, but still under 30 seconds
+    fn part2(starting_numbers: &Vec<usize>) -> Result<usize> {
+        Self::part2_with(starting_numbers, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part1_with(starting_numbers: &Vec<usize>, params: &Params, progress: &Progress) -> Result<usize> {
+        run_memory_game(starting_numbers, params.part1_turns, progress)
+    }
+
+    fn part2_with(starting_numbers: &Vec<usize>, params: &Params, progress: &Progress) -> Result<usize> {
+        run_memory_game(starting_numbers, params.part2_turns, progress)
+    }
 }
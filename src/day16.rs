@@ -1,60 +1,34 @@
-use std::{
-    collections::HashMap,
-};
 use regex::Regex;
-// use permutohedron::{
-//     heap_recursive,
-//     control::Control,
-// };
+use serde::Deserialize;
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    graph::{Graph, NodeId},
+    progress::Progress,
+    solver::Solver,
+};
 
 #[derive(Debug, Clone)]
-struct Field {
-    name: String,
-    possible_ranges: [(usize, usize); 2],
+pub struct Field {
+    pub name: String,
+    pub possible_ranges: [(usize, usize); 2],
 }
 
-fn parse_data() -> (Vec<Field>, Vec<usize>, Vec<Vec<usize>>) {
-    let mut lines = super::file::read_file("./inputs/day16.txt");
-    
-    // Parse fields
-    let mut fields: Vec<Field> = Vec::new();
-    loop {
-        match &lines.next().unwrap()[..] {
-            "" => break,
-            line => {
-                lazy_static! {
-                    static ref RE: Regex = Regex::new(r"^([^:]+): (\d+)-(\d+) or (\d+)-(\d+)$").expect("invalid field regex");
-                }
-                let caps = RE.captures(&line).expect("invalid field");
-                let name = caps[1].to_string();
-                let first_range = (caps[2].parse::<usize>().unwrap(), caps[3].parse::<usize>().unwrap());
-                let second_range = (caps[4].parse::<usize>().unwrap(), caps[5].parse::<usize>().unwrap());
-                fields.push(Field {
-                    name: name,
-                    possible_ranges: [first_range, second_range],
-                });
-            },
-        }
-    }
-
-    // Parse own ticket
-    assert_eq!(&lines.next().unwrap()[..], "your ticket:");
-    let own_ticket: Vec<usize> = lines.next().unwrap().split(',').map(|v| v.parse::<usize>().unwrap()).collect();
-    assert_eq!(&lines.next().unwrap()[..], "");
-    
-    // Parse nearby tickets
-    assert_eq!(&lines.next().unwrap()[..], "nearby tickets:");
-    let mut nearby_tickets: Vec<Vec<usize>> = Vec::new();
-    for line in lines {
-        nearby_tickets.push(line.split(',').map(|v| v.parse::<usize>().unwrap()).collect());
+impl Field {
+    pub fn contains(&self, value: usize) -> bool {
+        self.possible_ranges.iter().any(|(min, max)| value >= *min && value <= *max)
     }
-
-    (fields, own_ticket, nearby_tickets)
 }
 
+// Nodes of the graph matching ticket columns to fields
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Slot {
+    Column(usize),
+    Field(usize),
+}
 
 // Part 1
-fn get_error_rate(fields: &Vec<Field>, tickets: &Vec<Vec<usize>>) -> usize {
+pub fn get_error_rate(fields: &[Field], tickets: &[Vec<usize>]) -> usize {
     let valid_ranges: Vec<&(usize, usize)> = fields.iter().flat_map(|f| &f.possible_ranges).collect();
     let is_valid_value = |value: usize| -> bool {
         valid_ranges.iter().any(|(min, max)| value >= *min && value <= *max)
@@ -63,95 +37,112 @@ fn get_error_rate(fields: &Vec<Field>, tickets: &Vec<Vec<usize>>) -> usize {
 }
 
 // Part 2
-fn get_valid_tickets(fields: &Vec<Field>, tickets: &Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+pub fn get_valid_tickets(fields: &[Field], tickets: &[Vec<usize>]) -> Vec<Vec<usize>> {
     let valid_ranges: Vec<&(usize, usize)> = fields.iter().flat_map(|f| &f.possible_ranges).collect();
     let is_valid_value = |value: usize| -> bool {
         valid_ranges.iter().any(|(min, max)| value >= *min && value <= *max)
     };
-    tickets.iter().filter(|t| !t.iter().any(|v| !is_valid_value(*v))).map(|t| t.clone()).collect()
+    tickets.iter().filter(|t| !t.iter().any(|v| !is_valid_value(*v))).cloned().collect()
 }
 
-// fn get_ranges_for_columns(matrix: &Vec<Vec<usize>>) -> Vec<(usize, usize)> {
-//     let mut column_ranges = Vec::with_capacity(matrix[0].len());
-//     for i in 0..matrix[0].len() {
-//         let mut iter = matrix.iter();
-//         let first = iter.next().unwrap();
-//         let mut min = first[i];
-//         let mut max = first[i];
-//         for line in iter {
-//             if line[i] < min {
-//                 min = line[i]
-//             }
-//             if line[i] > max {
-//                 max = line[i]
-//             }
-//         }
-//         column_ranges.push((min, max));
-//     }
-//     column_ranges
-// }
-
-fn get_correct_field_order<'a>(fields: &'a Vec<Field>, tickets: &Vec<Vec<usize>>) -> Vec<&'a Field> {
-    // First attempt: Too slow!
-    // let mut fields_copy = fields.clone();
-    // let column_ranges = get_ranges_for_columns(tickets);
-    // let mut found: Option<Vec<Field>> = None;
-    // heap_recursive(&mut fields_copy, |permutation| {
-    //     if column_ranges.iter().enumerate().all(|(i, range)| -> bool {
-    //         let (first_min, first_max) = permutation[i].possible_ranges[0];
-    //         let (second_min, second_max) = permutation[i].possible_ranges[1];
-    //         (range.0 >= first_min && range.1 <= first_max) || (range.0 >= second_min && range.1 <= second_max)
-    //     }) {
-    //         found = Some(permutation.to_vec());
-    //         return Control::Break(())
-    //     }
-    //     Control::Continue
-    // });
-    // return found.expect("couldn't find valid field order");
-
-    let mut possible_fields_per_column: Vec<Vec<&Field>> = tickets[0].iter().map(|_| fields.iter().collect()).collect();
-    for ticket in tickets.iter() {
-        for (i, value) in ticket.iter().enumerate() {
-            possible_fields_per_column[i] = possible_fields_per_column[i].iter().filter(|field| -> bool {
-                let (first_min, first_max) = field.possible_ranges[0];
-                let (second_min, second_max) = field.possible_ranges[1];
-                (*value >= first_min && *value <= first_max) || (*value >= second_min && *value <= second_max)
-            }).map(|field| *field).collect()
+pub fn get_correct_field_order<'a>(fields: &'a [Field], tickets: &[Vec<usize>]) -> Result<Vec<&'a Field>> {
+    // Columns have an edge to every field that fits all of their values, and each field can only be used once
+    let mut graph: Graph<Slot> = Graph::new();
+    let columns: Vec<NodeId> = (0..fields.len()).map(|i| graph.intern(Slot::Column(i))).collect();
+    for (i, field) in fields.iter().enumerate() {
+        let field_node = graph.intern(Slot::Field(i));
+        for (column, column_node) in columns.iter().enumerate() {
+            if tickets.iter().all(|ticket| field.contains(ticket[column])) {
+                graph.add_edge(*column_node, field_node, ());
+            }
         }
     }
-    // println!("{:?}", possible_fields_per_column.iter().map(|f| f.len()).collect::<Vec<_>>());
-    // ASSUMPTION: You can eliminate non-possible fields by linear process of elimination
-    while possible_fields_per_column.iter().any(|c| c.len() > 1) {
-        let clearable_fields: Vec<(usize, &Field)> = possible_fields_per_column.iter().enumerate().filter(|(_, c)| c.len() == 1).map(|(i, c)| (i, c[0])).collect();
-        for (i, field) in clearable_fields {
-            for j in 0..possible_fields_per_column.len() {
-                if j != i {
-                    possible_fields_per_column[j].retain(|&f| f.name != field.name);
-                }
+    trace!("Columns fit {:?} fields each", columns.iter().map(|id| graph.successors(*id).len()).collect::<Vec<usize>>());
+    let field_order = graph.bipartite_matching(&columns).iter().map(|matched| match matched.map(|id| graph.label(id)) {
+        Some(Slot::Field(i)) => Ok(&fields[*i]),
+        _ => Err(Error::solve("some columns don't match any field")),
+    }).collect::<Result<Vec<&Field>>>()?;
+    debug!("Field order: {:?}", field_order.iter().map(|f| &f.name).collect::<Vec<_>>());
+    Ok(field_order)
+}
+
+fn parse_ticket(line: &Line, field_count: usize) -> Result<Vec<usize>> {
+    let ticket = line.split(',').map(|v| line.parse_at::<usize>(v, "ticket value")).collect::<Result<Vec<usize>>>()?;
+    if ticket.len() != field_count {
+        return Err(line.error(1, format!("expected {} values, one for each field, found {}", field_count, ticket.len())))
+    }
+    Ok(ticket)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    // Fields whose values are multiplied together in part 2, and how many of them there must be
+    pub field_prefix: String,
+    pub field_count: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { field_prefix: String::from("departure"), field_count: 6 }
+    }
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    // Fields, own ticket and nearby tickets
+    type Input = (Vec<Field>, Vec<usize>, Vec<Vec<usize>>);
+    type Params = Params;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(mut lines: Lines) -> Result<(Vec<Field>, Vec<usize>, Vec<Vec<usize>>)> {
+        // Parse fields
+        let mut fields: Vec<Field> = Vec::new();
+        for line in lines.next_record().ok_or_else(|| lines.end_of_input("a field"))? {
+            lazy_static! {
+                static ref RE: Regex = Regex::new(r"^([^:]+): (\d+)-(\d+) or (\d+)-(\d+)$").expect("invalid field regex");
             }
+            let caps = RE.captures(&line).ok_or_else(|| line.error(1, "expected a field like \"name: 1-3 or 5-7\""))?;
+            let bound = |n: usize| line.parse_at::<usize>(caps.get(n).unwrap().as_str(), "range bound");
+            let name = caps[1].to_string();
+            let first_range = (bound(2)?, bound(3)?);
+            let second_range = (bound(4)?, bound(5)?);
+            fields.push(Field {
+                name,
+                possible_ranges: [first_range, second_range],
+            });
         }
+
+        // Parse own ticket
+        let mut own_section = lines.section("your ticket:")?;
+        let own_ticket = parse_ticket(&own_section.expect_line("your ticket")?, fields.len())?;
+        own_section.expect_end()?;
+
+        // Parse nearby tickets
+        let nearby_tickets = lines.section("nearby tickets:")?.map(|l| parse_ticket(&l, fields.len())).collect::<Result<_>>()?;
+        lines.expect_end()?;
+
+        Ok((fields, own_ticket, nearby_tickets))
+    }
+
+    fn part1((fields, _, nearby_tickets): &(Vec<Field>, Vec<usize>, Vec<Vec<usize>>)) -> Result<usize> {
+        Ok(get_error_rate(fields, nearby_tickets))
     }
-    // println!("{:?}", possible_fields_per_column);
-    let correct_fields: Vec<&Field> = possible_fields_per_column.iter().flatten().map(|f| f.clone()).collect();
-    assert_eq!(correct_fields.len(), fields.len());
-    correct_fields
-}
 
-pub fn main () {
-    let data = parse_data();
-    // println!("{:?}", data.0);
-    // println!("{:?}", data.1);
-    // println!("{:?}", data.2);
-
-    // Part 1
-    // println!("Error rate: {}", get_error_rate(&data.0, &data.2));
-
-    // Part 2
-    let valid_tickets = get_valid_tickets(&data.0, &data.2);
-    let fields = get_correct_field_order(&data.0, &valid_tickets);
-    // println!("Field order: {:?}", fields.iter().map(|f| &f.name).collect::<Vec<_>>());
-    let departure_field_indexes: Vec<usize> = fields.iter().enumerate().filter(|(_, field)| field.name.starts_with("departure")).map(|(i, _)| i).collect();
-    assert_eq!(departure_field_indexes.len(), 6);
-    let departure_product: usize = departure_field_indexes.iter().map(|i| data.1[*i]).product();
-    println!("Departure fields product: {}", departure_product)
+    fn part2(input: &(Vec<Field>, Vec<usize>, Vec<Vec<usize>>)) -> Result<usize> {
+        Self::part2_with(input, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part2_with((fields, own_ticket, nearby_tickets): &(Vec<Field>, Vec<usize>, Vec<Vec<usize>>), params: &Params, _progress: &Progress) -> Result<usize> {
+        let valid_tickets = get_valid_tickets(fields, nearby_tickets);
+        debug!("{} of {} nearby tickets are valid", valid_tickets.len(), nearby_tickets.len());
+        let fields = get_correct_field_order(fields, &valid_tickets)?;
+        let field_indexes: Vec<usize> = fields.iter().enumerate().filter(|(_, field)| field.name.starts_with(&params.field_prefix)).map(|(i, _)| i).collect();
+        if field_indexes.len() != params.field_count {
+            return Err(Error::solve(format!("expected {} {} fields, found {}", params.field_count, params.field_prefix, field_indexes.len())))
+        }
+        Ok(field_indexes.iter().map(|i| own_ticket[*i]).product())
+    }
 }
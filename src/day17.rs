@@ -4,41 +4,19 @@ use std::{
     hash::{Hash, Hasher},
     iter::from_fn,
 };
+use serde::Deserialize;
+use super::{
+    error::Result,
+    file::Lines,
+    grid::Grid,
+    progress::Progress,
+    solver::Solver,
+};
 
-type Coordinates = (isize, isize, isize, isize);
+pub type Coordinates = (isize, isize, isize, isize);
 
-// Part 1
-fn iterate_over_coordinates_part_1<'a>(
-    min_coordinates: &'a Coordinates,
-    max_coordinates: &'a Coordinates
-) -> impl Iterator<Item=Coordinates> + 'a {
-    let mut pos = -1isize;
-    let x_width = max_coordinates.0 - min_coordinates.0 + 1;
-    let y_width = max_coordinates.1 - min_coordinates.1 + 1;
-    let z_width = max_coordinates.2 - min_coordinates.2 + 1;
-    let max_pos = x_width * y_width * z_width;
-    from_fn(move || {
-        loop {
-            pos += 1;
-            if pos == max_pos {
-                return None
-            } else {
-                let z_diff = pos / (x_width * y_width);
-                let y_diff = pos % (x_width * y_width) / x_width;
-                let x_diff = pos % x_width;
-                return Some((
-                    min_coordinates.0 + x_diff,
-                    min_coordinates.1 + y_diff,
-                    min_coordinates.2 + z_diff,
-                    0,
-                ));
-            }
-        }
-    })
-}
-
-// Part 2
-fn iterate_over_coordinates_part_2<'a>(
+// Iterates over every coordinate in the hypercube; a 3D cube simply has a single w value
+pub fn iterate_over_coordinates<'a>(
     min_coordinates: &'a Coordinates,
     max_coordinates: &'a Coordinates
 ) -> impl Iterator<Item=Coordinates> + 'a {
@@ -49,22 +27,20 @@ fn iterate_over_coordinates_part_2<'a>(
     let w_width = max_coordinates.3 - min_coordinates.3 + 1;
     let max_pos = x_width * y_width * z_width * w_width;
     from_fn(move || {
-        loop {
-            pos += 1;
-            if pos == max_pos {
-                return None
-            } else {
-                let w_diff = pos / (x_width * y_width * z_width);
-                let z_diff = pos % (x_width * y_width * z_width) / (x_width * y_width);
-                let y_diff = pos % (x_width * y_width) / x_width;
-                let x_diff = pos % x_width;
-                return Some((
-                    min_coordinates.0 + x_diff,
-                    min_coordinates.1 + y_diff,
-                    min_coordinates.2 + z_diff,
-                    min_coordinates.3 + w_diff,
-                ));
-            }
+        pos += 1;
+        if pos == max_pos {
+            None
+        } else {
+            let w_diff = pos / (x_width * y_width * z_width);
+            let z_diff = pos % (x_width * y_width * z_width) / (x_width * y_width);
+            let y_diff = pos % (x_width * y_width) / x_width;
+            let x_diff = pos % x_width;
+            Some((
+                min_coordinates.0 + x_diff,
+                min_coordinates.1 + y_diff,
+                min_coordinates.2 + z_diff,
+                min_coordinates.3 + w_diff,
+            ))
         }
     })
 }
@@ -100,34 +76,34 @@ impl ActiveCube {
 }
 
 #[derive(Debug, Clone)]
-struct Dimension {
+pub struct Dimension {
+    // Part 1 only expands over x, y and z; part 2 also expands over w
+    four_dimensional: bool,
     min_coordinates: Coordinates,
     max_coordinates: Coordinates,
     active_cubes: HashMap<Coordinates, ActiveCube>,
 }
 
 impl Dimension {
-    fn new() -> Self {
+    pub fn new(four_dimensional: bool) -> Self {
         Dimension {
+            four_dimensional,
             min_coordinates: (0, 0, 0, 0),
             max_coordinates: (0, 0, 0, 0),
             active_cubes: HashMap::new(),
         }
     }
 
-    fn deactivate_cube(&mut self, coordinates: &Coordinates) {
-        match self.active_cubes.remove(coordinates) {
-            Some(cube) => {
-                cube.neighbors.iter().for_each(|c| {
-                    let c = self.active_cubes.get_mut(c).unwrap();
-                    c.remove_neighbor(&cube.coordinates);
-                });
-            },
-            None => (),
+    pub fn deactivate_cube(&mut self, coordinates: &Coordinates) {
+        if let Some(cube) = self.active_cubes.remove(coordinates) {
+            cube.neighbors.iter().for_each(|c| {
+                let c = self.active_cubes.get_mut(c).unwrap();
+                c.remove_neighbor(&cube.coordinates);
+            });
         };
     }
 
-    fn activate_cube(&mut self, coordinates: &Coordinates) {
+    pub fn activate_cube(&mut self, coordinates: &Coordinates) {
         let cube = ActiveCube {
             coordinates: *coordinates,
             neighbors: self.get_active_neighbors_iter(coordinates).iter().map(|c| c.coordinates).collect(),
@@ -148,56 +124,63 @@ impl Dimension {
         );
     }
 
-    fn get_active_coordinates(&self) -> impl Iterator<Item=&Coordinates> {
-        self.active_cubes.iter().map(|(k, _)| k)
+    pub fn active_cube_count(&self) -> usize {
+        self.active_cubes.len()
+    }
+
+    pub fn get_active_coordinates(&self) -> impl Iterator<Item=&Coordinates> {
+        self.active_cubes.keys()
+    }
+
+    // The x and y slice at the given z and w, like in the puzzle text, over the bounds of the whole dimension
+    pub fn get_layer(&self, z: isize, w: isize) -> Grid<bool> {
+        let width = (self.max_coordinates.0 - self.min_coordinates.0 + 1) as usize;
+        let height = (self.max_coordinates.1 - self.min_coordinates.1 + 1) as usize;
+        Grid::from_fn(width, height, |x, y| {
+            let coordinates = (self.min_coordinates.0 + x as isize, self.min_coordinates.1 + y as isize, z, w);
+            self.active_cubes.contains_key(&coordinates)
+        })
+    }
+
+    // Smallest and largest coordinates that ever had an active cube
+    pub fn get_bounds(&self) -> (Coordinates, Coordinates) {
+        (self.min_coordinates, self.max_coordinates)
+    }
+
+    // Returns the corners of the hypercube around the given bounds, expanded by one in every active dimension
+    fn expand_bounds(&self, min_coordinates: &Coordinates, max_coordinates: &Coordinates) -> (Coordinates, Coordinates) {
+        let w_expansion = if self.four_dimensional { 1 } else { 0 };
+        (
+            (min_coordinates.0 - 1, min_coordinates.1 - 1, min_coordinates.2 - 1, min_coordinates.3 - w_expansion),
+            (max_coordinates.0 + 1, max_coordinates.1 + 1, max_coordinates.2 + 1, max_coordinates.3 + w_expansion),
+        )
     }
 
     // TODO: Figure out how to return Iterator instead of Vec
     fn get_active_neighbors_iter(&self, coordinates: &Coordinates) -> Vec<&ActiveCube> {
-        // Part 1
-        // iterate_over_coordinates_part_1(
-        //     &(coordinates.0 - 1, coordinates.1 - 1, coordinates.2 - 1, 0),
-        //     &(coordinates.0 + 1, coordinates.1 + 1, coordinates.2 + 1, 0),
-        // Part 2
-        iterate_over_coordinates_part_2(
-            &(coordinates.0 - 1, coordinates.1 - 1, coordinates.2 - 1, coordinates.3 - 1),
-            &(coordinates.0 + 1, coordinates.1 + 1, coordinates.2 + 1, coordinates.3 + 1),
-
-        ).filter(|c| c != coordinates).map(|c| self.active_cubes.get(&c)).flatten().collect()
+        let (min_coordinates, max_coordinates) = self.expand_bounds(coordinates, coordinates);
+        iterate_over_coordinates(&min_coordinates, &max_coordinates)
+            .filter(|c| c != coordinates).filter_map(|c| self.active_cubes.get(&c)).collect()
     }
 
     fn for_each_active_neighbors<F>(&mut self, coordinates: &Coordinates, f: F) where
         F: Fn(&mut ActiveCube)
     {
-        // Part 1
-        // iterate_over_coordinates_part_1(
-        //     &(coordinates.0 - 1, coordinates.1 - 1, coordinates.2 - 1, 0),
-        //     &(coordinates.0 + 1, coordinates.1 + 1, coordinates.2 + 1, 0),
-        // Part 2
-        iterate_over_coordinates_part_2(
-            &(coordinates.0 - 1, coordinates.1 - 1, coordinates.2 - 1, coordinates.3 - 1),
-            &(coordinates.0 + 1, coordinates.1 + 1, coordinates.2 + 1, coordinates.3 + 1),
-        ).filter(|c| c != coordinates).for_each(|c| match self.active_cubes.get_mut(&c) {
-            Some(cube) => f(cube),
-            _ => (),
-        });
-    }
-
-    fn run_cycle(&mut self) {
+        let (min_coordinates, max_coordinates) = self.expand_bounds(coordinates, coordinates);
+        iterate_over_coordinates(&min_coordinates, &max_coordinates)
+            .filter(|c| c != coordinates).for_each(|c| if let Some(cube) = self.active_cubes.get_mut(&c) {
+                f(cube)
+            });
+    }
+
+    pub fn run_cycle(&mut self) {
         let mut cubes_to_deactivate: Vec<Coordinates> = Vec::new();
         let mut cubes_to_activate: Vec<Coordinates> = Vec::new();
 
-        // Part 1
-        // for coordinate in iterate_over_coordinates_part_1(
-        //     &(self.min_coordinates.0 - 1, self.min_coordinates.1 - 1, self.min_coordinates.2 - 1, 0),
-        //     &(self.max_coordinates.0 + 1, self.max_coordinates.1 + 1, self.max_coordinates.2 + 1, 0),
-        // Part 2
-        for coordinate in iterate_over_coordinates_part_2(
-            &(self.min_coordinates.0 - 1, self.min_coordinates.1 - 1, self.min_coordinates.2 - 1, self.min_coordinates.3 - 1),
-            &(self.max_coordinates.0 + 1, self.max_coordinates.1 + 1, self.max_coordinates.2 + 1, self.max_coordinates.3 + 1),
-        ) {
+        let (min_coordinates, max_coordinates) = self.expand_bounds(&self.min_coordinates, &self.max_coordinates);
+        for coordinate in iterate_over_coordinates(&min_coordinates, &max_coordinates) {
             match (self.active_cubes.contains_key(&coordinate), self.get_active_neighbors_iter(&coordinate).len()) {
-                (true, x) if x < 2 || x > 3 => cubes_to_deactivate.push(coordinate),
+                (true, x) if !(2..=3).contains(&x) => cubes_to_deactivate.push(coordinate),
                 (false, 3) => cubes_to_activate.push(coordinate),
                 _ => (),
             }
@@ -210,24 +193,66 @@ impl Dimension {
     }
 }
 
-fn get_initial_state() -> Dimension {
-    let mut dimension = Dimension::new();
-    for (y, line) in super::file::read_file("./inputs/day17.txt").enumerate() {
-        for (x, _) in line.chars().enumerate().filter(|(_, c)| *c == '#') {
-            dimension.activate_cube(&(x as isize, y as isize, 0, 0));
-        }
+// The initial slice is at z = 0 and w = 0
+pub fn get_initial_state(initial_slice: &Grid<bool>, four_dimensional: bool) -> Dimension {
+    let mut dimension = Dimension::new(four_dimensional);
+    for ((x, y), _) in initial_slice.enumerate().filter(|(_, active)| **active) {
+        dimension.activate_cube(&(x as isize, y as isize, 0, 0));
     }
     dimension
 }
 
-pub fn main () {
-    let mut data = get_initial_state();
-    let total_cycles = 6;
+pub fn run_cycles(initial_slice: &Grid<bool>, four_dimensional: bool, total_cycles: usize) -> usize {
+    let mut data = get_initial_state(initial_slice, four_dimensional);
+    debug!("Initial state: {} active cubes", data.active_cube_count());
+    for i in 1..=total_cycles {
+        data.run_cycle();
+        debug!("After cycle {}: {} active cubes", i, data.active_cube_count());
+    }
+    data.active_cube_count()
+}
 
-    println!("Initial state: {} active cubes", data.active_cubes.len());
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    // Number of cycles of the boot process
+    pub cycles: usize,
+}
 
-    for i in 1..total_cycles + 1 {
-        data.run_cycle();
-        println!("{}) {} active cubes", i, data.active_cubes.len());
+impl Default for Params {
+    fn default() -> Self {
+        Params { cycles: 6 }
+    }
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    // Initial slice, where '#' is an active cube
+    type Input = Grid<bool>;
+    type Params = Params;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(mut lines: Lines) -> Result<Grid<bool>> {
+        let initial_slice = Grid::parse(&mut lines, "the initial slice")?;
+        lines.expect_end()?;
+        Ok(initial_slice)
+    }
+
+    fn part1(initial_slice: &Grid<bool>) -> Result<usize> {
+        Self::part1_with(initial_slice, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part2(initial_slice: &Grid<bool>) -> Result<usize> {
+        Self::part2_with(initial_slice, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part1_with(initial_slice: &Grid<bool>, params: &Params, _progress: &Progress) -> Result<usize> {
+        Ok(run_cycles(initial_slice, false, params.cycles))
+    }
+
+    fn part2_with(initial_slice: &Grid<bool>, params: &Params, _progress: &Progress) -> Result<usize> {
+        Ok(run_cycles(initial_slice, true, params.cycles))
     }
 }
@@ -1,5 +1,11 @@
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    solver::{NoParams, Solver},
+};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum Token {
+pub enum Token {
     Num(usize),
     OpAdd,
     OpMul,
@@ -7,120 +13,112 @@ enum Token {
     ParensClose,
 }
 
-fn tokenize_line(line: String) -> Vec<Token> {
+// Pushes the number that ends right before the given column, if any
+fn push_num_token(line: &Line, column: usize, curr_num_token: &mut String, tokens: &mut Vec<Token>) -> Result<()> {
+    if !curr_num_token.is_empty() {
+        let number = curr_num_token.parse()
+            .map_err(|_| line.error(column - curr_num_token.len(), format!("invalid number {:?}", curr_num_token)))?;
+        tokens.push(Token::Num(number));
+        curr_num_token.clear();
+    }
+    Ok(())
+}
+
+pub fn tokenize_line(line: &Line) -> Result<Vec<Token>> {
     let mut tokens: Vec<Token> = Vec::new();
-    let iter = line.chars();
     let mut curr_num_token = String::new();
-    for c in iter {
+    let mut open_parens_columns: Vec<usize> = Vec::new();
+    for (i, c) in line.chars().enumerate() {
+        let column = i + 1;
         match c {
             digit @ '0'..='9' => {
                 curr_num_token.push(digit);
             }
             '+' => {
-                if !curr_num_token.is_empty() {
-                    tokens.push(Token::Num(curr_num_token.parse().expect("invalid num token before +")));
-                    curr_num_token.clear();
-                }
+                push_num_token(line, column, &mut curr_num_token, &mut tokens)?;
                 tokens.push(Token::OpAdd);
             },
             '*' => {
-                if !curr_num_token.is_empty() {
-                    tokens.push(Token::Num(curr_num_token.parse().expect("invalid num token before *")));
-                    curr_num_token.clear();
-                }
+                push_num_token(line, column, &mut curr_num_token, &mut tokens)?;
                 tokens.push(Token::OpMul);
             },
             '(' => {
                 if !curr_num_token.is_empty() {
-                    panic!("unexpected (");
+                    return Err(line.error(column, "unexpected ( right after a number"));
                 }
+                open_parens_columns.push(column);
                 tokens.push(Token::ParensOpen);
             },
             ')' => {
-                if !curr_num_token.is_empty() {
-                    tokens.push(Token::Num(curr_num_token.parse().expect("invalid num token before )")));
-                    curr_num_token.clear();
+                push_num_token(line, column, &mut curr_num_token, &mut tokens)?;
+                if open_parens_columns.pop().is_none() {
+                    return Err(line.error(column, "unmatched )"));
                 }
                 tokens.push(Token::ParensClose);
             },
             ' ' => {
-                if !curr_num_token.is_empty() {
-                    tokens.push(Token::Num(curr_num_token.parse().expect("invalid num token before space")));
-                    curr_num_token.clear();
-                }
+                push_num_token(line, column, &mut curr_num_token, &mut tokens)?;
             },
-            _ => panic!("invalid character"),
+            _ => return Err(line.error(column, format!("invalid character {:?}", c))),
         }
     };
-    if !curr_num_token.is_empty() {
-        tokens.push(Token::Num(curr_num_token.parse().expect("invalid num token before space")));
+    push_num_token(line, line.chars().count() + 1, &mut curr_num_token, &mut tokens)?;
+    if let Some(column) = open_parens_columns.pop() {
+        return Err(line.error(column, "unclosed ("));
     }
-    tokens
+    Ok(tokens)
 }
 
-fn evaluate_expression(expression: &Vec<Token>) -> usize {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Precedence {
+    // Part 1
+    LeftToRight,
+    // Part 2
+    AdditionFirst,
+}
+
+pub fn evaluate_expression(expression: &[Token], precedence: &Precedence) -> Result<usize> {
+    let malformed = |reason: &str| Error::solve(format!("malformed expression: {}", reason));
     let mut stack: Vec<Token> = Vec::new();
 
     for token_iter in expression.iter() {
         let mut token = *token_iter;
         loop {
             let peek = stack.pop();
-            match peek {
-                Some(t) => stack.push(t),
-                None => (),
-            };
-            match (token, peek) {
-
-                // Part 1
-                // (Token::Num(second), Some(Token::OpAdd)) | (Token::Num(second), Some(Token::OpMul)) => {
-                //     let op = stack.pop().unwrap();
-                //     let first_tok = stack.pop().unwrap();
-                //     match (first_tok, op) {
-                //         (Token::Num(first), Token::OpAdd) => stack.push(Token::Num(first + second)),
-                //         (Token::Num(first), Token::OpMul) => stack.push(Token::Num(first * second)),
-                //         (_, Token::OpAdd) => panic!("cannot add non-numerical values"),
-                //         (_, Token::OpMul) => panic!("cannot multiply non-numerical values"),
-                //         _ => panic!("impossible condition (operator disappeared from top of stack)"),
-                //     };
-                //     break;
-                // },
-
-                // Part 2
-                (Token::Num(second), Some(Token::OpAdd)) => {
-                    stack.pop(); // OpAdd
-                    let first_tok = stack.pop().unwrap();
-                    match first_tok {
-                        Token::Num(first) => stack.push(Token::Num(first + second)),
-                        _ => panic!("cannot add non-numerical values"),
+            if let Some(t) = peek { stack.push(t) };
+            match (token, peek, precedence) {
+                (Token::Num(second), Some(Token::OpAdd), _) | (Token::Num(second), Some(Token::OpMul), Precedence::LeftToRight) => {
+                    let op = stack.pop().expect("operator was peeked from top of stack");
+                    let first_tok = stack.pop();
+                    match (first_tok, op) {
+                        (Some(Token::Num(first)), Token::OpAdd) => stack.push(Token::Num(first + second)),
+                        (Some(Token::Num(first)), Token::OpMul) => stack.push(Token::Num(first * second)),
+                        (_, Token::OpAdd) => return Err(malformed("cannot add non-numerical values")),
+                        (_, Token::OpMul) => return Err(malformed("cannot multiply non-numerical values")),
+                        _ => unreachable!("operator disappeared from top of stack"),
                     };
                     break;
                 },
-
-                (Token::Num(_), _) | (Token::OpAdd, _) | (Token::OpMul, _) | (Token::ParensOpen, _) => {
+                (Token::Num(_), _, _) | (Token::OpAdd, _, _) | (Token::OpMul, _, _) | (Token::ParensOpen, _, _) => {
                     stack.push(token);
                     break;
                 }
-                (Token::ParensClose, t) => {
-                    // Part 1
-                    // token = t.expect("no value before )");
-                    // stack.pop();
-                    // assert_eq!(stack.pop(), Some(Token::ParensOpen));
-                    
-                    // Part 2
-                    token = t.expect("no value before )");
+                (Token::ParensClose, t, _) => {
+                    token = t.ok_or_else(|| malformed("no value before )"))?;
                     stack.pop();
                     loop {
                         match stack.pop() {
+                            // Only happens with addition first, since multiplications are never deferred otherwise
                             Some(Token::OpMul) => {
                                 match (token, stack.pop()) {
                                     (Token::Num(second), Some(Token::Num(first))) => {
                                         token = Token::Num(first * second);
                                     },
-                                    _ => panic!("cannot multiply non-numerical values")
+                                    _ => return Err(malformed("cannot multiply non-numerical values")),
                                 }
                             },
                             Some(Token::ParensOpen) => break,
-                            _ => panic!("unmatched parens")
+                            _ => return Err(malformed("unmatched parens")),
                         }
                     }
                 },
@@ -131,9 +129,9 @@ fn evaluate_expression(expression: &Vec<Token>) -> usize {
     loop {
         match &stack.clone()[..] {
             // Only the final value must be remaining in the stack
-            [Token::Num(result)] => return *result,
+            [Token::Num(result)] => return Ok(*result),
     
-            // Part 2 -- doesn't affect part 1
+            // Deferred multiplications; only happens with addition first
             [.., Token::Num(first), Token::OpMul, Token::Num(second)] => {
                 stack.pop();
                 stack.pop();
@@ -141,17 +139,34 @@ fn evaluate_expression(expression: &Vec<Token>) -> usize {
                 stack.push(Token::Num(first * second));
             },
     
-            _ => panic!("failed to evaluate expression")
+            _ => return Err(malformed("failed to evaluate expression")),
         };
     }
 }
 
-pub fn main () {
-    let expressions = super::file::read_file("./inputs/day18.txt").map(tokenize_line);
-    // for e in expressions {
-    //     // println!("{:?}", e);
-    //     println!("{}", evaluate_expression(&e));
-    // };
-    let sum = expressions.map(|e| evaluate_expression(&e)).sum::<usize>();
-    println!("Sum of expressions: {}", sum);
+fn log_value(value: &Result<usize>) {
+    if let Ok(value) = value {
+        trace!("Expression = {}", value);
+    }
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Input = Vec<Vec<Token>>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(lines: Lines) -> Result<Vec<Vec<Token>>> {
+        lines.map(|l| tokenize_line(&l)).collect()
+    }
+
+    fn part1(expressions: &Vec<Vec<Token>>) -> Result<usize> {
+        expressions.iter().map(|e| evaluate_expression(e, &Precedence::LeftToRight)).inspect(log_value).sum()
+    }
+
+    fn part2(expressions: &Vec<Vec<Token>>) -> Result<usize> {
+        expressions.iter().map(|e| evaluate_expression(e, &Precedence::AdditionFirst)).inspect(log_value).sum()
+    }
 }
@@ -2,37 +2,42 @@ use std::{
     collections::HashMap,
 };
 use regex::Regex;
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    progress::Progress,
+    solver::{NoParams, Solver},
+};
 
 #[derive(Debug, Clone)]
-enum Rule {
+pub enum Rule {
     Option(Vec<Rule>),
     Concat(Vec<usize>),
     Char(char),
 }
 
-fn get_data() -> (HashMap<usize, Rule>, Vec<String>) {
-    let mut lines = super::file::read_file("./inputs/day19.txt");
-
+pub fn get_data(mut lines: Lines) -> Result<(HashMap<usize, Rule>, Vec<String>)> {
     // Parse rules
     let mut rules_map: HashMap<usize, Rule> = HashMap::new();
-    loop {
-        let line = lines.next().unwrap();
-        if line.is_empty() {
-            break;
-        }
+    let mut rule_lines: Vec<Line> = Vec::new();
+    let mut rules = lines.next_record().ok_or_else(|| lines.end_of_input("a rule"))?;
+    for line in rules.by_ref() {
         match line.splitn(2, ": ").collect::<Vec<_>>()[..] {
             [key_str, rule_set_str] => {
-                let key: usize = key_str.parse().expect("invalid rule number");
+                let key: usize = line.parse_at(key_str, "rule number")?;
+                if rules_map.contains_key(&key) {
+                    return Err(line.error_at(key_str, format!("rule {} is defined twice", key)));
+                }
 
                 match rule_set_str.chars().collect::<Vec<_>>()[..] {
                     ['"', c, '"'] => {
                         rules_map.insert(key, Rule::Char(c));
                     },
-                    [] => panic!("empty rule set"),
+                    [] => return Err(line.error_at(rule_set_str, "empty rule set")),
                     _ => {
-                        let mut rule_vec: Vec<Rule> = rule_set_str.split(" | ").map(|concat| -> Rule {
-                            Rule::Concat(concat.split(' ').map(|r| r.parse::<usize>().expect("invalid rule in rule set")).collect())
-                        }).collect();
+                        let mut rule_vec: Vec<Rule> = rule_set_str.split(" | ").map(|concat| -> Result<Rule> {
+                            Ok(Rule::Concat(concat.split(' ').map(|r| line.parse_at::<usize>(r, "rule number")).collect::<Result<_>>()?))
+                        }).collect::<Result<_>>()?;
                         if rule_vec.len() == 1 {
                             rules_map.insert(key, rule_vec.pop().unwrap());
                         } else {
@@ -41,11 +46,34 @@ fn get_data() -> (HashMap<usize, Rule>, Vec<String>) {
                     },
                 };
             },
-            _ => panic!("invalid rule line"),
+            _ => return Err(line.error(1, "expected a rule like \"0: 1 2 | 3\"")),
         };
+        rule_lines.push(line);
+    }
+
+    // Every rule must only refer to existing rules
+    for line in rule_lines.iter() {
+        let rule_set_str = line.split_once(": ").expect("rule lines were already parsed").1;
+        if let Some(missing) = rule_set_str.split([' ', '|'])
+            .find(|r| r.parse::<usize>().is_ok_and(|r| !rules_map.contains_key(&r)))
+        {
+            return Err(line.error_at(missing, format!("rule {} is not defined", missing)));
+        }
     }
+    if !rules_map.contains_key(&0) {
+        return Err(rules.error_at_end("expected rule 0 to be defined before the messages"));
+    }
+
+    // Parse messages
+    let messages: Vec<String> = lines.next_record().map(|record| record.map(Line::into_text).collect()).unwrap_or_default();
+    lines.expect_end()?;
 
-    // Part 2 -- Substitute fixed rules
+    Ok((rules_map, messages))
+}
+
+// Part 2
+pub fn substitute_and_normalize_rules(rules_map: &mut HashMap<usize, Rule>) -> Result<()> {
+    // Substitute fixed rules
     /*
     // Non-normal rule 8
     rules_map.insert(8, Rule::Option([
@@ -55,10 +83,13 @@ fn get_data() -> (HashMap<usize, Rule>, Vec<String>) {
     */
     // Chomsky normalized rule 8
     // ASSUMPTION: "42 => A B | C D | E F | ..."
-    let mut rule_8_vec = match rules_map.get(&42).unwrap() {
-        Rule::Option(vec) => vec.clone(),
-        _ => panic!("rule 42 in different format than expected"),
+    let mut rule_8_vec = match rules_map.get(&42) {
+        Some(Rule::Option(vec)) => vec.clone(),
+        _ => return Err(Error::solve("expected rule 42 to have the format \"A B | C D | ...\"")),
     };
+    if !rules_map.contains_key(&31) {
+        return Err(Error::solve("expected rule 31 to be defined"));
+    }
     rule_8_vec.push(Rule::Concat([42, 8].to_vec()));
     rules_map.insert(8, Rule::Option(rule_8_vec));
     /*
@@ -76,20 +107,17 @@ fn get_data() -> (HashMap<usize, Rule>, Vec<String>) {
     ].to_vec()));
     rules_map.insert(intermediate_rule, Rule::Concat([11, 31].to_vec()));
 
-    // Part 2 -- Normalize [Rx -> Ra Ry ; Rz -> Rw Ra ; Ra -> Rb | Rc] into [Rx -> Rb Ry | Rc Ry; Rz -> Rw Rb | Rw Rc]
+    // Normalize [Rx -> Ra Ry ; Rz -> Rw Ra ; Ra -> Rb | Rc] into [Rx -> Rb Ry | Rc Ry; Rz -> Rw Rb | Rw Rc]
     // ASSUMPTION: Rules to find equal the format "X => A | B | C | ..." with single rule on every option of the right-side
     let rules_to_normalize_1a: Vec<(usize, usize)> = rules_map.iter().flat_map(|(k, r)| -> Option<Vec<(usize, usize)>> {
         match r {
             Rule::Option(rulesets) => {
                 let mut normalization_vec: Vec<(usize, usize)> = Vec::new();
                 rulesets.iter().for_each(|rs| {
-                    match rs {
-                        Rule::Concat(r) => {
-                            if r.len() == 1 {
-                                normalization_vec.push((*k, r[0]));
-                            }
-                        },
-                        _ => (),
+                    if let Rule::Concat(r) = rs {
+                        if r.len() == 1 {
+                            normalization_vec.push((*k, r[0]));
+                        }
                     }
                 });
                 Some(normalization_vec)
@@ -99,170 +127,160 @@ fn get_data() -> (HashMap<usize, Rule>, Vec<String>) {
     }).flatten().collect();
     let mut rules_to_normalize_1b: HashMap<usize, Vec<usize>> = HashMap::new();
     rules_to_normalize_1a.iter().for_each(|(k, v)| {
-        rules_to_normalize_1b.entry(*k).or_insert(Vec::new()).push(*v);
+        rules_to_normalize_1b.entry(*k).or_default().push(*v);
     });
-    // println!("{:?}", rules_to_normalize_1b);
-    rules_to_normalize_1b.iter().for_each(|(k, v)| {
+    trace!("Unit rules to normalize: {:?}", rules_to_normalize_1b);
+    for (k, v) in rules_to_normalize_1b.iter() {
         rules_map.remove(k);
-        let rules_to_change: Vec<_> = rules_map.iter().flat_map(|(x, r)| -> Option<(usize, Rule)> {
+        // ASSUMPTION: Rule to normalize is "X => Y | Z" when a rule refers to it twice
+        let both_options = || -> Result<(usize, usize)> {
+            match &v[..] {
+                [c, d] => Ok((*c, *d)),
+                _ => Err(Error::solve(format!("rule {} has {} options, expected 2 to normalize it", k, v.len()))),
+            }
+        };
+        let mut rules_to_change: Vec<(usize, Rule)> = Vec::new();
+        for (x, r) in rules_map.iter() {
             match r {
                 Rule::Concat(subrules) => {
                     match &subrules[..] {
                         [a, b] if a == k && b == k => {
-                            // ASSUMPTION: Rule to normalize is "X => Y | Z"
-                            match &v[..] {
-                                [c, d] => {
-                                    Some((*x, Rule::Option(vec![
-                                        Rule::Concat(vec![*c, *c]),
-                                        Rule::Concat(vec![*c, *d]),
-                                        Rule::Concat(vec![*d, *c]),
-                                        Rule::Concat(vec![*d, *d]),
-                                    ])))
-                                },
-                                _ => panic!("unexpected format for rule in normalization step 1 (Concat)"),
-                            }
+                            let (c, d) = both_options()?;
+                            rules_to_change.push((*x, Rule::Option(vec![
+                                Rule::Concat(vec![c, c]),
+                                Rule::Concat(vec![c, d]),
+                                Rule::Concat(vec![d, c]),
+                                Rule::Concat(vec![d, d]),
+                            ])));
                         },
                         [a, b] if a == k => {
-                            Some((*x, Rule::Option(v.iter().map(|n| Rule::Concat(vec![*n, *b])).collect())))
+                            rules_to_change.push((*x, Rule::Option(v.iter().map(|n| Rule::Concat(vec![*n, *b])).collect())));
                         },
                         [a, b] if b == k => {
-                            Some((*x, Rule::Option(v.iter().map(|n| Rule::Concat(vec![*a, *n])).collect())))
+                            rules_to_change.push((*x, Rule::Option(v.iter().map(|n| Rule::Concat(vec![*a, *n])).collect())));
                         },
-                        _ => None,
+                        _ => (),
                     }
                 },
                 Rule::Option(rulesets) => {
                     let mut option_vec: Vec<Rule> = Vec::new();
-                    rulesets.iter().for_each(|rs| {
+                    for rs in rulesets.iter() {
                         // ASSUMPTION: All option rules only include concat rulesets
-                        match rs {
-                            Rule::Concat(subrules) => {
-                                match &subrules[..] {
-                                    [a, b] if a == k && b == k => {
-                                        // ASSUMPTION: Rule to normalize is "X => Y | Z"
-                                        match &v[..] {
-                                            [c, d] => {
-                                                option_vec.push(Rule::Concat(vec![*c, *c]));
-                                                option_vec.push(Rule::Concat(vec![*c, *d]));
-                                                option_vec.push(Rule::Concat(vec![*d, *c]));
-                                                option_vec.push(Rule::Concat(vec![*d, *d]));
-                                            },
-                                            _ => panic!("unexpected format for rule in normalization step 1 (Concat)"),
-                                        }
-                                    },
-                                    [a, b] if a == k => {
-                                        v.iter().for_each(|n| option_vec.push(Rule::Concat(vec![*n, *b])));
-                                    },
-                                    [a, b] if b == k => {
-                                        v.iter().for_each(|n| option_vec.push(Rule::Concat(vec![*a, *n])));
-                                    },
-                                    _ => option_vec.push(Rule::Concat(subrules.clone())),
-                                }
-                            }
-                            _ => panic!("unexpected non-concat rule inside option rule in normalization step 1")
+                        let subrules = match rs {
+                            Rule::Concat(subrules) => subrules,
+                            _ => return Err(Error::solve(format!("rule {} has an option that isn't a sequence of rules", x))),
+                        };
+                        match &subrules[..] {
+                            [a, b] if a == k && b == k => {
+                                let (c, d) = both_options()?;
+                                option_vec.push(Rule::Concat(vec![c, c]));
+                                option_vec.push(Rule::Concat(vec![c, d]));
+                                option_vec.push(Rule::Concat(vec![d, c]));
+                                option_vec.push(Rule::Concat(vec![d, d]));
+                            },
+                            [a, b] if a == k => {
+                                v.iter().for_each(|n| option_vec.push(Rule::Concat(vec![*n, *b])));
+                            },
+                            [a, b] if b == k => {
+                                v.iter().for_each(|n| option_vec.push(Rule::Concat(vec![*a, *n])));
+                            },
+                            _ => option_vec.push(Rule::Concat(subrules.clone())),
                         }
-                    });
-                    Some((*x, Rule::Option(option_vec)))
+                    }
+                    rules_to_change.push((*x, Rule::Option(option_vec)));
                 },
-                _ => None,
+                _ => (),
             }
-        }).collect();
-        for (x, r) in rules_to_change.iter() {
-            rules_map.insert(*x, r.clone());
         }
-    });
-
-    // Parse messages
-    let messages: Vec<String> = lines.collect();
-
-    // println!("{:?}", rules_map);
-    // rules_map.iter().for_each(|(k, v)| {
-    //     println!("{} {:?}", k, v);
-    // });
-
-    (rules_map, messages)
+        for (x, r) in rules_to_change.into_iter() {
+            rules_map.insert(x, r);
+        }
+    }
+    Ok(())
 }
 
 // Part 1
-fn get_validation_function_part_1(rules: &HashMap<usize, Rule>) -> impl Fn(&str) -> bool {
+pub fn get_validation_function_part_1(rules: &HashMap<usize, Rule>) -> Result<impl Fn(&str) -> bool> {
     let mut s = String::from("^");
-    s += &build_regex_string_for_rule(rules, &0);
+    s += &build_regex_string_for_rule(rules, &0, &mut Vec::new())?;
     s += "$";
-    let re: Regex = Regex::new(&s).expect("invalid rule regex");
-    move |message| {
+    let re: Regex = Regex::new(&s).map_err(|e| Error::solve(format!("cannot build a regex from the rules: {}", e)))?;
+    Ok(move |message: &str| {
         re.is_match(message)
-    }
+    })
 }
 
-fn build_regex_string_for_rule(rules: &HashMap<usize, Rule>, starting_rule: &usize) -> String {
-    match rules.get(starting_rule).expect("cannot find rule") {
-        Rule::Char(c) => {
-            let mut s = String::with_capacity(1);
-            s.push(*c);
-            s
-        },
-        Rule::Concat(subrules) => {
-            subrules.iter().map(|r| build_regex_string_for_rule(rules, r)).fold(String::new(), |acc, x| acc + &x )
-        },
+// Rules that are being expanded are kept in `path`, since a rule that refers back to itself has no regex
+fn build_regex_string_for_rule(rules: &HashMap<usize, Rule>, starting_rule: &usize, path: &mut Vec<usize>) -> Result<String> {
+    if path.contains(starting_rule) {
+        return Err(Error::solve(format!("rule {} refers back to itself, which part 1 can't match", starting_rule)));
+    }
+    path.push(*starting_rule);
+    let mut build_concat = |subrules: &[usize]| -> Result<String> {
+        subrules.iter().map(|r| build_regex_string_for_rule(rules, r, path)).collect()
+    };
+    let s = match rules.get(starting_rule).ok_or_else(|| Error::solve(format!("rule {} is not defined", starting_rule)))? {
+        Rule::Char(c) => c.to_string(),
+        Rule::Concat(subrules) => build_concat(subrules)?,
         Rule::Option(rulesets) => {
             let option = rulesets.iter().map(|rs| {
                 match rs {
-                    Rule::Concat(subrules) => {
-                        subrules.iter().map(|r| build_regex_string_for_rule(rules, r)).fold(String::new(), |acc, x| acc + &x )
-                    }
-                    _ => panic!("invalid Option rule")
+                    Rule::Concat(subrules) => build_concat(subrules),
+                    _ => Err(Error::solve(format!("rule {} has an option that isn't a sequence of rules", starting_rule))),
                 }
-            }).collect::<Vec<_>>().join("|");
-            let mut s = String::from("(");
-            s += &option;
-            s + ")"
+            }).collect::<Result<Vec<_>>>()?.join("|");
+            format!("({})", option)
         },
-    }
+    };
+    path.pop();
+    Ok(s)
 }
 
-fn get_validation_function_part_2<'a>(rules: &'a HashMap<usize, Rule>) -> impl Fn(&str) -> bool + 'a {
-    let flattened_rules: Vec<(usize, (usize, usize))> = rules.iter().map(|(k, v)| -> Vec<(usize, (usize, usize))> {
+pub fn get_validation_function_part_2<'a>(rules: &'a HashMap<usize, Rule>) -> Result<impl Fn(&str) -> bool + 'a> {
+    let not_normalized = |k: &usize| Error::solve(format!("rule {} is not a pair of rules or a character after normalization", k));
+    let mut flattened_rules: Vec<(usize, (usize, usize))> = Vec::new();
+    for (k, v) in rules.iter() {
         match v {
-            Rule::Char(_) => vec![],
+            Rule::Char(_) => (),
             Rule::Concat(subrules) => {
                 match &subrules[..] {
-                    [first, second] => vec![(*k, (*first, *second))],
-                    _ => panic!("non-normalized rule in Concat"),
+                    [first, second] => flattened_rules.push((*k, (*first, *second))),
+                    _ => return Err(not_normalized(k)),
                 }
             },
             Rule::Option(rulesets) => {
-                rulesets.iter().map(|rs| {
+                for rs in rulesets.iter() {
                     match rs {
                         Rule::Concat(subrules) => {
                             match &subrules[..] {
-                                [first, second] => (*k, (*first, *second)),
-                                _ => panic!("non-normalized rule in Option<Concat>"),
+                                [first, second] => flattened_rules.push((*k, (*first, *second))),
+                                _ => return Err(not_normalized(k)),
                             }
                         },
-                        _ => panic!("non-concat inside of option")
+                        _ => return Err(not_normalized(k)),
                     }
-                }).collect()
+                }
             }
         }
-    }).flatten().collect();
+    }
     let mut char_rules: HashMap<char, Vec<usize>> = HashMap::new();
     rules.iter().filter_map(|(k, v)| match v { Rule::Char(c) => Some((c, k)), _ => None } ).for_each(|(c, k)| {
-        char_rules.entry(*c).or_insert(Vec::new()).push(*k);
+        char_rules.entry(*c).or_default().push(*k);
     });
 
     // TODO: This is very slow, although it does find a result in the end.
-    move |message| {
+    Ok(move |message: &str| {
+        if message.is_empty() {
+            return false
+        }
         let mut cyk_matrix: Vec<Vec<Vec<usize>>> = vec![vec![vec![]; message.len()]; message.len()];
-        // Simply using a hashmap is even slower!!
-        // let mut cyk_matrix_2: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
 
         // Step 1
         for (s, c) in message.chars().enumerate() {
-            cyk_matrix[0][s] = char_rules.get(&c).unwrap().clone();
-            // cyk_matrix_2.insert((0, s), char_rules.get(&c).unwrap().clone());
+            cyk_matrix[0][s] = char_rules.get(&c).cloned().unwrap_or_default();
         }
 
-        // println!("{:?}", cyk_matrix[0]);
+        trace!("CYK row 1 of {:?}: {:?}", message, cyk_matrix[0]);
 
         // Step 2
         let n = message.len();
@@ -270,37 +288,56 @@ fn get_validation_function_part_2<'a>(rules: &'a HashMap<usize, Rule>) -> impl F
             for s in 1..n - l + 2 { // Start of span
                 for p in 1..l { // Partition of span
                     let found: Vec<_> = flattened_rules.iter().filter(|(_, (b, c))| {
-                        cyk_matrix[p - 1][s - 1].contains(&b) && cyk_matrix[l - p - 1][s + p - 1].contains(&c)
-                        // match (cyk_matrix_2.get(&(p - 1, s - 1)), cyk_matrix_2.get(&(l - p - 1, s + p - 1))) {
-                        //     (Some(possible_b), Some(possible_c)) => possible_b.contains(&b) && possible_c.contains(&c),
-                        //     _ => false,
-                        // }
+                        cyk_matrix[p - 1][s - 1].contains(b) && cyk_matrix[l - p - 1][s + p - 1].contains(c)
                     }).map(|(a, _)| *a).collect();
                     for i in found.iter() {
                         cyk_matrix[l - 1][s - 1].push(*i);
-                        // cyk_matrix_2.entry((l - 1, s - 1)).or_insert(Vec::new()).push(*i);
                     };
                 }
             }
-            // println!("{:?}", cyk_matrix[l-1]);
+            trace!("CYK row {} of {:?}: {:?}", l, message, cyk_matrix[l - 1]);
         }
 
         let is_valid = cyk_matrix[message.len() - 1][0].contains(&0);
-        // let is_valid = match cyk_matrix_2.get(&(message.len() - 1, 0)) {
-        //     Some(x) => x.contains(&0),
-        //     _ => false,
-        // };
+        trace!("{:?} is {}", message, if is_valid { "valid" } else { "invalid" });
         is_valid
-    }
+    })
 }
 
-pub fn main () {
-    let (rules, messages) = get_data();
-    // println!("{:?}\n", rules);
-    // println!("{:?}", messages);
+pub struct Day19;
+
+impl Solver for Day19 {
+    // Rules and messages
+    type Input = (HashMap<usize, Rule>, Vec<String>);
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
 
-    // let validator = get_validation_function_part_1(&rules);
-    let validator = get_validation_function_part_2(&rules);
-    let valid_messages = messages.iter().filter(|m| validator(m)).count();
-    println!("Valid messages: {}", valid_messages);
+    fn parse(lines: Lines) -> Result<(HashMap<usize, Rule>, Vec<String>)> {
+        get_data(lines)
+    }
+
+    fn part1((rules, messages): &(HashMap<usize, Rule>, Vec<String>)) -> Result<usize> {
+        let validator = get_validation_function_part_1(rules)?;
+        Ok(messages.iter().filter(|m| validator(m)).count())
+    }
+
+    fn part2(input: &(HashMap<usize, Rule>, Vec<String>)) -> Result<usize> {
+        Self::part2_with(input, &NoParams::default(), &Progress::unlimited())
+    }
+
+    // Each message runs the whole CYK algorithm, so progress is checked between messages
+    fn part2_with((rules, messages): &(HashMap<usize, Rule>, Vec<String>), _params: &NoParams, progress: &Progress) -> Result<usize> {
+        let mut rules = rules.clone();
+        substitute_and_normalize_rules(&mut rules)?;
+        let validator = get_validation_function_part_2(&rules)?;
+        let mut valid_messages = 0usize;
+        for (i, message) in messages.iter().enumerate() {
+            progress.check(i, Some(messages.len()))?;
+            if validator(message) {
+                valid_messages += 1;
+            }
+        }
+        Ok(valid_messages)
+    }
 }
@@ -1,41 +1,70 @@
 use std::{
-    iter::Iterator,
+    convert::TryFrom,
 };
 use regex::Regex;
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    solver::{NoParams, Solver},
+};
 
 #[derive(Debug)]
-struct PasswordValidation {
-    password: String,
-    policy_char: char,
-    policy_first: usize,
-    policy_second: usize,
+pub struct PasswordValidation {
+    pub password: String,
+    pub policy_char: char,
+    pub policy_first: usize,
+    pub policy_second: usize,
 }
 
-impl From<String> for PasswordValidation {
-    fn from(item: String) -> Self {
-        let re = Regex::new(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$").expect("invalid regex");
-        let caps = re.captures(&item).expect("regex did not match line");
-        PasswordValidation {
+impl TryFrom<&Line> for PasswordValidation {
+    type Error = Error;
+
+    fn try_from(line: &Line) -> Result<Self> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$").expect("invalid regex");
+        }
+        let caps = RE.captures(line).ok_or_else(|| line.error(1, "expected a password policy like \"1-3 a: abcde\""))?;
+        let validation = PasswordValidation {
             password: caps[4].to_string(),
-            policy_char: caps[3].chars().nth(0).expect("policy_char is missing"),
-            policy_first: caps[1].parse().expect("policy_first is not an int"),
-            policy_second: caps[2].parse().expect("policy_second is not an int"),
+            policy_char: caps[3].chars().next().expect("policy_char is missing"),
+            policy_first: line.parse_at(caps.get(1).unwrap().as_str(), "policy position")?,
+            policy_second: line.parse_at(caps.get(2).unwrap().as_str(), "policy position")?,
+        };
+        let password_length = validation.password.chars().count();
+        // Part 2 indexes the password with the policy positions
+        for (group, position) in [(1, validation.policy_first), (2, validation.policy_second)].iter() {
+            if *position == 0 || *position > password_length {
+                return Err(line.error_at(caps.get(*group).unwrap().as_str(), format!("policy position {} is out of range for the password", position)));
+            }
         }
+        Ok(validation)
     }
 }
 
-fn get_data() -> impl Iterator<Item=PasswordValidation> {
-    super::file::read_file("./inputs/day2.txt")
-        .map(|l| PasswordValidation::from(l))
-}
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<PasswordValidation>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
 
-pub fn main () {
-    let passwords = get_data();
-    let valid_passwords = passwords.filter(|pass| {
-        // let char_count = pass.password.as_str().chars().filter(|c| c == &pass.policy_char).count();
-        // (char_count >= pass.policy_first) && (char_count <= pass.policy_second)
-        let get_char_from_password = |policy_pos: usize| -> char { pass.password.as_str().chars().nth(policy_pos - 1).expect("policy_pos is out of range") };
-        (get_char_from_password(pass.policy_first) == pass.policy_char) ^ (get_char_from_password(pass.policy_second) == pass.policy_char)
-    });
-    println!("Valid passwords: {}", valid_passwords.count())
+    fn parse(lines: Lines) -> Result<Vec<PasswordValidation>> {
+        lines.map(|l| PasswordValidation::try_from(&l)).collect()
+    }
+
+    fn part1(passwords: &Vec<PasswordValidation>) -> Result<usize> {
+        Ok(passwords.iter().filter(|pass| {
+            let char_count = pass.password.as_str().chars().filter(|c| c == &pass.policy_char).count();
+            trace!("{:?} has {} {:?}, expected {} to {}", pass.password, char_count, pass.policy_char, pass.policy_first, pass.policy_second);
+            (char_count >= pass.policy_first) && (char_count <= pass.policy_second)
+        }).count())
+    }
+
+    fn part2(passwords: &Vec<PasswordValidation>) -> Result<usize> {
+        Ok(passwords.iter().filter(|pass| {
+            let get_char_from_password = |policy_pos: usize| -> char { pass.password.as_str().chars().nth(policy_pos - 1).expect("policy_pos is out of range") };
+            (get_char_from_password(pass.policy_first) == pass.policy_char) ^ (get_char_from_password(pass.policy_second) == pass.policy_char)
+        }).count())
+    }
 }
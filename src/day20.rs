@@ -1,24 +1,34 @@
 use std::{
     cmp,
     collections::{HashMap, HashSet},
-    iter::from_fn,
 };
 use num_integer::Roots;
 use regex::Regex;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    grid::Grid,
+    solver::{NoParams, Solver},
+};
 
 #[derive(Debug, Clone)]
-struct TileBorder {
-    hash: usize,
+pub struct TileBorder {
+    pub hash: usize,
 }
 
 impl TileBorder {
     fn new(hash: usize) -> Self {
         Self {
-            hash: hash,
+            hash,
         }
     }
 
-    fn flip(&self) -> Self {
+    // Same key for both reading directions of a border
+    pub fn key(&self) -> usize {
+        cmp::min(self.hash, self.flip().hash)
+    }
+
+    pub fn flip(&self) -> Self {
         let mut new_hash = 0usize;
         for i in 0..10 {
             new_hash <<= 1;
@@ -31,32 +41,37 @@ impl TileBorder {
 }
 
 #[derive(Debug, Clone)]
-struct Camera {
-    id: usize,
-    north: TileBorder,
-    east: TileBorder,
-    south: TileBorder,
-    west: TileBorder,
-    photo: Vec<Vec<bool>>
+pub struct Camera {
+    pub id: usize,
+    pub north: TileBorder,
+    pub east: TileBorder,
+    pub south: TileBorder,
+    pub west: TileBorder,
+    pub photo: Grid<bool>,
+}
+
+// Borders are read left to right and top to bottom
+fn border_hash<'a, I: Iterator<Item=&'a bool>>(pixels: I) -> usize {
+    pixels.fold(0usize, |acc, &x| (acc << 1) + x as usize)
 }
 
 impl Camera {
-    fn new(id: usize, photo: Vec<Vec<bool>>) -> Self {
-        let north = photo[0].iter().fold(0usize, |acc, &x| (acc << 1) + x as usize);
-        let east = photo.iter().map(|l| l.last().unwrap_or(&false)).fold(0usize, |acc, &x| (acc << 1) + x as usize);
-        let south = photo.last().unwrap_or(&vec![]).iter().fold(0usize, |acc, &x| (acc << 1) + x as usize);
-        let west = photo.iter().map(|l| l[0]).fold(0usize, |acc, x| (acc << 1) + x as usize);
+    pub fn new(id: usize, photo: Grid<bool>) -> Self {
+        let north = border_hash(photo.row(0).iter());
+        let east = border_hash(photo.column(photo.width() - 1));
+        let south = border_hash(photo.row(photo.height() - 1).iter());
+        let west = border_hash(photo.column(0));
         Self {
-            id: id,
+            id,
             north: TileBorder::new(north),
             east: TileBorder::new(east),
             south: TileBorder::new(south),
             west: TileBorder::new(west),
-            photo: photo,
+            photo,
         }
     }
 
-    fn all_possible_borders(&self) -> Vec<TileBorder> {
+    pub fn all_possible_borders(&self) -> Vec<TileBorder> {
         vec![
             self.north.clone(),
             self.east.clone(),
@@ -69,377 +84,174 @@ impl Camera {
         ]
     }
 
-    fn get_trimmed_photo(&self, cut_north: bool, cut_south: bool, cut_east: bool, cut_west: bool) -> Vec<Vec<bool>> {
-        let first_i = if cut_north { 1 } else { 0 };
-        let last_i = if cut_south { self.photo.len() - 1 } else { self.photo.len() };
-        let first_j = if cut_west { 1 } else { 0 };
-        let last_j = if cut_east { self.photo[0].len() - 1 } else { self.photo[0].len() };
-        self.photo[first_i..last_i].iter().map(|line| line[first_j..last_j].to_vec()).collect()
-    }
-
-    // fn rotations(&self) -> Vec<Self> {
-    //     vec![
-    //         self.clone(),
-    //         Self {
-    //             id: self.id,
-    //             north: self.east.clone(),
-    //             east: self.south.clone(),
-    //             south: self.west.clone(),
-    //             west: self.north.clone(),
-    //         },
-    //         Self {
-    //             id: self.id,
-    //             north: self.south.clone(),
-    //             east: self.west.clone(),
-    //             south: self.north.clone(),
-    //             west: self.east.clone(),
-    //         },
-    //         Self {
-    //             id: self.id,
-    //             north: self.west.clone(),
-    //             east: self.north.clone(),
-    //             south: self.east.clone(),
-    //             west: self.south.clone(),
-    //         },
-    //     ]
-    // }
-
-    fn rotate_cw(&self) -> Self {
-        Self {
-            id: self.id,
-            north: self.west.clone(),
-            east: self.north.clone(),
-            south: self.east.clone(),
-            west: self.south.clone(),
-            photo: matrix_rotate_cw(&self.photo),
-        }
-    }
-
-    fn horizontal_flip(&self) -> Self {
-        Self {
-            id: self.id,
-            north: self.north.flip(),
-            east: self.west.clone(),
-            south: self.south.flip(),
-            west: self.east.clone(),
-            photo: matrix_flip_h(&self.photo),
-        }
-    }
-
-    fn vertical_flip(&self) -> Self {
-        Self {
-            id: self.id,
-            north: self.south.clone(),
-            east: self.east.flip(),
-            south: self.north.clone(),
-            west: self.west.flip(),
-            photo: matrix_flip_v(&self.photo),
-        }
-    }
-
-    fn get_possibilities_iter<'a>(&'a self) -> impl Iterator<Item=Self> + 'a {
-        let mut counter = 0usize;
-        let mut curr_copy = self.clone();
-        from_fn(move || {
-            match counter {
-                16 => None,
-                3 => {
-                    let to_return = curr_copy.clone();
-                    curr_copy = self.horizontal_flip();
-                    counter += 1;
-                    Some(to_return)
-                },
-                7 => {
-                    let to_return = curr_copy.clone();
-                    curr_copy = self.vertical_flip();
-                    counter += 1;
-                    Some(to_return)
-                },
-                11 => {
-                    let to_return = curr_copy.clone();
-                    curr_copy = self.horizontal_flip().vertical_flip();
-                    counter += 1;
-                    Some(to_return)
-                },
-                _ => {
-                    let to_return = curr_copy.clone();
-                    curr_copy = to_return.rotate_cw();
-                    counter += 1;
-                    Some(to_return)
-                }
-            }
-        })
+    // The photo without its borders
+    pub fn get_trimmed_photo(&self) -> Grid<bool> {
+        self.photo.sub_grid(1, 1, self.photo.width() - 2, self.photo.height() - 2)
     }
-}
-
-fn matrix_rotate_cw<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    let n = matrix.len();
-    let mut new_matrix: Vec<Vec<T>> = vec![Vec::new(); n];
-    for i in 0..n {
-        for j in 0..n {
-            new_matrix[i].push(matrix[n - j - 1][i].clone());
-        }
-    }
-    new_matrix
-}
 
-fn matrix_flip_h<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    matrix.iter().map(|line| line.iter().rev().map(|x| x.clone()).collect()).collect()
-}
-
-fn matrix_flip_v<T: Clone>(matrix: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    matrix.iter().rev().map(|x| x.clone()).collect()
-}
-
-fn get_data() -> Vec<Camera> {
-    let mut lines = super::file::read_file("./inputs/day20.txt");
-    let mut cameras = Vec::<Camera>::new();
-    loop {
-        match lines.next() {
-            Some(line) => {
-                lazy_static! {
-                    static ref RE: Regex = Regex::new(r"^Tile (\d+):$").expect("invalid tile number regex");
-                }
-                let caps = RE.captures(&line).expect("line did not match tile number regex");
-                let id: usize = caps[1].parse().expect("invalid tile ID");
-                let mut photo: Vec<Vec<bool>> = Vec::with_capacity(10);
-                for _ in 0..10 {
-                    let line = lines.next().unwrap();
-                    photo.push(line.chars().map(|c| {
-                        match c {
-                            '#' => true,
-                            '.' => false,
-                            _ => panic!("invalid character in photo"),
-                        }
-                    }).collect());
-                }
-                cameras.push(Camera::new(id, photo));
-                match lines.next() {
-                    None => break,
-                    _ => (),
-                }
-            },
-            None => break,
-        };
+    // Every rotation and flip of this camera; borders are recomputed from the transformed photo,
+    // since their reading direction may change as well
+    pub fn get_possibilities_iter(&self) -> impl Iterator<Item=Self> + '_ {
+        self.photo.orientations().into_iter().map(move |photo| Self::new(self.id, photo))
     }
-    cameras
 }
 
-// Part 1
-fn get_product_of_corners(cameras: &Vec<Camera>) -> usize {
+pub fn get_matching_borders(cameras: &[Camera]) -> HashMap<usize, HashSet<usize>> {
     let mut matching_borders_with_cameras: HashMap<usize, HashSet<usize>> = HashMap::new();
     cameras.iter().map(|camera| (camera.id, camera.all_possible_borders())).for_each(|(id, borders)| {
         borders.iter().for_each(|border| {
-            matching_borders_with_cameras.entry(cmp::min(border.hash, border.flip().hash)).or_insert(HashSet::new()).insert(id);
+            matching_borders_with_cameras.entry(border.key()).or_default().insert(id);
         })
     });
-    // println!("{:?}", matching_borders_with_cameras);
-    let mut product = 1usize;
-    let mut count = 0usize;
-    for camera in cameras.iter() {
-        if matching_borders_with_cameras.values().filter(|set| set.iter().collect::<Vec<&usize>>() == vec![&camera.id]).count() == 2 {
-            // println!("Possible border camera: {}", camera.id);
-            product *= camera.id;
-            count += 1;
-        }
-    }
-    if count > 4 {
-        panic!("Unexpected format")
+    matching_borders_with_cameras
+}
+
+// Corners are the only cameras with two borders not shared with any other camera
+pub fn is_corner(camera: &Camera, matching_borders_with_cameras: &HashMap<usize, HashSet<usize>>) -> bool {
+    matching_borders_with_cameras.values().filter(|set| set.iter().collect::<Vec<&usize>>() == vec![&camera.id]).count() == 2
+}
+
+// Part 1
+pub fn get_product_of_corners(cameras: &[Camera]) -> Result<usize> {
+    let matching_borders_with_cameras = get_matching_borders(cameras);
+    let corners: Vec<usize> = cameras.iter()
+        .filter(|camera| is_corner(camera, &matching_borders_with_cameras))
+        .map(|camera| camera.id)
+        .collect();
+    debug!("Corner cameras: {:?}", corners);
+    if corners.len() != 4 {
+        return Err(Error::solve(format!("expected 4 corner cameras, found {}", corners.len())))
     }
-    product
+    Ok(corners.iter().product())
 }
 
 // Part 2
-fn get_image(cameras: &Vec<Camera>) {
+pub fn get_image(cameras: &[Camera]) -> Result<Grid<bool>> {
+    if cameras.is_empty() {
+        return Err(Error::solve("there are no cameras to arrange"))
+    }
     let size = cameras.len().sqrt();
-    assert_eq!(size * size, cameras.len());
-    // let layers = (size / 2) + (size % 2);
-
-    // Find connecting cameras
-    let mut matching_borders_with_cameras: HashMap<usize, HashSet<usize>> = HashMap::new();
-    cameras.iter().map(|camera| (camera.id, camera.all_possible_borders())).for_each(|(id, borders)| {
-        borders.iter().for_each(|border| {
-            matching_borders_with_cameras.entry(cmp::min(border.hash, border.flip().hash)).or_insert(HashSet::new()).insert(id);
-        })
-    });
-
-    // Separate cameras by class (4 corners, all borders, and innermost)
-    let mut corner_cameras = cameras.iter().filter(|camera| {
-        matching_borders_with_cameras.values()
-            // Find all borders of the camera not shared with any other camera
-            .filter(|set| set.iter().collect::<Vec<&usize>>() == vec![&camera.id])
-            // If it equals 2, this is a corner camera
-            .count() == 2
-    }).collect::<Vec<_>>();
-    let border_cameras = cameras.iter().filter(|camera| {
-        matching_borders_with_cameras.values()
-            // Find all borders of the camera not shared with any other camera
-            .filter(|set| set.iter().collect::<Vec<&usize>>() == vec![&camera.id])
-            // If it is greater than or equal to, this is a border camera
-            .count() >= 1
-    }).collect::<Vec<_>>();
-    let inner_cameras = cameras.iter().filter(|camera| {
-        matching_borders_with_cameras.values()
-            // Find all borders of the camera not shared with any other camera
-            .filter(|set| set.iter().collect::<Vec<&usize>>() == vec![&camera.id])
-            // If it is greater than or equal to, this is a border camera
-            .count() == 0
-    }).collect::<Vec<_>>();
+    if size * size != cameras.len() {
+        return Err(Error::solve(format!("{} cameras can't be arranged in a square", cameras.len())))
+    }
+    let matching_borders_with_cameras = get_matching_borders(cameras);
+    let is_outer_border = |border: &TileBorder| matching_borders_with_cameras[&border.key()].len() == 1;
 
-    // Choose one of the four corners randomly and place into first diagonal (i.e. [0][0])
-    let mut camera_array: Vec<Vec<Camera>> = vec![vec![Camera::new(0, vec![vec![false]]); size]; size];
-    camera_array[0][0] = corner_cameras.pop().unwrap().clone();
+    // Place cameras line by line, starting from a corner whose unmatched borders face north and west
+    let mut camera_array: Vec<Vec<Camera>> = Vec::with_capacity(size);
     let mut consumed_ids = HashSet::<usize>::new();
-    consumed_ids.insert(camera_array[0][0].id);
-
-    // Build the second diagonal
-    let mut second_diagonal_cameras = cameras.iter().map(|camera| {
-        let matches_border = matching_borders_with_cameras.values()
-            // Find all borders of the camera shared with the cornermost camera
-            .filter(|set| set.contains(&camera_array[0][0].id) && set.contains(&camera.id))
-            .count() == 1;
-        if matches_border {
-            Some(camera.clone())
-        } else {
-            None
+    for i in 0..size {
+        let mut line: Vec<Camera> = Vec::with_capacity(size);
+        for j in 0..size {
+            let camera = if i == 0 && j == 0 {
+                cameras.iter()
+                    .find(|camera| is_corner(camera, &matching_borders_with_cameras))
+                    .ok_or_else(|| Error::solve("couldn't find a corner camera"))?
+                    .get_possibilities_iter()
+                    .find(|possibility| is_outer_border(&possibility.north) && is_outer_border(&possibility.west))
+            } else {
+                let border_to_match = if j > 0 { &line[j - 1].east } else { &camera_array[i - 1][j].south };
+                matching_borders_with_cameras[&border_to_match.key()].iter()
+                    .filter(|id| !consumed_ids.contains(id))
+                    .flat_map(|id| cameras.iter().find(|camera| camera.id == *id).unwrap().get_possibilities_iter())
+                    .find(|possibility| {
+                        (j == 0 || possibility.west.hash == line[j - 1].east.hash) &&
+                        (i == 0 || possibility.north.hash == camera_array[i - 1][j].south.hash)
+                    })
+            }.ok_or_else(|| Error::solve(format!("couldn't find a camera for position ({}, {})", i, j)))?;
+            consumed_ids.insert(camera.id);
+            line.push(camera);
         }
-    }).flatten().collect::<Vec<_>>();
-    assert_eq!(second_diagonal_cameras.len(), 2);
-    camera_array[0][1] = second_diagonal_cameras.pop().unwrap();
-    camera_array[1][0] = second_diagonal_cameras.pop().unwrap();
-    consumed_ids.insert(camera_array[0][1].id);
-    consumed_ids.insert(camera_array[1][0].id);
+        trace!("Row {} of cameras: {:?}", i, line.iter().map(|c| c.id).collect::<Vec<usize>>());
+        camera_array.push(line);
+    }
+    debug!("Camera arrangement:\n{}", camera_array.iter()
+        .map(|line| line.iter().map(|c| c.id.to_string()).collect::<Vec<String>>().join(" "))
+        .collect::<Vec<String>>()
+        .join("\n"));
 
-    // Build the remaining increasing diagonals
-    for i in 2..size {
-        for j in 0..i + 1 {
-            let mut diagonal_camera_vec = (if j == 0 || j == i { border_cameras.iter() } else { inner_cameras.iter() }).filter(|camera| !consumed_ids.contains(&camera.id)).map(|camera| {
-                let matches_border = matching_borders_with_cameras.values()
-                    // Find all borders of the camera not shared with any other camera
-                    .filter(|set| {
-                        if j == 0 {
-                            let neighbor_id = &camera_array[j][i - j - 1].id;
-                            set.contains(neighbor_id) && set.contains(&camera.id)
-                        } else if j == i {
-                            let neighbor_id = &camera_array[j - 1][i - j].id;
-                            set.contains(neighbor_id) && set.contains(&camera.id)
-                        } else {
-                            let first_neighbor_id = &camera_array[j][i - j - 1].id;
-                            let second_neighbor_id = &camera_array[j - 1][i - j].id;
-                            (set.contains(first_neighbor_id) || set.contains(second_neighbor_id)) && set.contains(&camera.id)
-                        }
-                    })
-                    .count() == (if j == 0 || j == i { 1 } else { 2 });
-                if matches_border {
-                    Some(camera.clone())
-                } else {
-                    None
-                }
-            }).flatten().collect::<Vec<_>>();
-            // println!("{:?}", diagonal_camera_vec.iter().map(|c| c.id).collect::<Vec<_>>());
-            assert_eq!(diagonal_camera_vec.len(), 1);
-            camera_array[j][i - j] = diagonal_camera_vec.pop().unwrap().clone();
-            consumed_ids.insert(camera_array[j][i - j].id);
+    // Join the photos without their borders
+    let trimmed_photos: Vec<Vec<Grid<bool>>> = camera_array.iter().map(|line| line.iter().map(Camera::get_trimmed_photo).collect()).collect();
+    let photo_size = trimmed_photos[0][0].width();
+    Ok(Grid::from_fn(size * photo_size, size * photo_size, |x, y| {
+        trimmed_photos[y / photo_size][x / photo_size][(x % photo_size, y % photo_size)]
+    }))
+}
+
+pub static SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+// Cells covered by sea monsters in this orientation of the image only
+pub fn find_sea_monsters(image: &Grid<bool>) -> HashSet<(usize, usize)> {
+    let monster_offsets: Vec<(usize, usize)> = SEA_MONSTER.iter().enumerate()
+        .flat_map(|(y, line)| line.chars().enumerate().filter(|(_, c)| *c == '#').map(move |(x, _)| (x, y)))
+        .collect();
+    let monster_height = SEA_MONSTER.len();
+    let monster_width = SEA_MONSTER[0].len();
+    let mut monster_cells = HashSet::<(usize, usize)>::new();
+    if image.width() < monster_width || image.height() < monster_height {
+        return monster_cells
+    }
+    for y in 0..=image.height() - monster_height {
+        for x in 0..=image.width() - monster_width {
+            if monster_offsets.iter().all(|(dx, dy)| image[(x + dx, y + dy)]) {
+                monster_offsets.iter().for_each(|(dx, dy)| { monster_cells.insert((x + dx, y + dy)); });
+            }
         }
     }
+    monster_cells
+}
 
-    // Build the remaining decreasing diagonals
-    for i in size..(size * 2) - 1 {
-        for j in i - size + 1..size {
-            let mut diagonal_camera_vec = (if j == i - size + 1 || j == size - 1 { border_cameras.iter() } else { inner_cameras.iter() }).filter(|camera| !consumed_ids.contains(&camera.id)).map(|camera| {
-                let matches_border = matching_borders_with_cameras.values()
-                    // Find all borders of the camera not shared with any other camera
-                    .filter(|set| {
-                        if j == i - size + 1 {
-                            let neighbor_id = &camera_array[i - j][j - 1].id;
-                            set.contains(neighbor_id) && set.contains(&camera.id)
-                        } else if j == size - 1 {
-                            let neighbor_id = &camera_array[i - j - 1][j].id;
-                            set.contains(neighbor_id) && set.contains(&camera.id)
-                        } else {
-                            let first_neighbor_id = &camera_array[i - j][j - 1].id;
-                            let second_neighbor_id = &camera_array[i - j - 1][j].id;
-                            (set.contains(first_neighbor_id) || set.contains(second_neighbor_id)) && set.contains(&camera.id)
-                        }
-                    })
-                    .count() == (if j == i - size + 1 || j == size - 1 { 1 } else { 2 });
-                if matches_border {
-                    Some(camera.clone())
-                } else {
-                    None
-                }
-            }).flatten().collect::<Vec<_>>();
-            // println!("{:?}", diagonal_camera_vec.iter().map(|c| c.id).collect::<Vec<_>>());
-            assert_eq!(diagonal_camera_vec.len(), 1);
-            println!("{} {}", i, j);
-            camera_array[i - j][j] = diagonal_camera_vec.pop().unwrap().clone();
-            consumed_ids.insert(camera_array[i - j][j].id);
+// Part 2
+pub fn count_rough_waters(image: &Grid<bool>) -> Result<usize> {
+    let total_rough_waters = image.iter().filter(|x| **x).count();
+
+    // Try every orientation until the monsters show up
+    for orientation in image.orientations() {
+        let monster_cells = find_sea_monsters(&orientation);
+        if !monster_cells.is_empty() {
+            debug!("Found sea monsters covering {} cells", monster_cells.len());
+            trace!("Image with sea monsters:\n{}", orientation);
+            return Ok(total_rough_waters - monster_cells.len())
         }
     }
+    Err(Error::solve("couldn't find any sea monsters"))
+}
 
-    camera_array.iter().for_each(|line| {
-        println!("{:?}", line.iter().map(|c| c.id).collect::<Vec<_>>());
-    });
+pub struct Day20;
 
-    let mut final_camera_array = camera_array.clone();
+impl Solver for Day20 {
+    type Input = Vec<Camera>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
 
-    // Align cameras properly and generate full image
-    for i in 0..size {
-        for j in 0..size {
-            let mut found = false;
-            for possibility in camera_array[i][j].get_possibilities_iter() {
-                let mut is_valid = true;
-                if i == 0 && j == 0 {
-                    if matching_borders_with_cameras.get(cmp::min(&possibility.north.flip().hash, &possibility.north.hash)).unwrap().len() != 1 {
-                        is_valid = false;
-                    } else if matching_borders_with_cameras.get(cmp::min(&possibility.west.flip().hash, &possibility.west.hash)).unwrap().len() != 1 {
-                        is_valid = false;
-                    }
-                // } else if i == 0 && j == 1 {
-                // TODO: Do some rotations/flips on [0][0] to match this one's border; all the other tiles shouldn't need to modify previous ones.
-                // But at this point I'm still not close to finishing the exercise... It would still require joining the images and finding the monsters.
-                } else if i == 0 {
-                    if matching_borders_with_cameras.get(cmp::min(&possibility.north.flip().hash, &possibility.north.hash)).unwrap().len() != 1 {
-                        is_valid = false;
-                    } else if possibility.west.hash != camera_array[i][j - 1].east.hash {
-                        is_valid = false;
-                    }
-                } else if j == 0 {
-                    if matching_borders_with_cameras.get(cmp::min(&possibility.west.flip().hash, &possibility.west.hash)).unwrap().len() != 1 {
-                        is_valid = false;
-                    } else if possibility.north.hash != camera_array[i - 1][j - 1].south.hash {
-                        is_valid = false;
-                    }
-                } else {
-                    if possibility.west.hash != camera_array[i][j - 1].east.hash {
-                        is_valid = false;
-                    } else if possibility.north.hash != camera_array[i - 1][j - 1].south.hash {
-                        is_valid = false;
-                    }
-                }
-                if is_valid {
-                    final_camera_array[i][j] = possibility;
-                    found = true;
-                    break;
-                }
+    fn parse(mut lines: Lines) -> Result<Vec<Camera>> {
+        let mut cameras = Vec::<Camera>::new();
+        while let Some(mut tile) = lines.next_record() {
+            let line = tile.next().expect("records have at least one line");
+            lazy_static! {
+                static ref RE: Regex = Regex::new(r"^Tile (\d+):$").expect("invalid tile number regex");
             }
-            if !found {
-                panic!("couldn't find correct orientation for camera");
+            let caps = RE.captures(&line).ok_or_else(|| line.error(1, "expected a tile header like \"Tile 1234:\""))?;
+            let id: usize = line.parse_at(caps.get(1).unwrap().as_str(), "tile ID")?;
+            let photo: Grid<bool> = Grid::parse(&mut tile, "a line of the photo")?;
+            if photo.width() != 10 || photo.height() != 10 {
+                return Err(line.error(1, format!("expected a 10x10 photo after this header, found {}x{}", photo.width(), photo.height())))
             }
-            println!("ok")
+            cameras.push(Camera::new(id, photo));
         }
+        Ok(cameras)
     }
 
-    println!("cameras are okay!");
-}
-
-pub fn main() {
-    let data = get_data();
-    
-    // Part 1
-    // let product = get_product_of_corners(&data);
-    // println!("Product: {}", product);
+    fn part1(cameras: &Vec<Camera>) -> Result<usize> {
+        get_product_of_corners(cameras)
+    }
 
-    // Part 2
-    get_image(&data);
-}
\ No newline at end of file
+    fn part2(cameras: &Vec<Camera>) -> Result<usize> {
+        count_rough_waters(&get_image(cameras)?)
+    }
+}
@@ -1,10 +1,14 @@
-fn get_map() -> Vec<Vec<bool>> {
-    super::file::read_file("./inputs/day3.txt")
-        .map(|l| l.chars().map(|c| c == '#').collect())
-        .collect()
-}
+use serde::Deserialize;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    geometry::Vec2,
+    grid::Grid,
+    progress::Progress,
+    solver::Solver,
+};
 
-static SLOPE_LIST: [(usize, usize); 5] = [
+pub static SLOPE_LIST: [(usize, usize); 5] = [
     (1, 1),
     (3, 1),
     (5, 1),
@@ -12,27 +16,77 @@ static SLOPE_LIST: [(usize, usize); 5] = [
     (1, 2),
 ];
 
-pub fn main () {
-    let map = get_map();
-    let height = map.len();
-    let width = map[0].len();
-    let mut results: [usize; 5] = [0; 5];
-    for (i, (step_right, step_down)) in SLOPE_LIST.iter().enumerate() {
-        let mut tree_count = 0usize;
-        let mut x = 0usize;
-        let mut y = 0usize;
-        loop {
-            x = (x + step_right) % width;
-            y = y + step_down;
-            if y >= height {
-                break;
-            }
-            if map[y][x] {
-                tree_count += 1;
-            }
+// Slopes are (right, down)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    pub slope: (usize, usize),
+    pub slopes: Vec<(usize, usize)>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { slope: (3, 1), slopes: SLOPE_LIST.to_vec() }
+    }
+}
+
+// The map repeats to the right, so only going past the bottom ends the slope
+pub fn count_trees(map: &Grid<bool>, step_right: usize, step_down: usize) -> usize {
+    let slope = Vec2::from((step_right, step_down));
+    let mut tree_count = 0usize;
+    let mut position = Vec2::ZERO;
+    loop {
+        position += slope;
+        if position.y as usize >= map.height() {
+            break;
         }
-        results[i] = tree_count;
-        println!("Slope #{}: encountered {} trees", i, tree_count)
+        if *map.get_wrapping(position) {
+            tree_count += 1;
+        }
+    }
+    debug!("Slope right {} down {}: {} trees", step_right, step_down, tree_count);
+    tree_count
+}
+
+// Slopes that don't go down would never reach the bottom
+fn check_slope((step_right, step_down): (usize, usize)) -> Result<(usize, usize)> {
+    if step_down == 0 {
+        return Err(Error::solve(format!("slope right {} down {} never reaches the bottom", step_right, step_down)))
+    }
+    Ok((step_right, step_down))
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = Grid<bool>;
+    type Params = Params;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(mut lines: Lines) -> Result<Grid<bool>> {
+        let map = Grid::parse(&mut lines, "map lines")?;
+        lines.expect_end()?;
+        Ok(map)
+    }
+
+    fn part1(map: &Grid<bool>) -> Result<usize> {
+        Self::part1_with(map, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part2(map: &Grid<bool>) -> Result<usize> {
+        Self::part2_with(map, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part1_with(map: &Grid<bool>, params: &Params, _progress: &Progress) -> Result<usize> {
+        let (step_right, step_down) = check_slope(params.slope)?;
+        Ok(count_trees(map, step_right, step_down))
+    }
+
+    fn part2_with(map: &Grid<bool>, params: &Params, _progress: &Progress) -> Result<usize> {
+        params.slopes.iter().try_for_each(|slope| check_slope(*slope).map(|_| ()))?;
+        Ok(params.slopes.iter()
+            .map(|(step_right, step_down)| count_trees(map, *step_right, *step_down))
+            .product())
     }
-    println!("Product of all results: {}", results.iter().product::<usize>())
 }
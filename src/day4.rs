@@ -1,6 +1,11 @@
 use regex::Regex;
+use super::{
+    error::Result,
+    file::Lines,
+    solver::{NoParams, Solver},
+};
 
-static REQUIRED_FIELDS: [&str; 7] = [
+pub static REQUIRED_FIELDS: [&str; 7] = [
     "byr",
     "iyr",
     "eyr",
@@ -9,11 +14,12 @@ static REQUIRED_FIELDS: [&str; 7] = [
     "ecl",
     "pid",
 ];
-static OPTIONAL_FIELDS: [&str; 1] = [
+pub static OPTIONAL_FIELDS: [&str; 1] = [
     "cid",
 ];
 
-fn validate_passport(passport: &Vec<String>) -> bool {
+// Part 2
+pub fn validate_passport(passport: &[String]) -> bool {
     let mut validated_fields: Vec<&str> = Vec::new();
     for field_description in passport.iter() {
         let mut field_split = field_description.splitn(2, ':');
@@ -33,7 +39,7 @@ fn validate_passport(passport: &Vec<String>) -> bool {
                 match value.parse::<usize>() {
                     Err(_) => return false,
                     Ok(year) => {
-                        if year < 1920 || year > 2002 {
+                        if !(1920..=2002).contains(&year) {
                             return false
                         }
                     },
@@ -43,7 +49,7 @@ fn validate_passport(passport: &Vec<String>) -> bool {
                 match value.parse::<usize>() {
                     Err(_) => return false,
                     Ok(year) => {
-                        if year < 2010 || year > 2020 {
+                        if !(2010..=2020).contains(&year) {
                             return false
                         }
                     },
@@ -53,7 +59,7 @@ fn validate_passport(passport: &Vec<String>) -> bool {
                 match value.parse::<usize>() {
                     Err(_) => return false,
                     Ok(year) => {
-                        if year < 2020 || year > 2030 {
+                        if !(2020..=2030).contains(&year) {
                             return false
                         }
                     },
@@ -62,18 +68,21 @@ fn validate_passport(passport: &Vec<String>) -> bool {
             "hgt" => { // (Height) - a number followed by either cm or in:
                        // If cm, the number must be at least 150 and at most 193.
                        // If in, the number must be at least 59 and at most 76.
+                if value.len() < 2 {
+                    return false
+                }
                 let (value_num, unit) = value.split_at(value.len() - 2);
                 match value_num.parse::<usize>() {
                     Err(_) => return false,
                     Ok(height) => {
                         match unit {
                             "cm" => {
-                                if height < 150 || height > 193 {
+                                if !(150..=193).contains(&height) {
                                     return false
                                 }
                             },
                             "in" => {
-                                if height < 59 || height > 76 {
+                                if !(59..=76).contains(&height) {
                                     return false
                                 }
                             },
@@ -86,20 +95,19 @@ fn validate_passport(passport: &Vec<String>) -> bool {
                 lazy_static! {
                     static ref RE: Regex = Regex::new(r"^#[0-9a-f]{6}$").expect("invalid hcl regex");
                 }
-                if !RE.is_match(&value) {
+                if !RE.is_match(value) {
                     return false
                 }
             }
-            "ecl" => { // (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
-                if !["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value) {
+            "ecl" // (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
+                if !["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value) => {
                     return false
                 }
-            }
             "pid" => { // (Passport ID) - a nine-digit number, including leading zeroes.
                 lazy_static! {
                     static ref RE: Regex = Regex::new(r"^\d{9}$").expect("invalid pid regex");
                 }
-                if !RE.is_match(&value) {
+                if !RE.is_match(value) {
                     return false
                 }
             }
@@ -116,29 +124,49 @@ fn validate_passport(passport: &Vec<String>) -> bool {
     true
 }
 
-fn get_valid_passports() -> Vec<Vec<String>> {
-    let mut valid_passports: Vec<Vec<String>> = Vec::new();
-    let mut current_passport: Vec<String> = Vec::new();
-    super::file::read_file("./inputs/day4.txt").for_each(|l| {
-        if l == "" {
-            if validate_passport(&current_passport) {
-                // TODO: Find out how to give up current reference instead of copying
-                valid_passports.push(current_passport.to_vec())
-            }
-            current_passport = Vec::new()
-        } else {
-            l.split(' ').for_each(|f| current_passport.push(String::from(f)))
-        }
-    });
-    // Final passport
-    if validate_passport(&current_passport) {
-        // TODO: Find out how to give up current reference instead of copying
-        valid_passports.push(current_passport.to_vec())
-    };
-    valid_passports
+// Part 1
+pub fn has_required_fields(passport: &[String]) -> bool {
+    let names: Vec<&str> = passport.iter().map(|f| f.split(':').next().expect("badly formed field name")).collect();
+    let missing: Vec<&&str> = REQUIRED_FIELDS.iter().filter(|required_field| !names.contains(required_field)).collect();
+    if !missing.is_empty() {
+        trace!("{:?} is missing {:?}", passport, missing);
+    }
+    missing.is_empty()
 }
 
-pub fn main () {
-    let passports = get_valid_passports();
-    println!("Valid passports: {}", passports.len())
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = Vec<Vec<String>>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(lines: Lines) -> Result<Vec<Vec<String>>> {
+        // Each passport is a record, with its fields spread over any number of lines
+        lines.records().map(|passport| -> Result<Vec<String>> {
+            let mut fields: Vec<String> = Vec::new();
+            for l in passport {
+                for f in l.split(' ') {
+                    if !f.contains(':') {
+                        return Err(l.error_at(f, format!("expected a field like \"name:value\", found {:?}", f)));
+                    }
+                    fields.push(String::from(f))
+                }
+            }
+            Ok(fields)
+        }).collect()
+    }
+
+    fn part1(passports: &Vec<Vec<String>>) -> Result<usize> {
+        Ok(passports.iter().filter(|p| has_required_fields(p)).count())
+    }
+
+    fn part2(passports: &Vec<Vec<String>>) -> Result<usize> {
+        Ok(passports.iter().filter(|p| {
+            let valid = validate_passport(p);
+            trace!("{:?} is {}", p, if valid { "valid" } else { "invalid" });
+            valid
+        }).count())
+    }
 }
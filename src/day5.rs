@@ -1,53 +1,76 @@
 use std::{
-    iter::Iterator,
+    convert::TryFrom,
+};
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    solver::{NoParams, Solver},
 };
 
-fn to_seat_id(boarding_pass: String) -> usize {
-    usize::from_str_radix(
+pub fn to_seat_id(boarding_pass: &Line) -> Result<usize> {
+    if let Some((i, c)) = boarding_pass.chars().enumerate().find(|(_, c)| !"FBLR".contains(*c)) {
+        return Err(boarding_pass.error(i + 1, format!("invalid character {:?} in boarding pass", c)));
+    }
+    if boarding_pass.is_empty() || boarding_pass.len() > 10 {
+        return Err(boarding_pass.error(1, "boarding pass must have between 1 and 10 characters"));
+    }
+    Ok(usize::from_str_radix(
         &boarding_pass
             .replace("F", "0")
             .replace("B", "1")
             .replace("L", "0")
             .replace("R", "1"),
-        2).unwrap()
+        2).unwrap())
 }
 
-struct Seat {
-    id: usize
+pub struct Seat {
+    pub id: usize
 }
 
 impl Seat {
-    fn row(&self) -> usize {
-        &self.id >> 3
+    pub fn row(&self) -> usize {
+        self.id >> 3
     }
 
-    fn column(&self) -> usize {
-        &self.id % 8
+    pub fn column(&self) -> usize {
+        self.id % 8
     }
 }
 
-impl From<String> for Seat {
-    fn from(item: String) -> Self {
-        Self { id: to_seat_id(item) }
+impl TryFrom<&Line> for Seat {
+    type Error = Error;
+
+    fn try_from(item: &Line) -> Result<Self> {
+        Ok(Self { id: to_seat_id(item)? })
     }
 }
 
-fn get_seats() -> impl Iterator<Item=Seat> {
-    super::file::read_file("./inputs/day5.txt")
-        .map(Seat::from)
-}
+pub struct Day5;
+
+impl Solver for Day5 {
+    // Sorted by seat ID
+    type Input = Vec<Seat>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(lines: Lines) -> Result<Vec<Seat>> {
+        let mut seats: Vec<Seat> = lines.map(|l| Seat::try_from(&l)).collect::<Result<_>>()?;
+        seats.sort_by_key(|s| s.id);
+        Ok(seats)
+    }
+
+    fn part1(seats: &Vec<Seat>) -> Result<usize> {
+        seats.last().map(|s| s.id).ok_or_else(|| Error::solve("there are no seats"))
+    }
 
-pub fn main () {
-    let mut seats: Vec<Seat> = get_seats().collect();
-    seats.sort_by_key(|s| s.id);
-    println!("Min seat ID: {}", seats.first().unwrap().id);
-    println!("Max seat ID: {}", seats.last().unwrap().id);
-    // for seat in seats {
-    //     println!("Row {} column {} seat ID {}", seat.row(), seat.column(), seat.id)
-    // }
-    for i in 1..seats.len() {
-        if seats[i].id - seats[i - 1].id > 1 {
-            println!("Missing seat(s) between {} and {}!", seats[i - 1].id, seats[i].id)
-        } 
+    fn part2(seats: &Vec<Seat>) -> Result<usize> {
+        for i in 1..seats.len() {
+            if seats[i].id - seats[i - 1].id > 1 {
+                debug!("Missing seat(s) between {} and {}", seats[i - 1].id, seats[i].id);
+                return Ok(seats[i - 1].id + 1)
+            }
+        }
+        Err(Error::solve("couldn't find a missing seat"))
     }
 }
@@ -1,6 +1,11 @@
+use super::{
+    error::Result,
+    file::Lines,
+    solver::{NoParams, Solver},
+};
 
 // Part 1
-fn count_unique_answers(answers: &Vec<String>) -> usize {
+pub fn count_unique_answers(answers: &[String]) -> usize {
     let mut unique_answers: Vec<char> = Vec::new();
     for answer in answers.iter().flat_map(|a| a.chars()) {
         if !unique_answers.contains(&answer) {
@@ -11,39 +16,45 @@ fn count_unique_answers(answers: &Vec<String>) -> usize {
 }
 
 // Part 2
-fn count_matching_answers(answers: &Vec<String>) -> usize {
+pub fn count_matching_answers(answers: &[String]) -> usize {
     let mut answers_iter = answers.iter();
     let mut matching_answers: Vec<char> = answers_iter.next().unwrap().chars().collect();
     for answer in answers_iter.map(|a| -> Vec<char> { a.chars().collect() } ) {
         matching_answers = matching_answers
             .iter()
-            .filter(|a| answer.contains(&a))
-            .map(|a| a.clone())
+            .filter(|a| answer.contains(a))
+            .cloned()
             .collect();
     }
-    // println!("{:?}\n{:?}\n", answers, matching_answers);
+    trace!("{:?} all answered {:?}", answers, matching_answers);
     matching_answers.len()
 }
 
-fn get_total_answers() -> usize {
-    let mut total_answers: usize = 0;
-    let mut current_group: Vec<String> = Vec::new();
-    super::file::read_file("./inputs/day6.txt").for_each(|l| {
-        if l == "" {
-            // total_answers += count_unique_answers(&current_group);
-            total_answers += count_matching_answers(&current_group);
-            current_group = Vec::new()
-        } else {
-            current_group.push(l)
-        }
-    });
-    // Final group
-    // total_answers += count_unique_answers(&current_group);
-    total_answers += count_matching_answers(&current_group);
-    total_answers
-}
+pub struct Day6;
+
+impl Solver for Day6 {
+    // Groups of answers, one string per person
+    type Input = Vec<Vec<String>>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(lines: Lines) -> Result<Vec<Vec<String>>> {
+        lines.records().map(|group| {
+            group.map(|l| {
+                if let Some((i, c)) = l.chars().enumerate().find(|(_, c)| !c.is_ascii_lowercase()) {
+                    return Err(l.error(i + 1, format!("invalid answer {:?}", c)));
+                }
+                Ok(l.into_text())
+            }).collect()
+        }).collect()
+    }
+
+    fn part1(groups: &Vec<Vec<String>>) -> Result<usize> {
+        Ok(groups.iter().map(|g| count_unique_answers(g)).sum())
+    }
 
-pub fn main () {
-    let answers = get_total_answers();
-    println!("Total answers: {}", answers)
+    fn part2(groups: &Vec<Vec<String>>) -> Result<usize> {
+        Ok(groups.iter().map(|g| count_matching_answers(g)).sum())
+    }
 }
@@ -1,9 +1,17 @@
-use std::{
-    collections::{HashSet, HashMap},
-};
 use regex::Regex;
+use serde::Deserialize;
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    graph::{Cycle, Graph, NodeId},
+    progress::Progress,
+    solver::Solver,
+};
 
-fn parse_rule(rule: String) -> (String, Vec<(usize, String)>) {
+// Edges go from each bag color to the colors it contains, weighted by how many of them
+pub type BagRules = Graph<String, usize>;
+
+pub fn parse_rule(rule: &Line) -> Result<(String, Vec<(usize, String)>)> {
     // X bags contain Y1 Z1 bags, Y2 Z2 bag.
     //  =>
     // (X, [(Y1, Z1), (Y2, Z2)])
@@ -11,94 +19,107 @@ fn parse_rule(rule: String) -> (String, Vec<(usize, String)>) {
         static ref RE: Regex = Regex::new(r"^(\d+) ([a-z]+ [a-z]+) bags?\.?$").expect("invalid parse_rule regex");
     }
     let mut split = rule.splitn(2, " bags contain ");
-    let container = split.next().expect("missing container in rule");
-    let contents = split.next().expect("missing contents in rule");
+    let container = split.next().expect("splitn always returns at least one item");
+    let contents = split.next().ok_or_else(|| rule.error(1, "expected a rule like \"X bags contain ...\""))?;
     let parsed_contents = contents.split(", ")
         .filter(|item| item.trim() != "no other bags.")
-        .map(|item| -> (usize, String) {
-            let caps = RE.captures(item).expect("regex did not match line");
-            (caps[1].parse().expect("bag count is not int"), caps[2].to_string())
+        .map(|item| -> Result<(usize, String)> {
+            let caps = RE.captures(item).ok_or_else(|| rule.error_at(item, format!("expected bag contents like \"1 bright white bag\", found {:?}", item)))?;
+            Ok((rule.parse_at(caps.get(1).unwrap().as_str(), "bag count")?, caps[2].to_string()))
         })
-        .collect();
-    (String::from(container), parsed_contents)
+        .collect::<Result<_>>()?;
+    Ok((String::from(container), parsed_contents))
 }
 
-// Part 1
-fn get_contained_by_map() -> HashMap<String, Vec<String>> {
-    let mut rules: HashMap<String, Vec<String>> = HashMap::new();
-    super::file::read_file("./inputs/day7.txt")
-        .map(parse_rule)
-        .for_each(|rule| {
-            let (container, contents) = rule;
-            contents.iter().for_each(|content| {
-                let (_, bag) = content;
-                match rules.remove(bag) {
-                    Some(mut vec) => {
-                        vec.push(container.clone());
-                        rules.insert(bag.clone(), vec);
-                    },
-                    _ => {
-                        rules.insert(bag.clone(), vec![container.clone()]);
-                    },
-                }
-            });
-        });
-    rules
+fn find_color(rules: &BagRules, color: &str) -> Result<NodeId> {
+    rules.id(&String::from(color)).ok_or_else(|| Error::solve(format!("there is no rule for {} bags", color)))
+}
+
+fn cycle_error(rules: &BagRules, Cycle(cycle): Cycle) -> Error {
+    let colors: Vec<&str> = cycle.iter().chain(cycle.first()).map(|id| rules.label(*id).as_str()).collect();
+    Error::solve(format!("bags contain themselves: {}", colors.join(" -> ")))
 }
 
 // Part 1
-fn get_colors_containing(wanted_color: String) -> HashSet<String> {
-    let mut result: HashSet<String> = HashSet::new();
-    let mut analyzed: Vec<String> = Vec::new();
-    let mut to_analyze: Vec<String> = vec![wanted_color];
-    let map = get_contained_by_map();
-    loop {
-        if to_analyze.is_empty() {
-            break;
-        }
-        let current_color = to_analyze.pop().expect("to_analyze was empty");
-        match map.get(&current_color) {
-            None => (),
-            Some(colors) => {
-                colors.iter().for_each(|c| { result.insert(c.clone()); });
-                colors.iter().filter(|c| !analyzed.contains(c)).for_each(|c| to_analyze.push(c.clone()));
-            }
-        }
-        analyzed.push(current_color);
-    }
-    result
+pub fn get_colors_containing(rules: &BagRules, wanted_color: &str) -> Result<Vec<String>> {
+    let wanted_color = find_color(rules, wanted_color)?;
+    debug!("{} rules for {} colors", rules.nodes().filter(|id| !rules.successors(*id).is_empty()).count(), rules.len());
+    Ok(rules.reversed().reachable_bfs(wanted_color).into_iter().map(|id| rules.label(id).clone()).collect())
 }
 
 // Part 2
-fn get_contains_map() -> HashMap<String, Vec<(usize, String)>> {
-    let mut rules = HashMap::new();
-    super::file::read_file("./inputs/day7.txt")
-        .map(parse_rule)
-        .for_each(|rule| {
-            let (container, contents) = rule;
-            rules.insert(container, contents);
-        });
-    rules
+pub fn get_total_bags_inside(rules: &BagRules, wanted_color: &str) -> Result<usize> {
+    let wanted_color = find_color(rules, wanted_color)?;
+    rules.fold_successors(wanted_color, |id, contents| {
+        let total = contents.iter().map(|(count, inside)| *count * (1 + inside)).sum();
+        trace!("{} bags contain {} bags", rules.label(id), total);
+        total
+    }).map_err(|cycle| cycle_error(rules, cycle))
 }
 
-// Part 2
-fn get_total_bags_inside(wanted_color: &String) -> usize {
-    lazy_static! {
-        static ref MAP: HashMap<String, Vec<(usize, String)>> = get_contains_map();
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    // Color of our own bag
+    pub color: String,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { color: String::from("shiny gold") }
     }
-    let mut total = 0;
-    MAP.get(wanted_color).expect("unknown color in contains_map").iter().for_each(|(count, color)| {
-        total += count * (1 + get_total_bags_inside(color))
-    });
-    total
 }
 
-pub fn main () {
-    // Part 1
-    // let colors = get_colors_containing(String::from("shiny gold"));
-    // println!("All colors that contain a shiny gold bag: {}", colors.len());
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = BagRules;
+    type Params = Params;
+    type Output1 = usize;
+    type Output2 = usize;
 
-    // Part 2
-    let contained = get_total_bags_inside(&String::from("shiny gold"));
-    println!("Bags contained by the gold bag: {}", contained)
+    fn parse(lines: Lines) -> Result<BagRules> {
+        let mut rules = BagRules::new();
+        let mut has_rule: Vec<bool> = Vec::new();
+        let mut mentions: Vec<(Line, String)> = Vec::new();
+        for line in lines {
+            let (container, contents) = parse_rule(&line)?;
+            let container = rules.intern(container);
+            has_rule.resize(rules.len(), false);
+            if has_rule[container] {
+                return Err(line.error(1, format!("{} bags already have a rule", rules.label(container))))
+            }
+            has_rule[container] = true;
+            for (count, color) in contents {
+                let inside = rules.intern(color.clone());
+                rules.add_edge(container, inside, count);
+                mentions.push((line.clone(), color));
+            }
+        }
+        // Every color that shows up inside of a bag must have a rule of its own
+        has_rule.resize(rules.len(), false);
+        for (line, color) in mentions {
+            if !has_rule[rules.id(&color).unwrap()] {
+                let offset = line.rfind(color.as_str()).unwrap();
+                return Err(line.error_at(&line[offset..], format!("there is no rule for {} bags", color)))
+            }
+        }
+        Ok(rules)
+    }
+
+    fn part1(rules: &BagRules) -> Result<usize> {
+        Self::part1_with(rules, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part2(rules: &BagRules) -> Result<usize> {
+        Self::part2_with(rules, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part1_with(rules: &BagRules, params: &Params, _progress: &Progress) -> Result<usize> {
+        Ok(get_colors_containing(rules, &params.color)?.len())
+    }
+
+    fn part2_with(rules: &BagRules, params: &Params, _progress: &Progress) -> Result<usize> {
+        get_total_bags_inside(rules, &params.color)
+    }
 }
@@ -1,94 +1,117 @@
 use std::{
     collections::HashSet,
 };
-use regex::Regex;
+use super::{
+    error::{Error, Result},
+    file::{Line, Lines},
+    solver::{NoParams, Solver},
+};
 
 #[derive(Debug, Clone)]
-enum Instruction {
+pub enum Instruction {
     Accumulator(isize),
     Jump(isize),
     NoOp(isize),
 }
 
-fn parse_instruction(line: String) -> Instruction {
-    let mut split = line.splitn(2, " ");
-    let operation = split.next().expect("missing operation in instruction");
-    let argument = split.next().expect("missing argument in instruction");
+pub fn parse_instruction(line: &Line) -> Result<Instruction> {
+    let mut split = line.splitn(2, ' ');
+    let operation = split.next().expect("splitn always returns at least one item");
+    let argument = split.next().ok_or_else(|| line.error(line.len() + 1, "missing argument in instruction"))?;
     match operation {
-        "acc" => Instruction::Accumulator(argument.parse().expect("invalid acc argument")),
-        "jmp" => Instruction::Jump(argument.parse().expect("invalid jmp argument")),
-        "nop" => Instruction::NoOp(argument.parse().expect("invalid nop argument")),
-        _ => panic!("Invalid operation")
+        "acc" => Ok(Instruction::Accumulator(line.parse_at(argument, "acc argument")?)),
+        "jmp" => Ok(Instruction::Jump(line.parse_at(argument, "jmp argument")?)),
+        "nop" => Ok(Instruction::NoOp(line.parse_at(argument, "nop argument")?)),
+        _ => Err(line.error_at(operation, format!("invalid operation {:?}", operation))),
     }
 }
 
-// Part 1
-fn run_code_until_loop() {
-    let mut acc = 0isize;
-    let mut head = 0usize;
-    let mut prev_heads: HashSet<usize> = HashSet::new();
-    let code: Vec<Instruction> = super::file::read_file("./inputs/day8.txt").map(parse_instruction).collect();
-    loop {
-        if prev_heads.contains(&head) {
-            println!("LOOP DETECTED!");
-            println!("Current acc: {}", acc);
-            return
-        }
-        prev_heads.insert(head);
-        match code[head] {
-            Instruction::Accumulator(inc) => {
-                acc += inc;
-                head += 1;
+pub enum ExitStatus {
+    LoopDetected(isize),
+    Terminated(isize),
+}
+
+// State of the handheld between two instructions; the head is the index of the next instruction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Machine {
+    pub acc: isize,
+    pub head: usize,
+}
+
+impl Machine {
+    pub fn is_terminated(&self, code: &[Instruction]) -> bool {
+        self.head >= code.len()
+    }
+
+    // Runs the instruction at the head, unless the code already terminated
+    pub fn step(&mut self, code: &[Instruction]) {
+        match code.get(self.head) {
+            Some(Instruction::Accumulator(inc)) => {
+                self.acc += inc;
+                self.head += 1;
             },
-            Instruction::Jump(inc) => {
-                head = (head as isize + inc) as usize;
+            Some(Instruction::Jump(inc)) => {
+                self.head = (self.head as isize + inc) as usize;
             },
-            Instruction::NoOp(_) => {
-                head += 1;
+            Some(Instruction::NoOp(_)) => {
+                self.head += 1;
             },
+            None => (),
         }
     }
 }
 
-// Part 2
-fn fix_code() {
-    let original_code: Vec<Instruction> = super::file::read_file("./inputs/day8.txt").map(parse_instruction).collect();
-    for (i, fixable_instruction) in original_code.iter().enumerate() {
-        let mut code = original_code.clone();
-        match fixable_instruction {
-            Instruction::Jump(arg) => code[i] = Instruction::NoOp(arg.clone()),
-            Instruction::NoOp(arg) => code[i] = Instruction::Jump(arg.clone()),
-            _ => continue,
+pub fn run_code(code: &[Instruction]) -> ExitStatus {
+    let mut machine = Machine::default();
+    let mut prev_heads: HashSet<usize> = HashSet::new();
+    loop {
+        if prev_heads.contains(&machine.head) {
+            trace!("Loop detected at instruction {}, with {} in the accumulator", machine.head + 1, machine.acc);
+            return ExitStatus::LoopDetected(machine.acc)
         }
-        let mut acc = 0isize;
-        let mut head = 0usize;
-        let mut prev_heads: HashSet<usize> = HashSet::new();
-        loop {
-            if prev_heads.contains(&head) {
-                // LOOP DETECTED!
-                break
-            }
-            if head >= code.len() {
-                println!("Fixed by changing line {}! Final accumulator is: {}", i + 1, acc);
-                return
-            }
-            prev_heads.insert(head);
-            match code[head] {
-                Instruction::Accumulator(inc) => {
-                    acc += inc;
-                    head += 1;
-                },
-                Instruction::Jump(inc) => {
-                    head = (head as isize + inc) as usize;
-                },
-                Instruction::NoOp(_) => {
-                    head += 1;
-                },
-            }
+        if machine.is_terminated(code) {
+            trace!("Terminated at instruction {}, with {} in the accumulator", machine.head + 1, machine.acc);
+            return ExitStatus::Terminated(machine.acc)
         }
+        prev_heads.insert(machine.head);
+        machine.step(code);
     }
 }
 
-pub fn main () {
-    fix_code()
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Vec<Instruction>;
+    type Params = NoParams;
+    type Output1 = isize;
+    type Output2 = isize;
+
+    fn parse(lines: Lines) -> Result<Vec<Instruction>> {
+        lines.map(|l| parse_instruction(&l)).collect()
+    }
+
+    // Accumulator right before the loop
+    fn part1(code: &Vec<Instruction>) -> Result<isize> {
+        match run_code(code) {
+            ExitStatus::LoopDetected(acc) => Ok(acc),
+            ExitStatus::Terminated(_) => Err(Error::solve("code terminated without a loop")),
+        }
+    }
+
+    // Accumulator after fixing the single corrupted instruction
+    fn part2(original_code: &Vec<Instruction>) -> Result<isize> {
+        for (i, fixable_instruction) in original_code.iter().enumerate() {
+            let mut code = original_code.clone();
+            match fixable_instruction {
+                Instruction::Jump(arg) => code[i] = Instruction::NoOp(*arg),
+                Instruction::NoOp(arg) => code[i] = Instruction::Jump(*arg),
+                _ => continue,
+            }
+            if let ExitStatus::Terminated(acc) = run_code(&code) {
+                debug!("Fixed by changing instruction {} to {:?}", i + 1, code[i]);
+                return Ok(acc)
+            }
+        }
+        Err(Error::solve("no single instruction change makes the code terminate"))
+    }
 }
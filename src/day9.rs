@@ -1,36 +1,28 @@
 use std::{
-    collections::{HashSet, VecDeque},
+    fmt,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    progress::Progress,
+    solver::{Output, Solver},
 };
-use regex::Regex;
 
-fn find_attack_number(preamble_size: usize) -> usize {
-    let mut current_preamble: VecDeque<usize> = VecDeque::new();
-    let mut current_preamble_sums: VecDeque<Vec<usize>> = VecDeque::new();
-    for number in
-        super::file::read_file("./inputs/day9.txt")
-            .map(|n| n.parse::<usize>().expect("line is not an usize"))
-    {
-        if current_preamble.len() == preamble_size {
-            if !current_preamble_sums.iter().any(
-                |sum_vec| sum_vec.iter().any(
-                    |sum| sum == &number
-                )
-            ) {
-                return number;
-            }
-            current_preamble.pop_front();
-            current_preamble_sums.pop_front();
+// Both numbers of a pair must be among the preamble_size numbers right before the one they sum to
+pub fn find_attack_number(number_list: &[usize], preamble_size: usize) -> Result<usize> {
+    for (i, &number) in number_list.iter().enumerate().skip(preamble_size) {
+        let preamble = &number_list[i - preamble_size..i];
+        if !preamble.iter().enumerate().any(|(j, a)| preamble[j + 1..].iter().any(|b| a + b == number)) {
+            debug!("{} is not a sum of two of the {} numbers before it", number, preamble_size);
+            return Ok(number);
         }
-        current_preamble_sums.push_back(current_preamble.iter().map(|n| number + n).collect());
-        current_preamble.push_back(number);
     }
-    panic!("Couldn't find attack number!")
+    Err(Error::solve("every number is a sum of two numbers in its preamble"))
 }
 
-fn find_encryption_weakness(attack_number: usize) -> (usize, usize) {
-    let number_list: Vec<usize> = super::file::read_file("./inputs/day9.txt")
-            .map(|n| n.parse::<usize>().expect("line is not an usize"))
-            .collect();
+pub fn find_encryption_weakness(number_list: &[usize], attack_number: usize) -> Result<(usize, usize)> {
     for i in 0..number_list.len() {
         let mut sum = 0usize;
         let mut min = number_list[i];
@@ -43,11 +35,15 @@ fn find_encryption_weakness(attack_number: usize) -> (usize, usize) {
         loop {
             sum += curr;
             if sum == attack_number {
-                return (min, max)
+                debug!("Numbers {} to {} sum to {}", i + 1, j + 1, attack_number);
+                return Ok((min, max))
             } else if sum > attack_number {
                 break
             }
             j += 1;
+            if j >= number_list.len() {
+                break
+            }
             curr = number_list[j];
             if curr > max {
                 max = curr
@@ -57,12 +53,71 @@ fn find_encryption_weakness(attack_number: usize) -> (usize, usize) {
             }
         }
     }
-    panic!("Couldn't find encryption weakness!")
+    Err(Error::solve(format!("no contiguous range sums to {}", attack_number)))
+}
+
+// Smallest and largest numbers of the contiguous range, whose sum is the answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weakness {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl fmt::Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.min + self.max)
+    }
+}
+
+impl Output for Weakness {
+    const KIND: &'static str = "integer";
+
+    fn extra(&self) -> Vec<(&'static str, Value)> {
+        vec![("min", Value::from(self.min)), ("max", Value::from(self.max))]
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    // Number of previous numbers that each number must be a sum of two of
+    pub preamble: usize,
 }
 
-pub fn main () {
-    let attack_number = find_attack_number(25);
-    println!("Number to attack: {}", &attack_number);
-    let (weakness_min, weakness_max) = find_encryption_weakness(attack_number);
-    println!("Encryption weakness: {} + {} = {}", weakness_min, weakness_max, weakness_min + weakness_max)
+impl Default for Params {
+    fn default() -> Self {
+        Params { preamble: 25 }
+    }
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = Vec<usize>;
+    type Params = Params;
+    type Output1 = usize;
+    type Output2 = Weakness;
+
+    fn parse(lines: Lines) -> Result<Vec<usize>> {
+        lines.map(|n| n.parse_at::<usize>(&n, "number"))
+            .collect()
+    }
+
+    fn part1(number_list: &Vec<usize>) -> Result<usize> {
+        Self::part1_with(number_list, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part2(number_list: &Vec<usize>) -> Result<Weakness> {
+        Self::part2_with(number_list, &Params::default(), &Progress::unlimited())
+    }
+
+    fn part1_with(number_list: &Vec<usize>, params: &Params, _progress: &Progress) -> Result<usize> {
+        find_attack_number(number_list, params.preamble)
+    }
+
+    fn part2_with(number_list: &Vec<usize>, params: &Params, _progress: &Progress) -> Result<Weakness> {
+        let attack_number = find_attack_number(number_list, params.preamble)?;
+        let (min, max) = find_encryption_weakness(number_list, attack_number)?;
+        Ok(Weakness { min, max })
+    }
 }
@@ -0,0 +1,83 @@
+use std::{
+    error,
+    fmt,
+    result,
+    time::Duration,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    // The input couldn't be read at all
+    Io {
+        source: String,
+        reason: String,
+    },
+    // The input doesn't follow the expected format; lines and columns start at 1
+    Parse {
+        source: String,
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    // The input was parsed, but no answer could be found for it
+    Solve(String),
+    // A request to the puzzle server failed
+    Http {
+        url: String,
+        reason: String,
+    },
+    // The parameters of a day are invalid, e.g. in the config file
+    Config {
+        source: String,
+        reason: String,
+    },
+    // The HTTP server couldn't start
+    Serve {
+        address: String,
+        reason: String,
+    },
+    // A part ran for longer than its timeout
+    Timeout(Duration),
+    // A part was stopped from another thread
+    Cancelled,
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    pub fn solve<S: Into<String>>(reason: S) -> Self {
+        Error::Solve(reason.into())
+    }
+}
+
+// Formatted like rustc diagnostics:
+//
+// error: invalid operation "foo"
+//   --> inputs/day8.txt:12:1
+//    |
+// 12 | foo +3
+//    | ^
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { source, reason } => write!(f, "error: couldn't read {}: {}", source, reason),
+            Error::Parse { source, line, column, text, reason } => {
+                let padding = " ".repeat(line.to_string().len());
+                writeln!(f, "error: {}", reason)?;
+                writeln!(f, "{}--> {}:{}:{}", padding, source, line, column)?;
+                writeln!(f, "{} |", padding)?;
+                writeln!(f, "{} | {}", line, text)?;
+                write!(f, "{} | {}^", padding, " ".repeat(column.saturating_sub(1)))
+            },
+            Error::Solve(reason) => write!(f, "error: {}", reason),
+            Error::Http { url, reason } => write!(f, "error: request to {} failed: {}", url, reason),
+            Error::Config { source, reason } => write!(f, "error: invalid parameters in {}: {}", source, reason),
+            Error::Serve { address, reason } => write!(f, "error: couldn't listen on {}: {}", address, reason),
+            Error::Timeout(timeout) => write!(f, "error: timed out after {:?}", timeout),
+            Error::Cancelled => write!(f, "error: cancelled"),
+        }
+    }
+}
+
+impl error::Error for Error {}
@@ -1,16 +1,227 @@
 use std::{
-    iter::Iterator,
+    iter::{self, Iterator},
     fs::File,
     io::{self, BufRead},
-    path::Path,
+    ops::Deref,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    vec,
 };
+use super::error::{Error, Result};
 
-// See "impl Trait" for more information on the return type:
-// https://doc.rust-lang.org/rust-by-example/trait/impl_trait.html
-pub fn read_file(filename: &str) -> impl Iterator<Item=String> {
-    let path = Path::new(filename);
-    let file = File::open(&path).expect("couldn't open file");
-    io::BufReader::new(file)
-        .lines()
-        .map(|l| l.expect("couldn't parse line"))
+// Where a puzzle input comes from; every solver parses from the same lines iterator regardless of the source
+#[derive(Debug, Clone)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Text { name: String, text: String },
+}
+
+impl Source {
+    // Default input for a day, i.e. "inputs/dayN.txt"
+    pub fn for_day(day: usize) -> Self {
+        Source::File(PathBuf::from(format!("inputs/day{}.txt", day)))
+    }
+
+    // "-" reads from stdin, like most command line tools
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        if path.as_ref() == Path::new("-") {
+            Source::Stdin
+        } else {
+            Source::File(path.as_ref().to_path_buf())
+        }
+    }
+
+    pub fn text(name: &str, text: &str) -> Self {
+        Source::Text { name: String::from(name), text: String::from(text) }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Source::File(path) => path.display().to_string(),
+            Source::Stdin => String::from("<stdin>"),
+            Source::Text { name, .. } => name.clone(),
+        }
+    }
+
+    // The whole input is read upfront, so that I/O errors are reported before parsing starts
+    pub fn lines(&self) -> Result<Lines> {
+        let io_error = |e: io::Error| Error::Io { source: self.name(), reason: e.to_string() };
+        let text_lines: Vec<String> = match self {
+            Source::File(path) => {
+                let file = File::open(path).map_err(io_error)?;
+                io::BufReader::new(file).lines().collect::<io::Result<_>>().map_err(io_error)?
+            },
+            Source::Stdin => io::stdin().lock().lines().collect::<io::Result<_>>().map_err(io_error)?,
+            Source::Text { text, .. } => text.lines().map(String::from).collect(),
+        };
+        Ok(Lines::new(&self.name(), text_lines))
+    }
+}
+
+// A single line of input, which knows where it came from in order to report errors
+#[derive(Debug, Clone)]
+pub struct Line {
+    source: Arc<str>,
+    number: usize,
+    text: String,
+}
+
+impl Line {
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_text(self) -> String {
+        self.text
+    }
+
+    // Columns start at 1
+    pub fn error<S: Into<String>>(&self, column: usize, reason: S) -> Error {
+        Error::Parse {
+            source: self.source.to_string(),
+            line: self.number,
+            column,
+            text: self.text.clone(),
+            reason: reason.into(),
+        }
+    }
+
+    // Error pointing to a slice of this line, e.g. one returned by split() or by a regex capture
+    pub fn error_at<S: Into<String>>(&self, part: &str, reason: S) -> Error {
+        self.error(self.column_of(part), reason)
+    }
+
+    // Parses a slice of this line, reporting the slice's column on failure
+    pub fn parse_at<T: FromStr>(&self, part: &str, what: &str) -> Result<T> {
+        part.parse::<T>().map_err(|_| self.error_at(part, format!("invalid {} {:?}", what, part)))
+    }
+
+    fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        }
+    }
+}
+
+impl Deref for Line {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Lines {
+    source: Arc<str>,
+    last_number: usize,
+    iter: vec::IntoIter<String>,
+}
+
+impl Lines {
+    fn new(source: &str, text_lines: Vec<String>) -> Self {
+        Lines {
+            source: Arc::from(source),
+            last_number: 0,
+            iter: text_lines.into_iter(),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    // Like next(), but running out of lines is an error
+    pub fn expect_line(&mut self, what: &str) -> Result<Line> {
+        match self.next() {
+            Some(line) => Ok(line),
+            None => Err(self.end_of_input(what)),
+        }
+    }
+
+    // For inputs that must be read entirely, e.g. a single map followed by an empty line
+    pub fn expect_end(&mut self) -> Result<()> {
+        match self.next() {
+            Some(line) => Err(line.error(1, "expected the end of input")),
+            None => Ok(()),
+        }
+    }
+
+    pub fn end_of_input(&self, what: &str) -> Error {
+        self.error_at_end(format!("unexpected end of input, expected {}", what))
+    }
+
+    // Error on the line after the last one read, e.g. the empty line that ended a record
+    pub fn error_at_end<S: Into<String>>(&self, reason: S) -> Error {
+        Error::Parse {
+            source: self.source.to_string(),
+            line: self.last_number + 1,
+            column: 1,
+            text: String::new(),
+            reason: reason.into(),
+        }
+    }
+
+    // Lines up to the next empty line, which is consumed, keeping their original line numbers.
+    // Extra empty lines between records are skipped; returns None at the end of input
+    pub fn next_record(&mut self) -> Option<Lines> {
+        while self.iter.as_slice().first().is_some_and(|text| text.is_empty()) {
+            self.next();
+        }
+        if self.iter.as_slice().is_empty() {
+            return None
+        }
+        let first_number = self.last_number;
+        let mut text_lines: Vec<String> = Vec::new();
+        for line in self.by_ref() {
+            if line.is_empty() {
+                break
+            }
+            text_lines.push(line.into_text());
+        }
+        Some(Lines {
+            source: Arc::clone(&self.source),
+            last_number: first_number,
+            iter: text_lines.into_iter(),
+        })
+    }
+
+    // Every remaining record, e.g. the passports of day 4
+    pub fn records(mut self) -> impl Iterator<Item=Lines> {
+        iter::from_fn(move || self.next_record())
+    }
+
+    // Record that starts with the given header, e.g. "your ticket:"; only the lines after the header are returned
+    pub fn section(&mut self, header: &str) -> Result<Lines> {
+        let mut record = self.next_record().ok_or_else(|| self.end_of_input(&format!("{:?}", header)))?;
+        let first_line = record.next().expect("records have at least one line");
+        if first_line.text() != header {
+            return Err(first_line.error(1, format!("expected {:?}, found {:?}", header, first_line.text())))
+        }
+        Ok(record)
+    }
+}
+
+impl Iterator for Lines {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Line> {
+        let text = self.iter.next()?;
+        self.last_number += 1;
+        Some(Line {
+            source: Arc::clone(&self.source),
+            number: self.last_number,
+            text,
+        })
+    }
 }
@@ -0,0 +1,988 @@
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+    mem,
+    ops::Range,
+    time::Duration,
+};
+use rand::{
+    seq::{index, SliceRandom},
+    Rng,
+    SeedableRng,
+};
+use rand_chacha::ChaCha8Rng;
+use toml::value::Table;
+use super::{
+    day11::{self, WaitingArea, WaitingAreaPixel},
+    day20,
+    day3,
+    day4::{OPTIONAL_FIELDS, REQUIRED_FIELDS},
+    day8::{self, ExitStatus, Instruction},
+    file::Source,
+    grid::Grid,
+    progress::Budget,
+    solver::{Day, Part, Solution},
+};
+
+// A random input of a day, with the answers for the default parameters when the way it was built makes them
+// known, e.g. the only pair of entries that sum to 2020 on day 1 is planted. The same seed always generates
+// the same input, so that a failure can be reproduced from the seed alone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub text: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Generated {
+    fn known<A: ToString, B: ToString>(text: String, part1: A, part2: B) -> Self {
+        Generated { text, part1: Some(part1.to_string()), part2: Some(part2.to_string()) }
+    }
+
+    fn unknown(text: String) -> Self {
+        Generated { text, part1: None, part2: None }
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    // Named after the seed, so that parse errors show how to generate the input again, e.g. "day9-seed42"
+    pub fn source(&self, day: usize, seed: u64) -> Source {
+        Source::text(&format!("day{}-seed{}", day, seed), &self.text)
+    }
+
+    // A message for each part of the solution that failed, or whose answer isn't the known one
+    pub fn mismatches(&self, solution: &Solution) -> Vec<String> {
+        solution.answers.iter().filter_map(|answer| match (&answer.result, self.answer(answer.part)) {
+            (Ok(result), Some(known)) if result != known => {
+                Some(format!("part {}: expected {}, found {}", answer.part.number(), known, result))
+            },
+            (Ok(_), _) => None,
+            (Err(e), _) => Some(format!("part {}: {}", answer.part.number(), e)),
+        }).collect()
+    }
+}
+
+type Random = ChaCha8Rng;
+
+type Generator = fn(&mut Random) -> Generated;
+
+static GENERATORS: &[(usize, Generator)] = &[
+    (1, day1),
+    (2, day2),
+    (3, day3),
+    (4, day4),
+    (5, day5),
+    (6, day6),
+    (7, day7),
+    (8, day8),
+    (9, day9),
+    (10, day10),
+    (11, day11),
+    (12, day12),
+    (13, day13),
+    (14, day14),
+    (15, day15),
+    (16, day16),
+    (17, day17),
+    (18, day18),
+    (19, day19),
+    (20, day20),
+];
+
+// Days scaffolded with `new-day` have no generator until one is added here
+pub fn has_generator(day: usize) -> bool {
+    GENERATORS.iter().any(|(d, _)| *d == day)
+}
+
+pub fn generate(day: usize, seed: u64) -> Option<Generated> {
+    let (_, generator) = GENERATORS.iter().find(|(d, _)| *d == day)?;
+    Some(generator(&mut Random::seed_from_u64(seed)))
+}
+
+// How many generated inputs passed, and the time spent on all of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checked {
+    pub day: usize,
+    pub count: u64,
+    pub failed: u64,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl Checked {
+    pub fn passed(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+impl fmt::Display for Checked {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f, "Day {}: {} of {} inputs passed; parse {:?}, solve {:?} in all",
+            self.day, self.count - self.failed, self.count, self.parse_time, self.solve_time,
+        )
+    }
+}
+
+// Solves the input of each seed with the default parameters. Parts whose answers aren't known only have to
+// succeed; `on_mismatch` is called with the seed of each failure, to generate the input again. None if the
+// day has no generator
+pub fn check<F: FnMut(u64, &str)>(day: usize, puzzle: &dyn Day, seeds: Range<u64>, budget: &Budget, mut on_mismatch: F) -> Option<Checked> {
+    let mut checked = Checked { day, count: 0, failed: 0, parse_time: Duration::ZERO, solve_time: Duration::ZERO };
+    for seed in seeds {
+        let generated = generate(day, seed)?;
+        let solution = generated.source(day, seed).lines()
+            .and_then(|lines| puzzle.solve_within(lines, &[Part::One, Part::Two], &Table::new(), budget));
+        let mismatches = match solution {
+            Ok(solution) => {
+                checked.parse_time += solution.parse_time;
+                checked.solve_time += solution.answers.iter().map(|answer| answer.time).sum::<Duration>();
+                generated.mismatches(&solution)
+            },
+            Err(e) => vec![e.to_string()],
+        };
+        checked.count += 1;
+        if !mismatches.is_empty() {
+            checked.failed += 1;
+        }
+        for mismatch in mismatches {
+            on_mismatch(seed, &mismatch);
+        }
+    }
+    Some(checked)
+}
+
+fn lines<T: ToString>(items: &[T]) -> String {
+    items.iter().map(|item| item.to_string() + "\n").collect()
+}
+
+fn letter(rng: &mut Random) -> char {
+    rng.gen_range('a'..='z')
+}
+
+fn map(rng: &mut Random, width: usize, height: usize, density: f64, cells: (char, char)) -> String {
+    let mut text = String::new();
+    for _ in 0..height {
+        text.extend((0..width).map(|_| if rng.gen_bool(density) { cells.0 } else { cells.1 }));
+        text.push('\n');
+    }
+    text
+}
+
+// Both the only pair and the only triple that sum to 2020 are planted. Every other entry is larger than half
+// of 2020, so the others can only sum to 2020 with planted entries, which is checked as they're added
+fn day1(rng: &mut Random) -> Generated {
+    const TARGET: i32 = 2020;
+    let sums = |entries: &[i32], entry: i32| -> bool {
+        entries.contains(&(TARGET - entry)) || entries.iter().any(|e| TARGET - entry - e != *e && entries.contains(&(TARGET - entry - e)))
+    };
+    loop {
+        let pair = rng.gen_range(1..TARGET / 2);
+        let (first, second) = (rng.gen_range(1..=1000), rng.gen_range(1..=1000));
+        let mut entries = vec![pair, TARGET - pair, first, second, TARGET - first - second];
+        let distinct: HashSet<i32> = entries.iter().copied().collect();
+        let pairs = (0..5).flat_map(|i| (i + 1..5).map(move |j| (i, j))).filter(|(i, j)| entries[*i] + entries[*j] == TARGET).count();
+        let triples = (0..5).flat_map(|i| (i + 1..5).flat_map(move |j| (j + 1..5).map(move |k| (i, j, k))))
+            .filter(|(i, j, k)| entries[*i] + entries[*j] + entries[*k] == TARGET)
+            .count();
+        if distinct.len() != 5 || pairs != 1 || triples != 1 {
+            continue
+        }
+        let count = rng.gen_range(20..=200);
+        while entries.len() < count {
+            let entry = rng.gen_range(TARGET / 2 + 1..TARGET);
+            if !entries.contains(&entry) && !sums(&entries, entry) {
+                entries.push(entry);
+            }
+        }
+        entries.shuffle(rng);
+        return Generated::known(lines(&entries), pair * (TARGET - pair), first * second * (TARGET - first - second))
+    }
+}
+
+// Whether each password follows each policy is decided first, then the password is built to match
+fn day2(rng: &mut Random) -> Generated {
+    let count = rng.gen_range(100..=1000);
+    let (mut text, mut valid_counts, mut valid_positions) = (String::new(), 0, 0);
+    let mut generated = 0;
+    while generated < count {
+        let policy_char = letter(rng);
+        let first = rng.gen_range(1..=10);
+        let second = rng.gen_range(first + 1..=first + 10);
+        let length = rng.gen_range(second..=second + 5);
+        let (counts_valid, positions_valid) = (rng.gen_bool(0.5), rng.gen_bool(0.5));
+        // Exactly one of the positions has the letter if the second policy holds, otherwise both or none do
+        let at_positions = match positions_valid {
+            true => if rng.gen_bool(0.5) { [true, false] } else { [false, true] },
+            false => [rng.gen_bool(0.5); 2],
+        };
+        let fixed = at_positions.iter().filter(|at| **at).count();
+        let counts: Vec<usize> = (fixed..=fixed + length - 2).filter(|c| (first..=second).contains(c) == counts_valid).collect();
+        let total = match counts.choose(rng) {
+            Some(total) => *total,
+            None => continue,
+        };
+        let other = |rng: &mut Random| loop {
+            let c = letter(rng);
+            if c != policy_char {
+                break c
+            }
+        };
+        let mut password: Vec<char> = (0..length).map(|_| other(rng)).collect();
+        for (position, at) in [first, second].iter().zip(at_positions.iter()) {
+            if *at {
+                password[position - 1] = policy_char;
+            }
+        }
+        let free: Vec<usize> = (0..length).filter(|i| *i != first - 1 && *i != second - 1).collect();
+        for i in index::sample(rng, free.len(), total - fixed) {
+            password[free[i]] = policy_char;
+        }
+        writeln!(text, "{}-{} {}: {}", first, second, policy_char, password.iter().collect::<String>()).expect("writing to a string can't fail");
+        valid_counts += counts_valid as usize;
+        valid_positions += positions_valid as usize;
+        generated += 1;
+    }
+    Generated::known(text, valid_counts, valid_positions)
+}
+
+// Each slope walks down the map first, placing a tree or not on the squares it lands on that weren't placed yet,
+// and counting the trees it lands on; the squares no slope lands on are filled in afterwards
+fn day3(rng: &mut Random) -> Generated {
+    let (width, height) = (rng.gen_range(11..=40), rng.gen_range(100..=400));
+    let mut squares: Vec<Vec<Option<bool>>> = vec![vec![None; width]; height];
+    let trees: Vec<usize> = day3::SLOPE_LIST.iter().map(|(right, down)| {
+        (1..).map(|step| (step * right % width, step * down)).take_while(|(_, y)| *y < height)
+            .filter(|(x, y)| *squares[*y][*x].get_or_insert_with(|| rng.gen_bool(0.25)))
+            .count()
+    }).collect();
+    let text: String = squares.iter().map(|row| {
+        row.iter().map(|square| if square.unwrap_or_else(|| rng.gen_bool(0.25)) { '#' } else { '.' }).collect::<String>() + "\n"
+    }).collect();
+    Generated::known(text, trees[1], trees.iter().product::<usize>())
+}
+
+// Each passport is either valid, missing a required field, or has a single invalid value
+fn day4(rng: &mut Random) -> Generated {
+    const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+    let hex = |rng: &mut Random, count: usize| -> String {
+        (0..count).map(|_| std::char::from_digit(rng.gen_range(0..16), 16).expect("digits are below 16")).collect()
+    };
+    let year = |rng: &mut Random, valid: bool, min: usize, max: usize| -> String {
+        match valid {
+            true => rng.gen_range(min..=max),
+            false if rng.gen_bool(0.5) => rng.gen_range(min - 20..min),
+            false => rng.gen_range(max + 1..=max + 20),
+        }.to_string()
+    };
+    let value = |rng: &mut Random, field: &str, valid: bool| -> String {
+        match field {
+            "byr" => year(rng, valid, 1920, 2002),
+            "iyr" => year(rng, valid, 2010, 2020),
+            "eyr" => year(rng, valid, 2020, 2030),
+            "hgt" => match (valid, rng.gen_range(0..3)) {
+                (true, 0) => format!("{}cm", rng.gen_range(150..=193)),
+                (true, _) => format!("{}in", rng.gen_range(59..=76)),
+                (false, 0) => format!("{}cm", if rng.gen_bool(0.5) { rng.gen_range(100..150) } else { rng.gen_range(194..=250) }),
+                (false, 1) => format!("{}in", if rng.gen_bool(0.5) { rng.gen_range(40..59) } else { rng.gen_range(77..=99) }),
+                (false, _) => rng.gen_range(59..=193).to_string(),
+            },
+            "hcl" => match (valid, rng.gen_range(0..3)) {
+                (true, _) => format!("#{}", hex(rng, 6)),
+                (false, 0) => hex(rng, 6),
+                (false, 1) => format!("#{}", hex(rng, 5)),
+                (false, _) => format!("#{}{}", hex(rng, 5), rng.gen_range('g'..='z')),
+            },
+            "ecl" => match valid {
+                true => String::from(*EYE_COLORS.choose(rng).expect("there are eye colors")),
+                false => loop {
+                    let color: String = (0..3).map(|_| letter(rng)).collect();
+                    if !EYE_COLORS.contains(&color.as_str()) {
+                        break color
+                    }
+                },
+            },
+            "pid" => match valid {
+                true => format!("{:09}", rng.gen_range(0..1_000_000_000)),
+                false if rng.gen_bool(0.5) => format!("{:08}", rng.gen_range(0..100_000_000)),
+                false => format!("{:010}", rng.gen_range(0..10_000_000_000u64)),
+            },
+            _ => rng.gen_range(100..=350).to_string(),
+        }
+    };
+    let count = rng.gen_range(100..=300);
+    let (mut passports, mut complete, mut valid) = (Vec::new(), 0, 0);
+    for _ in 0..count {
+        // Valid, missing a field, or with an invalid value
+        let kind = rng.gen_range(0..3);
+        let mut fields = REQUIRED_FIELDS.to_vec();
+        if rng.gen_bool(0.5) {
+            fields.extend(OPTIONAL_FIELDS.iter());
+        }
+        if kind == 1 {
+            fields.remove(rng.gen_range(0..REQUIRED_FIELDS.len()));
+        }
+        let invalid = match kind {
+            2 => Some(REQUIRED_FIELDS[rng.gen_range(0..REQUIRED_FIELDS.len())]),
+            _ => None,
+        };
+        fields.shuffle(rng);
+        let mut passport = String::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.gen_bool(0.3) { '\n' } else { ' ' });
+            }
+            let value = value(rng, field, invalid != Some(*field));
+            write!(passport, "{}:{}", field, value).expect("writing to a string can't fail");
+        }
+        passports.push(passport);
+        complete += (kind != 1) as usize;
+        valid += (kind == 0) as usize;
+    }
+    Generated::known(passports.join("\n\n") + "\n", complete, valid)
+}
+
+// Every seat from the first to the last one is taken, but one
+fn day5(rng: &mut Random) -> Generated {
+    let first = rng.gen_range(8..=200);
+    let last = rng.gen_range(first + 100..=1015);
+    let missing = rng.gen_range(first + 1..last);
+    let mut seats: Vec<String> = (first..=last).filter(|id| *id != missing).map(|id| {
+        let row = (0..7).rev().map(|bit| if (id >> 3) & (1 << bit) == 0 { 'F' } else { 'B' });
+        let column = (0..3).rev().map(|bit| if id & (1 << bit) == 0 { 'L' } else { 'R' });
+        row.chain(column).collect()
+    }).collect();
+    seats.shuffle(rng);
+    Generated::known(lines(&seats), last, missing)
+}
+
+// The questions that anyone and that everyone in a group answered are picked first; the others are each answered
+// by some people of the group, but not all of them
+fn day6(rng: &mut Random) -> Generated {
+    let count = rng.gen_range(100..=500);
+    let (mut groups, mut anyone, mut everyone) = (Vec::new(), 0, 0);
+    while groups.len() < count {
+        let people = rng.gen_range(1..=5);
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        letters.shuffle(rng);
+        let union = rng.gen_range(1..=26);
+        let common = if people == 1 { union } else { rng.gen_range(0..=union) };
+        let mut answers: Vec<Vec<char>> = vec![letters[..common].to_vec(); people];
+        for letter in letters[common..union].iter() {
+            let answered = loop {
+                let answered: Vec<bool> = (0..people).map(|_| rng.gen_bool(0.5)).collect();
+                if answered.contains(&true) && answered.contains(&false) {
+                    break answered
+                }
+            };
+            answers.iter_mut().zip(answered).filter(|(_, answered)| *answered).for_each(|(a, _)| a.push(*letter));
+        }
+        // Empty lines would split the group
+        if answers.iter().any(|a| a.is_empty()) {
+            continue
+        }
+        let group: Vec<String> = answers.iter_mut().map(|a| {
+            a.shuffle(rng);
+            a.iter().collect()
+        }).collect();
+        groups.push(group.join("\n"));
+        anyone += union;
+        everyone += common;
+    }
+    Generated::known(groups.join("\n\n") + "\n", anyone, everyone)
+}
+
+// Bags only contain bags of deeper levels, so that there are no cycles and the counts stay small
+fn day7(rng: &mut Random) -> Generated {
+    const ADJECTIVES: [&str; 18] = [
+        "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light",
+        "mirrored", "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+    ];
+    const COLORS: [&str; 24] = [
+        "aqua", "beige", "black", "blue", "bronze", "brown", "chartreuse", "coral", "crimson", "cyan", "fuchsia", "gold",
+        "gray", "green", "indigo", "lavender", "lime", "magenta", "maroon", "olive", "orange", "plum", "purple", "red",
+    ];
+    let mut colors: Vec<String> = ADJECTIVES.iter().flat_map(|a| COLORS.iter().map(move |c| format!("{} {}", a, c)))
+        .filter(|color| color != "shiny gold")
+        .collect();
+    colors.shuffle(rng);
+    colors.truncate(rng.gen_range(50..=400));
+    colors.push(String::from("shiny gold"));
+    let gold = colors.len() - 1;
+    let levels: Vec<usize> = (0..colors.len()).map(|i| if i == gold { rng.gen_range(2..=3) } else { rng.gen_range(0..=5) }).collect();
+    // The (count, color) pairs inside each color
+    let contents: Vec<Vec<(usize, usize)>> = levels.iter().map(|level| {
+        let deeper: Vec<usize> = (0..colors.len()).filter(|i| levels[*i] > *level).collect();
+        let count = rng.gen_range(0..=4).min(deeper.len());
+        deeper.choose_multiple(rng, count).map(|inner| (rng.gen_range(1..=5), *inner)).collect()
+    }).collect();
+    // The deepest colors are counted first, since the others only contain deeper ones
+    let mut by_depth: Vec<usize> = (0..colors.len()).collect();
+    by_depth.sort_by_key(|i| cmp::Reverse(levels[*i]));
+    let (mut holds_gold, mut bags_inside) = (vec![false; colors.len()], vec![0; colors.len()]);
+    for i in by_depth {
+        holds_gold[i] = contents[i].iter().any(|(_, inner)| *inner == gold || holds_gold[*inner]);
+        bags_inside[i] = contents[i].iter().map(|(count, inner)| count * (1 + bags_inside[*inner])).sum();
+    }
+    let mut rules: Vec<String> = colors.iter().zip(contents.iter()).map(|(color, contents)| {
+        let contents: Vec<String> = contents.iter().map(|(count, inner)| match count {
+            1 => format!("1 {} bag", colors[*inner]),
+            n => format!("{} {} bags", n, colors[*inner]),
+        }).collect();
+        match contents.is_empty() {
+            true => format!("{} bags contain no other bags.", color),
+            false => format!("{} bags contain {}.", color, contents.join(", ")),
+        }
+    }).collect();
+    rules.shuffle(rng);
+    Generated::known(lines(&rules), holds_gold.iter().filter(|holds| **holds).count(), bags_inside[gold])
+}
+
+// The program is made of blocks that end with a jump. The path that terminates runs through some of the blocks,
+// in any order, and the others jump back to their own start. Then an instruction on the path gets corrupted: a nop
+// into a jump back along the path, or the jump at the end of a block into a nop that falls into a block off the path
+fn day8(rng: &mut Random) -> Generated {
+    loop {
+        let lengths: Vec<usize> = (0..rng.gen_range(10..=80)).map(|_| rng.gen_range(1..=6)).collect();
+        let starts: Vec<usize> = lengths.iter().scan(0, |start, length| {
+            *start += length;
+            Some(*start - length)
+        }).collect();
+        let size: usize = lengths.iter().sum();
+        let mut path: Vec<usize> = (1..lengths.len()).collect();
+        path.shuffle(rng);
+        path.truncate(rng.gen_range(1..lengths.len()));
+        path.insert(0, 0);
+        let mut code: Vec<Instruction> = Vec::with_capacity(size);
+        for (block, length) in lengths.iter().enumerate() {
+            for i in 0..length - 1 {
+                let position = (starts[block] + i) as isize;
+                code.push(match rng.gen_bool(0.6) {
+                    true => Instruction::Accumulator(rng.gen_range(1..=50) * if rng.gen_bool(0.5) { 1 } else { -1 }),
+                    false => Instruction::NoOp(rng.gen_range(-position..size as isize - position)),
+                });
+            }
+            let end = starts[block] + length - 1;
+            let target = match path.iter().position(|b| *b == block) {
+                Some(i) if i + 1 < path.len() => starts[path[i + 1]],
+                Some(_) => size,
+                None => starts[block],
+            };
+            code.push(Instruction::Jump(target as isize - end as isize));
+        }
+        let executed: Vec<usize> = path.iter().flat_map(|block| starts[*block]..starts[*block] + lengths[*block]).collect();
+        let acc = |code: &[Instruction], positions: &[usize]| -> isize {
+            positions.iter().map(|p| match code[*p] { Instruction::Accumulator(inc) => inc, _ => 0 }).sum()
+        };
+        let fixed_acc = acc(&code, &executed);
+
+        let step = rng.gen_range(0..executed.len());
+        let corrupted = executed[step];
+        let acc_before_loop = match code[corrupted] {
+            Instruction::NoOp(_) => {
+                let back = executed[rng.gen_range(0..=step)];
+                code[corrupted] = Instruction::Jump(back as isize - corrupted as isize);
+                acc(&code, &executed[..step])
+            },
+            Instruction::Jump(inc) => {
+                // The block right after must be off the path, so that it loops
+                let next = match starts.iter().position(|s| *s == corrupted + 1) {
+                    Some(next) if !path.contains(&next) => next,
+                    _ => continue,
+                };
+                code[corrupted] = Instruction::NoOp(inc);
+                acc(&code, &executed[..step]) + acc(&code, &(starts[next]..starts[next] + lengths[next]).collect::<Vec<usize>>())
+            },
+            Instruction::Accumulator(_) => continue,
+        };
+        // Other instructions must not fix the program as well
+        let fixes = (0..size).filter(|i| {
+            let mut code = code.clone();
+            code[*i] = match code[*i] {
+                Instruction::Jump(inc) => Instruction::NoOp(inc),
+                Instruction::NoOp(inc) => Instruction::Jump(inc),
+                Instruction::Accumulator(_) => return false,
+            };
+            matches!(day8::run_code(&code), ExitStatus::Terminated(_))
+        }).count();
+        if fixes != 1 {
+            continue
+        }
+        let text = code.iter().map(|instruction| match instruction {
+            Instruction::Accumulator(inc) => format!("acc {:+}\n", inc),
+            Instruction::Jump(inc) => format!("jmp {:+}\n", inc),
+            Instruction::NoOp(inc) => format!("nop {:+}\n", inc),
+        }).collect();
+        return Generated::known(text, acc_before_loop, fixed_acc)
+    }
+}
+
+// Every number after the preamble is the sum of two of the 25 numbers before it, until the planted invalid number,
+// which is the sum of a contiguous range of the numbers before it, and of no other range
+fn day9(rng: &mut Random) -> Generated {
+    const PREAMBLE: usize = 25;
+    let valid_number = |rng: &mut Random, numbers: &[usize]| -> usize {
+        let window = &numbers[numbers.len() - PREAMBLE..];
+        loop {
+            let (i, j) = (rng.gen_range(0..PREAMBLE), rng.gen_range(0..PREAMBLE));
+            if window[i] != window[j] {
+                break window[i] + window[j]
+            }
+        }
+    };
+    loop {
+        let mut numbers: Vec<usize> = index::sample(rng, 100, PREAMBLE).iter().map(|n| n + 1).collect();
+        let length = rng.gen_range(100..=600);
+        let invalid_at = rng.gen_range(PREAMBLE + 10..length);
+        while numbers.len() < invalid_at {
+            let number = valid_number(rng, &numbers);
+            numbers.push(number);
+        }
+        let window = &numbers[invalid_at - PREAMBLE..];
+        let planted = (0..100).find_map(|_| {
+            let start = rng.gen_range(0..invalid_at - 2);
+            let end = rng.gen_range(start + 2..=(start + 17).min(invalid_at));
+            let invalid: usize = numbers[start..end].iter().sum();
+            let is_sum = window.iter().enumerate().any(|(i, a)| window[i + 1..].iter().any(|b| a + b == invalid));
+            let ranges = (0..invalid_at).filter(|i| {
+                let mut sum = 0;
+                for (length, n) in numbers[*i..invalid_at].iter().enumerate() {
+                    sum += n;
+                    if sum >= invalid {
+                        return sum == invalid && length > 0
+                    }
+                }
+                false
+            }).count();
+            match !is_sum && ranges == 1 && !numbers.contains(&invalid) {
+                true => Some((invalid, start, end)),
+                false => None,
+            }
+        });
+        let (invalid, start, end) = match planted {
+            Some(planted) => planted,
+            None => continue,
+        };
+        numbers.push(invalid);
+        while numbers.len() < length {
+            let number = valid_number(rng, &numbers);
+            numbers.push(number);
+        }
+        let range = &numbers[start..end];
+        let weakness = range.iter().min().expect("ranges have two numbers") + range.iter().max().expect("ranges have two numbers");
+        return Generated::known(lines(&numbers), invalid, weakness)
+    }
+}
+
+// The chain goes up in runs of differences of 1 jolt, separated by differences of 3. A run of n differences of 1
+// can be plugged in as many ways as the n-th tribonacci number, since no more than two adapters in a row can be left out
+fn day10(rng: &mut Random) -> Generated {
+    const ARRANGEMENTS: [usize; 5] = [1, 1, 2, 4, 7];
+    let (mut adapters, mut joltage) = (Vec::new(), 0);
+    let (mut ones, mut threes, mut arrangements) = (0, 0, 1usize);
+    for run in 0..rng.gen_range(10..=40) {
+        if arrangements.checked_mul(ARRANGEMENTS[4]).is_none() {
+            break
+        }
+        if run > 0 {
+            joltage += 3;
+            adapters.push(joltage);
+            threes += 1;
+        }
+        let length = rng.gen_range(0..=4);
+        for _ in 0..length {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+        ones += length;
+        arrangements *= ARRANGEMENTS[length];
+    }
+    // The device is 3 jolts above the last adapter
+    threes += 1;
+    adapters.shuffle(rng);
+    Generated::known(lines(&adapters), ones * threes, arrangements)
+}
+
+// Some layouts flip between two states forever, and have no answer, so layouts are drawn again until the seats
+// settle with both rules. The answers are unknown: only running the rules until the seats settle gives them, which
+// is what the solver does
+fn day11(rng: &mut Random) -> Generated {
+    loop {
+        let (width, height) = (rng.gen_range(10..=95), rng.gen_range(10..=95));
+        let area = Grid::from_fn(width, height, |_, _| match rng.gen_bool(0.8) {
+            true => WaitingAreaPixel::EmptySeat,
+            false => WaitingAreaPixel::Floor,
+        });
+        if day11_settles(&area, day11::count_occupied_adjacent, 4) && day11_settles(&area, day11::count_occupied_visible, 5) {
+            return Generated::unknown(area.to_string() + "\n")
+        }
+    }
+}
+
+fn day11_settles(area: &WaitingArea, count_occupied: fn((usize, usize), &WaitingArea) -> usize, tolerance: usize) -> bool {
+    let (mut previous, mut current) = (None, area.clone());
+    for _ in 0..1000 {
+        let next = day11::iterate_waiting_area(&current, count_occupied, tolerance);
+        if next == current {
+            return true
+        }
+        if previous.as_ref() == Some(&next) {
+            return false
+        }
+        previous = Some(mem::replace(&mut current, next));
+    }
+    false
+}
+
+// Both ships follow each instruction as it's drawn: the first one moves itself or turns, and the second one moves
+// its waypoint, east and north of the ship
+fn day12(rng: &mut Random) -> Generated {
+    let turn_left = |(east, north): (isize, isize), degrees: isize| (0..degrees / 90).fold((east, north), |(e, n), _| (-n, e));
+    let (mut ship, mut heading) = ((0isize, 0isize), (1isize, 0isize));
+    let (mut ship_by_waypoint, mut waypoint) = ((0isize, 0isize), (10isize, 1isize));
+    let mut instructions = Vec::new();
+    for _ in 0..rng.gen_range(500..=800) {
+        match rng.gen_range(0..7) {
+            0..=3 => {
+                let (action, (east, north)) = [('N', (0, 1)), ('S', (0, -1)), ('E', (1, 0)), ('W', (-1, 0))][rng.gen_range(0..4)];
+                let value = rng.gen_range(1..=5);
+                ship = (ship.0 + east * value, ship.1 + north * value);
+                waypoint = (waypoint.0 + east * value, waypoint.1 + north * value);
+                instructions.push(format!("{}{}", action, value));
+            },
+            4 => {
+                let value = rng.gen_range(1..=100);
+                ship = (ship.0 + heading.0 * value, ship.1 + heading.1 * value);
+                ship_by_waypoint = (ship_by_waypoint.0 + waypoint.0 * value, ship_by_waypoint.1 + waypoint.1 * value);
+                instructions.push(format!("F{}", value));
+            },
+            _ => {
+                let degrees = [90, 180, 270][rng.gen_range(0..3)];
+                let (action, left) = if rng.gen_bool(0.5) { ('L', degrees) } else { ('R', 360 - degrees) };
+                heading = turn_left(heading, left);
+                waypoint = turn_left(waypoint, left);
+                instructions.push(format!("{}{}", action, degrees));
+            },
+        }
+    }
+    let distance = |(east, north): (isize, isize)| east.abs() + north.abs();
+    Generated::known(lines(&instructions), distance(ship), distance(ship_by_waypoint))
+}
+
+// The timestamp of part 2 is picked first, above the default start; then each bus is a prime placed at the offset
+// where it departs at that timestamp. Primes are added until their product is larger than the timestamp, so that
+// it's the earliest one
+fn day13(rng: &mut Random) -> Generated {
+    let mut primes: Vec<usize> = (11..1000).filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0)).collect();
+    loop {
+        let timestamp = rng.gen_range(100_000_000_000_000..1_000_000_000_000_000);
+        let mut buses: Vec<Option<usize>> = vec![None; 100];
+        let mut product = 1usize;
+        primes.shuffle(rng);
+        for prime in primes.iter() {
+            let offset = (prime - timestamp % prime) % prime;
+            if offset < buses.len() && buses[offset].is_none() {
+                buses[offset] = Some(*prime);
+                product *= prime;
+                if product > timestamp {
+                    break
+                }
+            }
+        }
+        if product <= timestamp {
+            continue
+        }
+        while buses.last() == Some(&None) {
+            buses.pop();
+        }
+        // The earliest bus must be the only one to leave first
+        let earliest = rng.gen_range(1_000_000..=1_010_000);
+        let mut waits: Vec<(usize, usize)> = buses.iter().flatten().map(|id| ((id - earliest % id) % id, *id)).collect();
+        waits.sort_unstable();
+        if waits[0].0 == waits[1].0 {
+            continue
+        }
+        let ids: Vec<String> = buses.iter().map(|id| id.map_or_else(|| String::from("x"), |id| id.to_string())).collect();
+        return Generated::known(format!("{}\n{}\n", earliest, ids.join(",")), waits[0].0 * waits[0].1, timestamp)
+    }
+}
+
+// Masks have at most 9 floating bits, so that part 2 writes to at most 512 addresses at a time. Both memories
+// are written as the program is built
+fn day14(rng: &mut Random) -> Generated {
+    let (mut program, mut values, mut addresses) = (Vec::new(), HashMap::new(), HashMap::new());
+    for _ in 0..rng.gen_range(50..=100) {
+        let count = rng.gen_range(0..=9);
+        let floating = index::sample(rng, 36, count).into_vec();
+        let mask: String = (0..36).map(|i| match (floating.contains(&i), rng.gen_bool(0.5)) {
+            (true, _) => 'X',
+            (false, true) => '1',
+            (false, false) => '0',
+        }).collect();
+        program.push(format!("mask = {}", mask));
+        let bits = |bit: char| mask.chars().fold(0u64, |acc, c| (acc << 1) | (c == bit) as u64);
+        let (ones, zeros) = (bits('1'), bits('0'));
+        for _ in 0..rng.gen_range(1..=6) {
+            let (address, value) = (rng.gen_range(0..65536u64), rng.gen_range(0..1u64 << 30));
+            program.push(format!("mem[{}] = {}", address, value));
+            values.insert(address, value & !zeros | ones);
+            for combination in 0..1u64 << floating.len() {
+                let floating_bits = floating.iter().enumerate().map(|(i, bit)| ((combination >> i) & 1) << (35 - bit)).sum::<u64>();
+                let floating_mask = floating.iter().map(|bit| 1u64 << (35 - bit)).sum::<u64>();
+                addresses.insert((address | ones) & !floating_mask | floating_bits, value);
+            }
+        }
+    }
+    Generated::known(lines(&program), values.values().sum::<u64>(), addresses.values().sum::<u64>())
+}
+
+// The answers are unknown, since the game has no shortcut: the only way to know the number spoken on a turn is to
+// play every turn before it, which is what the solver does
+fn day15(rng: &mut Random) -> Generated {
+    let count = rng.gen_range(3..=7);
+    let numbers: Vec<String> = index::sample(rng, 20, count).iter().map(|n| n.to_string()).collect();
+    Generated::unknown(numbers.join(",") + "\n")
+}
+
+// The ranges of the fields don't overlap, so each column only fits one field, and the invalid values are outside of
+// every range
+fn day16(rng: &mut Random) -> Generated {
+    const FIELDS: [&str; 20] = [
+        "departure location", "departure station", "departure platform", "departure track", "departure date",
+        "departure time", "arrival location", "arrival station", "arrival platform", "arrival track", "class",
+        "duration", "price", "route", "row", "seat", "train", "type", "wagon", "zone",
+    ];
+    let mut bounds = index::sample(rng, 950, 80).into_vec();
+    bounds.sort_unstable();
+    let mut ranges: Vec<(usize, usize)> = bounds.chunks(2).map(|pair| (pair[0] + 25, pair[1] + 25)).collect();
+    ranges.shuffle(rng);
+    let fields: Vec<[(usize, usize); 2]> = ranges.chunks(2).map(|pair| {
+        let mut field = [pair[0], pair[1]];
+        field.sort_unstable();
+        field
+    }).collect();
+    let mut columns: Vec<usize> = (0..FIELDS.len()).collect();
+    columns.shuffle(rng);
+    let ticket = |rng: &mut Random, fields: &[[(usize, usize); 2]]| -> Vec<usize> {
+        columns.iter().map(|field| {
+            let (min, max) = fields[*field][rng.gen_range(0..2)];
+            rng.gen_range(min..=max)
+        }).collect()
+    };
+    let own_ticket = ticket(rng, &fields);
+    let departures: usize = columns.iter().zip(own_ticket.iter()).filter(|(field, _)| **field < 6).map(|(_, value)| value).product();
+    let mut error_rate = 0;
+    let nearby_tickets: Vec<Vec<usize>> = (0..rng.gen_range(200..=250)).map(|_| {
+        let mut values = ticket(rng, &fields);
+        if rng.gen_bool(0.25) {
+            let invalid = if rng.gen_bool(0.5) { rng.gen_range(1..25) } else { rng.gen_range(976..1000) };
+            let column = rng.gen_range(0..values.len());
+            values[column] = invalid;
+            error_rate += invalid;
+        }
+        values
+    }).collect();
+    let mut text = String::new();
+    let mut order: Vec<usize> = (0..FIELDS.len()).collect();
+    order.shuffle(rng);
+    for i in order {
+        let [(min1, max1), (min2, max2)] = fields[i];
+        writeln!(text, "{}: {}-{} or {}-{}", FIELDS[i], min1, max1, min2, max2).expect("writing to a string can't fail");
+    }
+    let join = |ticket: &[usize]| ticket.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",");
+    write!(text, "\nyour ticket:\n{}\n\nnearby tickets:\n", join(&own_ticket)).expect("writing to a string can't fail");
+    text.extend(nearby_tickets.iter().map(|ticket| join(ticket) + "\n"));
+    Generated::known(text, error_rate, departures)
+}
+
+// Like day 11, the answers are unknown, since only running the six cycles gives the active cubes
+fn day17(rng: &mut Random) -> Generated {
+    let size = rng.gen_range(3..=8);
+    Generated::unknown(map(rng, size, size, 0.5, ('#', '.')))
+}
+
+// Expressions are evaluated both ways as they're built; ones whose values get too large are built again
+fn day18(rng: &mut Random) -> Generated {
+    const LIMIT: usize = 1_000_000_000_000;
+    let (mut expressions, mut left_to_right, mut addition_first) = (Vec::new(), 0, 0);
+    while expressions.len() < 300 {
+        if let Some((text, values)) = day18_expression(rng, 0).filter(|(_, (a, b))| *a <= LIMIT && *b <= LIMIT) {
+            expressions.push(text);
+            left_to_right += values.0;
+            addition_first += values.1;
+        }
+    }
+    Generated::known(lines(&expressions), left_to_right, addition_first)
+}
+
+// An expression, with its value when evaluated from left to right and with additions first; None on overflow
+fn day18_expression(rng: &mut Random, depth: usize) -> Option<(String, (usize, usize))> {
+    let mut text = String::new();
+    let (mut left_to_right, mut sum, mut product) = (0usize, 0usize, 1usize);
+    for i in 0..rng.gen_range(2..=6) {
+        let multiply = i > 0 && rng.gen_bool(0.5);
+        if i > 0 {
+            text += if multiply { " * " } else { " + " };
+        }
+        let (operand, values) = match depth < 2 && rng.gen_bool(0.3) {
+            true => {
+                let (inner, values) = day18_expression(rng, depth + 1)?;
+                (format!("({})", inner), values)
+            },
+            false => {
+                let digit = rng.gen_range(1..=9);
+                (digit.to_string(), (digit, digit))
+            },
+        };
+        text += &operand;
+        match (i, multiply) {
+            (0, _) => {
+                left_to_right = values.0;
+                sum = values.1;
+            },
+            (_, true) => {
+                left_to_right = left_to_right.checked_mul(values.0)?;
+                product = product.checked_mul(sum)?;
+                sum = values.1;
+            },
+            (_, false) => {
+                left_to_right = left_to_right.checked_add(values.0)?;
+                sum = sum.checked_add(values.1)?;
+            },
+        }
+    }
+    Some((text, (left_to_right, product.checked_mul(sum)?)))
+}
+
+// Rules 42 and 31 match three letter words starting with "a" and "b" respectively, so each message splits into
+// a single sequence of words, which decides whether it matches
+fn day19(rng: &mut Random) -> Generated {
+    let mut numbers = (1..=130).filter(|n| ![8, 11, 31, 42].contains(n)).collect::<Vec<usize>>();
+    numbers.shuffle(rng);
+    let (a, b, t, u) = (numbers[0], numbers[1], numbers[2], numbers[3]);
+    let mut rules = vec![
+        String::from("0: 8 11"),
+        String::from("8: 42"),
+        String::from("11: 42 31"),
+        format!("42: {} {} | {} {}", a, t, a, u),
+        format!("31: {} {} | {} {}", b, t, b, u),
+        format!("{}: {} {} | {} {}", t, a, b, b, a),
+        format!("{}: {} {} | {} {}", u, a, a, b, b),
+        format!("{}: \"a\"", a),
+        format!("{}: \"b\"", b),
+    ];
+    rules.shuffle(rng);
+    let (mut messages, mut part1, mut part2) = (Vec::new(), 0, 0);
+    for _ in 0..rng.gen_range(300..=500) {
+        // Whether each word matches rule 42
+        let words: Vec<bool> = match rng.gen_range(0..10) {
+            0..=1 => vec![true, true, false],
+            2..=6 => {
+                let (first, second) = (rng.gen_range(1..=6), rng.gen_range(1..=6));
+                (0..first + second).map(|i| i < first).collect()
+            },
+            _ => (0..rng.gen_range(1..=10)).map(|_| rng.gen_bool(0.5)).collect(),
+        };
+        let mut message: String = words.iter().flat_map(|first| vec![if *first { 'a' } else { 'b' }, letter_ab(rng), letter_ab(rng)]).collect();
+        let split = rng.gen_bool(0.1);
+        if split {
+            message.extend((0..rng.gen_range(1..=2)).map(|_| letter_ab(rng)));
+        }
+        let (first, second) = (words.iter().take_while(|w| **w).count(), words.iter().skip_while(|w| **w).filter(|w| !**w).count());
+        let matched = !split && first + second == words.len();
+        part1 += (matched && first == 2 && second == 1) as usize;
+        part2 += (matched && second >= 1 && first > second) as usize;
+        messages.push(message);
+    }
+    Generated::known(rules.join("\n") + "\n\n" + &lines(&messages), part1, part2)
+}
+
+fn letter_ab(rng: &mut Random) -> char {
+    if rng.gen_bool(0.5) { 'a' } else { 'b' }
+}
+
+// The image is cut into tiles from a known image with planted sea monsters. The seams between tiles are picked
+// so that no two borders match unless they're neighbors, and none reads the same both ways
+fn day20(rng: &mut Random) -> Generated {
+    let size = rng.gen_range(3..=12);
+    loop {
+        let corners: Vec<Vec<bool>> = (0..=size).map(|_| (0..=size).map(|_| rng.gen_bool(0.5)).collect()).collect();
+        let mut keys = HashSet::new();
+        let mut seam = |rng: &mut Random, start: bool, end: bool| -> Option<Vec<bool>> {
+            (0..1000).find_map(|_| {
+                let bits: Vec<bool> = std::iter::once(start).chain((0..8).map(|_| rng.gen_bool(0.5))).chain(std::iter::once(end)).collect();
+                let hash = bits.iter().fold(0usize, |acc, bit| (acc << 1) | *bit as usize);
+                let flipped = bits.iter().rev().fold(0usize, |acc, bit| (acc << 1) | *bit as usize);
+                match hash != flipped && keys.insert(hash.min(flipped)) {
+                    true => Some(bits),
+                    false => None,
+                }
+            })
+        };
+        // Horizontal seams go from left to right, vertical ones from top to bottom
+        let horizontal: Option<Vec<Vec<Vec<bool>>>> = (0..=size)
+            .map(|i| (0..size).map(|j| seam(rng, corners[i][j], corners[i][j + 1])).collect())
+            .collect();
+        let vertical: Option<Vec<Vec<Vec<bool>>>> = (0..size)
+            .map(|i| (0..=size).map(|j| seam(rng, corners[i][j], corners[i + 1][j])).collect())
+            .collect();
+        let (horizontal, vertical) = match (horizontal, vertical) {
+            (Some(horizontal), Some(vertical)) => (horizontal, vertical),
+            _ => continue,
+        };
+        let (image, monsters) = match day20_image(rng, size * 8) {
+            Some(image) => image,
+            None => continue,
+        };
+        let ids: Vec<usize> = index::sample(rng, 9000, size * size).iter().map(|id| id + 1000).collect();
+        let mut tiles: Vec<(usize, Grid<bool>)> = (0..size * size).map(|tile| {
+            let (i, j) = (tile / size, tile % size);
+            let photo = Grid::from_fn(10, 10, |x, y| match (x, y) {
+                (_, 0) => horizontal[i][j][x],
+                (_, 9) => horizontal[i + 1][j][x],
+                (0, _) => vertical[i][j][y],
+                (9, _) => vertical[i][j + 1][y],
+                _ => image[(j * 8 + x - 1, i * 8 + y - 1)],
+            });
+            (ids[tile], photo.orientations().swap_remove(rng.gen_range(0..8)))
+        }).collect();
+        let corner_ids = ids[0] * ids[size - 1] * ids[size * (size - 1)] * ids[size * size - 1];
+        let rough_waters = image.iter().filter(|x| **x).count() - monsters * 15;
+        tiles.shuffle(rng);
+        let text: Vec<String> = tiles.iter().map(|(id, photo)| format!("Tile {}:\n{}\n", id, photo)).collect();
+        return Generated::known(text.join("\n"), corner_ids, rough_waters)
+    }
+}
+
+// A square image with sea monsters that don't share any cell, and the number of monsters; the waves around them
+// are drawn again until the monsters show up in that orientation only, and nowhere else
+fn day20_image(rng: &mut Random, size: usize) -> Option<(Grid<bool>, usize)> {
+    let monster: Vec<(usize, usize)> = day20::SEA_MONSTER.iter().enumerate()
+        .flat_map(|(y, line)| line.chars().enumerate().filter(|(_, c)| *c == '#').map(move |(x, _)| (x, y)))
+        .collect();
+    let (width, height) = (day20::SEA_MONSTER[0].len(), day20::SEA_MONSTER.len());
+    let mut cells = HashSet::new();
+    let mut monsters = 0;
+    for _ in 0..rng.gen_range(1..=size * size / 300 + 1) {
+        let (x, y) = (rng.gen_range(0..=size - width), rng.gen_range(0..=size - height));
+        let covered: Vec<(usize, usize)> = monster.iter().map(|(dx, dy)| (x + dx, y + dy)).collect();
+        if covered.iter().all(|cell| !cells.contains(cell)) {
+            cells.extend(covered);
+            monsters += 1;
+        }
+    }
+    (0..100).find_map(|_| {
+        let image = Grid::from_fn(size, size, |x, y| cells.contains(&(x, y)) || rng.gen_bool(0.3));
+        let found = image.orientations().iter().filter(|orientation| !day20::find_sea_monsters(orientation).is_empty()).count();
+        match found == 1 && day20::find_sea_monsters(&image) == cells {
+            true => Some((image, monsters)),
+            false => None,
+        }
+    })
+}
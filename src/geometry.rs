@@ -0,0 +1,146 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+// Signed 2D vector; y grows downwards, like the lines of a map, so north is (0, -1)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Vec2 { x, y }
+    }
+
+    // Distance from the origin, moving only along the axes
+    pub fn manhattan(&self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn manhattan_distance(&self, other: Vec2) -> usize {
+        (*self - other).manhattan()
+    }
+
+    // Rotates about the origin by a number of quarter turns
+    pub fn rotate(&self, turn: Turn, quarter_turns: usize) -> Self {
+        (0..quarter_turns % 4).fold(*self, |v, _| match turn {
+            Turn::Right => Vec2::new(-v.y, v.x),
+            Turn::Left => Vec2::new(v.y, -v.x),
+        })
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Vec2 {
+    fn from((x, y): (usize, usize)) -> Self {
+        Vec2 { x: x as isize, y: y as isize }
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, factor: isize) -> Vec2 {
+        Vec2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    // In clockwise order, so that turning right moves forward in this list
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn vector(&self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::new(0, -1),
+            Direction::East => Vec2::new(1, 0),
+            Direction::South => Vec2::new(0, 1),
+            Direction::West => Vec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn(&self, turn: Turn, quarter_turns: usize) -> Self {
+        let index = Direction::ALL.iter().position(|d| d == self).unwrap();
+        let offset = match turn {
+            Turn::Right => quarter_turns % 4,
+            Turn::Left => 4 - quarter_turns % 4,
+        };
+        Direction::ALL[(index + offset) % 4]
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn(Turn::Right, 2)
+    }
+}
+
+// The 8 directions around a position, row by row
+pub static ADJACENT: [Vec2; 8] = [
+    Vec2::new(-1, -1), Vec2::new(0, -1), Vec2::new(1, -1),
+    Vec2::new(-1, 0), Vec2::new(1, 0),
+    Vec2::new(-1, 1), Vec2::new(0, 1), Vec2::new(1, 1),
+];
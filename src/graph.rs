@@ -0,0 +1,231 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+pub type NodeId = usize;
+
+// Nodes that depend on each other in a loop, in order, e.g. [a, b] for a -> b -> a
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<NodeId>);
+
+// Directed graph with weighted edges. Labels are interned, so that adding an edge to an existing label reuses its node
+#[derive(Debug, Clone)]
+pub struct Graph<L, W = ()> {
+    labels: Vec<L>,
+    ids: HashMap<L, NodeId>,
+    successors: Vec<Vec<(NodeId, W)>>,
+    predecessors: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<L: Eq + Hash + Clone, W: Clone> Default for Graph<L, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: Eq + Hash + Clone, W: Clone> Graph<L, W> {
+    pub fn new() -> Self {
+        Graph { labels: Vec::new(), ids: HashMap::new(), successors: Vec::new(), predecessors: Vec::new() }
+    }
+
+    // Returns the existing node for this label, or adds a new one
+    pub fn intern(&mut self, label: L) -> NodeId {
+        if let Some(id) = self.ids.get(&label) {
+            return *id
+        }
+        let id = self.labels.len();
+        self.labels.push(label.clone());
+        self.ids.insert(label, id);
+        self.successors.push(Vec::new());
+        self.predecessors.push(Vec::new());
+        id
+    }
+
+    pub fn id(&self, label: &L) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: NodeId) -> &L {
+        &self.labels[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item=NodeId> {
+        0..self.labels.len()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: W) {
+        self.successors[from].push((to, weight.clone()));
+        self.predecessors[to].push((from, weight));
+    }
+
+    // Interns both labels
+    pub fn add_labeled_edge(&mut self, from: L, to: L, weight: W) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.add_edge(from, to, weight);
+    }
+
+    pub fn successors(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.successors[id]
+    }
+
+    pub fn predecessors(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.predecessors[id]
+    }
+
+    // Same nodes, with every edge going the other way
+    pub fn reversed(&self) -> Self {
+        Graph {
+            labels: self.labels.clone(),
+            ids: self.ids.clone(),
+            successors: self.predecessors.clone(),
+            predecessors: self.successors.clone(),
+        }
+    }
+
+    // Nodes reachable from the start, nearest first; the start itself is only included if it's part of a cycle
+    pub fn reachable_bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut reached = Vec::new();
+        let mut queue: VecDeque<NodeId> = VecDeque::from(vec![start]);
+        while let Some(id) = queue.pop_front() {
+            for (next, _) in self.successors[id].iter() {
+                if !visited[*next] {
+                    visited[*next] = true;
+                    reached.push(*next);
+                    queue.push_back(*next);
+                }
+            }
+        }
+        reached
+    }
+
+    // Nodes reachable from the start, in depth-first preorder; the start itself is only included if it's part of a cycle
+    pub fn reachable_dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.len()];
+        let mut reached = Vec::new();
+        // Pushed in reverse, so that successors are visited in the order their edges were added
+        let mut stack: Vec<NodeId> = self.successors[start].iter().rev().map(|(next, _)| *next).collect();
+        while let Some(id) = stack.pop() {
+            if visited[id] {
+                continue
+            }
+            visited[id] = true;
+            reached.push(id);
+            stack.extend(self.successors[id].iter().rev().map(|(next, _)| *next).filter(|next| !visited[*next]));
+        }
+        reached
+    }
+
+    // Every node after all of its predecessors
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, Cycle> {
+        let mut in_degrees: Vec<usize> = self.predecessors.iter().map(Vec::len).collect();
+        let mut ready: VecDeque<NodeId> = self.nodes().filter(|id| in_degrees[*id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for (next, _) in self.successors[id].iter() {
+                in_degrees[*next] -= 1;
+                if in_degrees[*next] == 0 {
+                    ready.push_back(*next);
+                }
+            }
+        }
+        if order.len() == self.len() {
+            return Ok(order)
+        }
+        // Every remaining node has a remaining predecessor, so walking them backwards must loop
+        let mut path: Vec<NodeId> = Vec::new();
+        let mut id = self.nodes().find(|id| in_degrees[*id] > 0).unwrap();
+        while !path.contains(&id) {
+            path.push(id);
+            id = self.predecessors[id].iter().map(|(previous, _)| *previous).find(|previous| in_degrees[*previous] > 0).unwrap();
+        }
+        let start = path.iter().position(|p| *p == id).unwrap();
+        let mut cycle = path.split_off(start);
+        cycle.reverse();
+        cycle.rotate_right(1);
+        Err(Cycle(cycle))
+    }
+
+    // Computes a value for the start from the values of its successors, computing each reachable node only once.
+    // `f` gets the node and the weight and value of each of its edges
+    pub fn fold_successors<T: Clone, F>(&self, start: NodeId, mut f: F) -> Result<T, Cycle>
+    where
+        F: FnMut(NodeId, Vec<(&W, T)>) -> T,
+    {
+        let mut values: HashMap<NodeId, T> = HashMap::new();
+        let mut path: Vec<NodeId> = Vec::new();
+        self.fold_from(start, &mut f, &mut values, &mut path)
+    }
+
+    fn fold_from<T: Clone, F>(&self, id: NodeId, f: &mut F, values: &mut HashMap<NodeId, T>, path: &mut Vec<NodeId>) -> Result<T, Cycle>
+    where
+        F: FnMut(NodeId, Vec<(&W, T)>) -> T,
+    {
+        if let Some(value) = values.get(&id) {
+            return Ok(value.clone())
+        }
+        if let Some(start) = path.iter().position(|p| *p == id) {
+            return Err(Cycle(path[start..].to_vec()))
+        }
+        path.push(id);
+        let mut children = Vec::with_capacity(self.successors[id].len());
+        for (next, weight) in self.successors[id].iter() {
+            children.push((weight, self.fold_from(*next, f, values, path)?));
+        }
+        path.pop();
+        let value = f(id, children);
+        values.insert(id, value.clone());
+        Ok(value)
+    }
+
+    // Number of distinct paths between two nodes
+    pub fn count_paths(&self, from: NodeId, to: NodeId) -> Result<usize, Cycle> {
+        self.fold_successors(from, |id, children| {
+            if id == to { 1 } else { children.iter().map(|(_, count)| count).sum() }
+        })
+    }
+
+    // Maximum matching from the given nodes to their successors, where each successor is matched at most once.
+    // Returns the matched successor of each of the given nodes, in the same order
+    pub fn bipartite_matching(&self, left: &[NodeId]) -> Vec<Option<NodeId>> {
+        let mut matched_to: HashMap<NodeId, usize> = HashMap::new();
+        for i in 0..left.len() {
+            let mut seen: Vec<NodeId> = Vec::new();
+            self.augment(left, i, &mut matched_to, &mut seen);
+        }
+        let mut matching = vec![None; left.len()];
+        for (right, i) in matched_to {
+            matching[i] = Some(right);
+        }
+        matching
+    }
+
+    // Tries to match left[i], moving previous matches to other successors if needed
+    fn augment(&self, left: &[NodeId], i: usize, matched_to: &mut HashMap<NodeId, usize>, seen: &mut Vec<NodeId>) -> bool {
+        for (right, _) in self.successors[left[i]].iter() {
+            if seen.contains(right) {
+                continue
+            }
+            seen.push(*right);
+            let available = match matched_to.get(right) {
+                None => true,
+                Some(&other) => self.augment(left, other, matched_to, seen),
+            };
+            if available {
+                matched_to.insert(*right, i);
+                return true
+            }
+        }
+        false
+    }
+}
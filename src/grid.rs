@@ -0,0 +1,244 @@
+use std::{
+    fmt,
+    iter::successors,
+    ops::{Index, IndexMut},
+};
+use super::{
+    error::Result,
+    file::Lines,
+    geometry::{Direction, Vec2, ADJACENT},
+};
+
+// Cells that can be read from a character map, and written back to one
+pub trait Cell: Sized {
+    // Accepted characters, for parse errors
+    const EXPECTED: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+// The most common map: '#' is set, '.' is not
+impl Cell for bool {
+    const EXPECTED: &'static str = "'#' or '.'";
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+// Rectangular map stored row by row in a single Vec; positions are (x, y), starting from the top left corner
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "expected {}x{} cells", width, height);
+        Grid { width, height, cells }
+    }
+
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| f(x, y)).collect();
+        Grid { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Vec2) -> bool {
+        position.x >= 0 && position.y >= 0 && (position.x as usize) < self.width && (position.y as usize) < self.height
+    }
+
+    // None outside of the grid
+    pub fn get(&self, position: Vec2) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[position.y as usize * self.width + position.x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Vec2) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.y as usize * self.width + position.x as usize])
+        } else {
+            None
+        }
+    }
+
+    // As if the grid repeated forever in every direction
+    pub fn get_wrapping(&self, position: Vec2) -> &T {
+        let x = position.x.rem_euclid(self.width as isize) as usize;
+        let y = position.y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    // Position one step away in the given direction, if it's still inside of the grid
+    pub fn step(&self, position: (usize, usize), direction: Vec2) -> Option<(usize, usize)> {
+        let next = Vec2::from(position) + direction;
+        if self.contains(next) {
+            Some((next.x as usize, next.y as usize))
+        } else {
+            None
+        }
+    }
+
+    // Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item=(usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        self.cells.iter()
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item=((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    // Orthogonal neighbors inside of the grid
+    pub fn neighbors4(&self, position: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        Direction::ALL.iter().filter_map(move |direction| self.step(position, direction.vector()))
+    }
+
+    // Orthogonal and diagonal neighbors inside of the grid
+    pub fn neighbors8(&self, position: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        ADJACENT.iter().filter_map(move |direction| self.step(position, *direction))
+    }
+
+    // Every position from the given one (excluded) towards the given direction, until the edge of the grid
+    pub fn ray(&self, position: (usize, usize), direction: Vec2) -> impl Iterator<Item=(usize, usize)> + '_ {
+        successors(self.step(position, direction), move |position| self.step(*position, direction))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |x, y| self[(y, self.height - 1 - x)].clone())
+    }
+
+    // Mirrors left and right
+    pub fn flip_horizontal(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |x, y| self[(self.width - 1 - x, y)].clone())
+    }
+
+    // Mirrors top and bottom
+    pub fn flip_vertical(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |x, y| self[(x, self.height - 1 - y)].clone())
+    }
+
+    // All 8 rotations and flips: the 4 rotations of this grid, then the 4 rotations of its horizontal flip
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations = Vec::with_capacity(8);
+        for start in [self.clone(), self.flip_horizontal()].iter() {
+            orientations.push(start.clone());
+            for _ in 0..3 {
+                let rotated = orientations.last().unwrap().rotate_cw();
+                orientations.push(rotated);
+            }
+        }
+        orientations
+    }
+
+    pub fn sub_grid(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        Grid::from_fn(width, height, |i, j| self[(x + i, y + j)].clone())
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    // Reads rows until the end of input or an empty line, which is consumed; every row must have the same width
+    pub fn parse(lines: &mut Lines, what: &str) -> Result<Self> {
+        let first_line = lines.expect_line(what)?;
+        if first_line.is_empty() {
+            return Err(first_line.error(1, format!("expected {}", what)))
+        }
+        let width = first_line.chars().count();
+        let mut cells: Vec<T> = Vec::new();
+        let mut height = 0usize;
+        let mut next_line = Some(first_line);
+        while let Some(line) = next_line {
+            if line.is_empty() {
+                break
+            }
+            let row = line.chars().enumerate().map(|(x, c)| {
+                T::from_char(c).ok_or_else(|| line.error(x + 1, format!("invalid character {:?}, expected {}", c, T::EXPECTED)))
+            }).collect::<Result<Vec<T>>>()?;
+            if row.len() != width {
+                return Err(line.error(row.len().min(width) + 1, format!("expected {} cells in every row", width)))
+            }
+            cells.extend(row);
+            height += 1;
+            next_line = lines.next();
+        }
+        Ok(Grid { width, height, cells })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside of the {}x{} grid", x, y, self.width, self.height);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside of the {}x{} grid", x, y, self.width, self.height);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+// Writes the grid back as a character map, without a trailing newline
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row.iter() {
+                write!(f, "{}", cell.to_char())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    result,
+};
+use serde::{Deserialize, Serialize, Serializer};
+use toml::value::Table;
+use super::{
+    error::{Error, Result},
+    file::Source,
+    progress::Budget,
+    solver::{Day, Part},
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl RecordedAnswers {
+    fn get(&self, part: Part) -> Option<&String> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    fn get_mut(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch { expected: String },
+    // Nothing was recorded for this day, part and input yet
+    Unknown,
+}
+
+// Confirmed answers, keyed by day, input file and part:
+//
+// [day1."inputs/day1.txt"]
+// part1 = '514579'
+// part2 = '241861950'
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    answers: BTreeMap<usize, BTreeMap<String, RecordedAnswers>>,
+}
+
+// Serializes days in numeric order, rather than in the alphabetical order of their "dayN" keys
+struct ByDay<'a>(&'a BTreeMap<usize, BTreeMap<String, RecordedAnswers>>);
+
+impl Serialize for ByDay<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(day, inputs)| (format!("day{}", day), inputs)))
+    }
+}
+
+impl Ledger {
+    // A missing file is an empty ledger, so that answers can be recorded from scratch
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let io_error = |reason: String| Error::Io { source: path.display().to_string(), reason };
+        let by_key: BTreeMap<String, BTreeMap<String, RecordedAnswers>> = match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| io_error(e.to_string()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(io_error(e.to_string())),
+        };
+        let answers = by_key.into_iter().map(|(key, inputs)| {
+            match key.strip_prefix("day").and_then(|day| day.parse::<usize>().ok()) {
+                Some(day) => Ok((day, inputs)),
+                None => Err(io_error(format!("invalid table [{}], expected a day like [day1]", key))),
+            }
+        }).collect::<Result<_>>()?;
+        Ok(Ledger { path: path.to_path_buf(), answers })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let io_error = |reason: String| Error::Io { source: self.path.display().to_string(), reason };
+        let text = toml::to_string_pretty(&ByDay(&self.answers)).map_err(|e| io_error(e.to_string()))?;
+        fs::write(&self.path, text).map_err(|e| io_error(e.to_string()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, day: usize, part: Part, input: &str) -> Option<&str> {
+        self.answers.get(&day)?.get(input)?.get(part).map(String::as_str)
+    }
+
+    // Inputs with at least one recorded answer for the given day
+    pub fn inputs(&self, day: usize) -> Vec<&str> {
+        match self.answers.get(&day) {
+            Some(inputs) => inputs.keys().map(String::as_str).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, day: usize, part: Part, input: &str, answer: &str) {
+        let recorded = self.answers.entry(day).or_default().entry(String::from(input)).or_default();
+        *recorded.get_mut(part) = Some(String::from(answer));
+    }
+
+    pub fn check(&self, day: usize, part: Part, input: &str, answer: &str) -> Check {
+        match self.get(day, part, input) {
+            Some(expected) if expected == answer => Check::Match,
+            Some(expected) => Check::Mismatch { expected: String::from(expected) },
+            None => Check::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    // Nothing was recorded for this part of the input
+    Unrecorded,
+    // Reading, parsing or solving failed
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Unrecorded => write!(f, "unrecorded"),
+            Status::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+// A row per day, part and input, followed by what went wrong with each failure or error
+#[derive(Debug, Clone, Default)]
+pub struct Verification {
+    pub rows: Vec<(usize, Part, String, Status)>,
+    pub diffs: Vec<String>,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let input_width = self.rows.iter().map(|(_, _, input, _)| input.len()).max().unwrap_or(0).max("Input".len());
+        writeln!(f, "Day  Part  {:<width$}  Result", "Input", width = input_width)?;
+        for (day, part, input, status) in self.rows.iter() {
+            writeln!(f, "{:>3}  {:>4}  {:<width$}  {}", day, part.number(), input, status, width = input_width)?;
+        }
+        for diff in self.diffs.iter() {
+            writeln!(f)?;
+            writeln!(f, "{}", diff)?;
+        }
+        Ok(())
+    }
+}
+
+// Solves every input with recorded answers again, or the default input of days without any, using the
+// puzzle's parameters since those are the ones answers are recorded for
+pub fn verify(days: &[(usize, &dyn Day)], ledger: &Ledger, budget: &Budget) -> Verification {
+    let mut verification = Verification::default();
+    for (day, puzzle) in days.iter() {
+        let mut inputs: Vec<String> = ledger.inputs(*day).into_iter().map(String::from).collect();
+        if inputs.is_empty() {
+            inputs.push(Source::for_day(*day).name());
+        }
+        for input in inputs {
+            let parts = [Part::One, Part::Two];
+            let results: Vec<(Part, result::Result<String, String>)> = match Source::from_path(&input).lines().and_then(|lines| puzzle.solve_within(lines, &parts, &Table::new(), budget)) {
+                Ok(solution) => solution.answers.into_iter().map(|a| (a.part, a.result.map_err(|e| e.to_string()))).collect(),
+                Err(e) => parts.iter().map(|part| (*part, Err(e.to_string()))).collect(),
+            };
+            for (part, result) in results {
+                let status = match result {
+                    Ok(answer) => match ledger.check(*day, part, &input, &answer) {
+                        Check::Match => Status::Pass,
+                        Check::Mismatch { expected } => {
+                            verification.diffs.push(format!("Day {}, part {} ({}):\n- {}\n+ {}", day, part.number(), input, expected, answer));
+                            Status::Fail
+                        },
+                        Check::Unknown => Status::Unrecorded,
+                    },
+                    Err(e) => {
+                        verification.diffs.push(format!("Day {}, part {} ({}):\n{}", day, part.number(), input, e));
+                        Status::Error
+                    },
+                };
+                verification.rows.push((*day, part, input.clone(), status));
+            }
+        }
+    }
+    verification
+}
@@ -0,0 +1,75 @@
+#[macro_use] extern crate lazy_static;
+#[macro_use] extern crate log;
+extern crate regex;
+extern crate num_integer;
+
+use solver::{Day, Puzzle};
+
+pub mod batch;
+pub mod bench;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod file;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod ledger;
+pub mod parallel;
+pub mod progress;
+pub mod repl;
+pub mod report;
+pub mod scaffold;
+pub mod serve;
+pub mod solver;
+pub mod watch;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+
+// Every available day, in order
+pub static DAYS: &[(usize, &dyn Day)] = &[
+    (1, &Puzzle::<day1::Day1>::new()),
+    (2, &Puzzle::<day2::Day2>::new()),
+    (3, &Puzzle::<day3::Day3>::new()),
+    (4, &Puzzle::<day4::Day4>::new()),
+    (5, &Puzzle::<day5::Day5>::new()),
+    (6, &Puzzle::<day6::Day6>::new()),
+    (7, &Puzzle::<day7::Day7>::new()),
+    (8, &Puzzle::<day8::Day8>::new()),
+    (9, &Puzzle::<day9::Day9>::new()),
+    (10, &Puzzle::<day10::Day10>::new()),
+    (11, &Puzzle::<day11::Day11>::new()),
+    (12, &Puzzle::<day12::Day12>::new()),
+    (13, &Puzzle::<day13::Day13>::new()),
+    (14, &Puzzle::<day14::Day14>::new()),
+    (15, &Puzzle::<day15::Day15>::new()),
+    (16, &Puzzle::<day16::Day16>::new()),
+    (17, &Puzzle::<day17::Day17>::new()),
+    (18, &Puzzle::<day18::Day18>::new()),
+    (19, &Puzzle::<day19::Day19>::new()),
+    (20, &Puzzle::<day20::Day20>::new()),
+];
+
+pub fn get_day(day: usize) -> Option<&'static dyn Day> {
+    DAYS.iter().find(|(d, _)| *d == day).map(|(_, puzzle)| *puzzle)
+}
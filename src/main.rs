@@ -1,14 +1,780 @@
-#![allow(dead_code)]
-#![allow(unused_imports)]
+use std::{
+    collections::BTreeMap,
+    env,
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+use log::LevelFilter;
+use structopt::StructOpt;
+use toml::value::Table;
 
-#[macro_use] extern crate lazy_static;
-extern crate regex;
-extern crate num_integer;
+use rust_aoc_2020::{
+    batch,
+    bench,
+    client::{self, Client, Fetched, Submission},
+    config::Config,
+    error,
+    file::Source,
+    generate,
+    get_day,
+    ledger::{self, Check, Ledger},
+    parallel::{self, Job},
+    progress::Budget,
+    repl,
+    report,
+    scaffold,
+    serve::Server,
+    solver::{Day, Part, Solution},
+    watch::{self, Watcher},
+    DAYS,
+};
 
-mod file;
+#[derive(Debug, StructOpt)]
+#[structopt(about = "Advent of Code 2020 solutions")]
+struct Options {
+    /// Ledger of confirmed answers, which every run is checked against
+    #[structopt(long, parse(from_os_str), default_value = "answers.toml", global = true)]
+    answers: PathBuf,
+    /// Server to fetch inputs from and submit answers to
+    #[structopt(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL, global = true)]
+    base_url: String,
+    /// Session cookie of a logged in user, required to fetch inputs and submit answers
+    #[structopt(long, env = "AOC_SESSION", hide_env_values = true, global = true)]
+    session: Option<String>,
+    /// Format of the results of `run` and `all`; "json" prints a JSON record per day and part
+    #[structopt(long, possible_values = &["text", "json"], default_value = "text", global = true)]
+    output: Format,
+    /// Logs intermediate steps to stderr: -v for progress, -vv for each day's intermediate results, -vvv for every step.
+    /// Without it, AOC_LOG is read as an env_logger filter, e.g. AOC_LOG=rust_aoc_2020::day15=trace
+    #[structopt(short, long, parse(from_occurrences), global = true)]
+    verbose: u8,
+    /// Stops long-running parts (days 11, 13, 15 and 19) after this many seconds each, reporting a timeout
+    #[structopt(long, global = true)]
+    timeout: Option<u64>,
+    /// Parameters of each day, replacing the values from the puzzle text, e.g. a [day9] table with preamble = 5
+    #[structopt(long, parse(from_os_str), default_value = "params.toml", global = true)]
+    params: PathBuf,
+    /// Overrides a parameter of the parameters file, e.g. --set day9.preamble=5; can be repeated
+    #[structopt(long = "set", number_of_values = 1, global = true)]
+    sets: Vec<String>,
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown output format {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Lists all available days
+    List,
+    /// Runs a single day
+    Run {
+        /// Day to run
+        day: usize,
+        /// Only run the given part (1 or 2); runs both if omitted
+        #[structopt(short, long, possible_values = &["1", "2"])]
+        part: Option<usize>,
+        /// Input file to use instead of inputs/dayN.txt; "-" reads from stdin
+        #[structopt(short, long, parse(from_os_str))]
+        input: Option<PathBuf>,
+        /// Records the answers in the ledger as confirmed
+        #[structopt(long)]
+        record: bool,
+    },
+    /// Runs every available day, in sequence unless --jobs is given
+    All {
+        /// Records the answers in the ledger as confirmed
+        #[structopt(long)]
+        record: bool,
+        /// Runs days in parallel on the given number of workers; 0 uses one worker per CPU
+        #[structopt(short, long)]
+        jobs: Option<usize>,
+    },
+    /// Runs a day again whenever its input or examples change, showing what changed since the last run
+    Watch {
+        /// Day to watch
+        day: usize,
+        /// Input file to watch instead of inputs/dayN.txt
+        #[structopt(short, long, parse(from_os_str))]
+        input: Option<PathBuf>,
+        /// Milliseconds between two checks of the files
+        #[structopt(long, default_value = "500")]
+        interval: u64,
+    },
+    /// Runs days over every set of inputs in a directory, e.g. inputs/alice/day7.txt, and prints their answers side by side
+    Batch {
+        /// Directory of input sets; the dayN.txt files in it form a set as well
+        #[structopt(parse(from_os_str))]
+        dir: PathBuf,
+        /// Days to run; runs every day if omitted
+        days: Vec<usize>,
+        /// Number of worker threads; uses one per CPU if omitted
+        #[structopt(short, long)]
+        jobs: Option<usize>,
+    },
+    /// Runs days on every input with recorded answers, and reports which answers changed; ignores parameters
+    Verify {
+        /// Days to verify; verifies every day if omitted
+        days: Vec<usize>,
+    },
+    /// Downloads inputs into inputs/, skipping days that were already downloaded
+    Fetch {
+        /// Days to fetch; fetches every day if omitted
+        days: Vec<usize>,
+    },
+    /// Submits an answer, and records it in the ledger if it's right; ignores parameters
+    Submit {
+        /// Day to submit an answer for
+        day: usize,
+        /// Part to submit an answer for
+        #[structopt(possible_values = &["1", "2"])]
+        part: usize,
+        /// Answer to submit; solves the day to get it if omitted
+        answer: Option<String>,
+        /// Input file to solve and record the answer for, instead of inputs/dayN.txt
+        #[structopt(short, long, parse(from_os_str))]
+        input: Option<PathBuf>,
+    },
+    /// Times parsing and each part separately over repeated runs
+    Bench {
+        /// Days to benchmark; benchmarks every day if omitted
+        days: Vec<usize>,
+        /// Number of runs for each day
+        #[structopt(short = "n", long, default_value = "10")]
+        iterations: usize,
+        /// File to write the results to, as tab-separated values
+        #[structopt(short, long, parse(from_os_str), default_value = "bench_output.txt")]
+        results: PathBuf,
+    },
+    /// Generates a random input of a day from a seed, printing the answers known from how it was built to stderr
+    Generate {
+        /// Day to generate an input for
+        day: usize,
+        /// Seed of the input; the same seed always generates the same input
+        #[structopt(long, default_value = "0")]
+        seed: u64,
+        /// Solves this many inputs instead, from the seed on, and reports the ones whose answers are wrong;
+        /// ignores parameters
+        #[structopt(short = "n", long)]
+        check: Option<u64>,
+    },
+    /// Loads an input into an interactive shell, to explore the state of days 7, 8, 17 and 20; `help` lists the commands
+    Shell {
+        /// Day to explore
+        day: usize,
+        /// Input file to use instead of inputs/dayN.txt; "-" reads from stdin
+        #[structopt(short, long, parse(from_os_str))]
+        input: Option<PathBuf>,
+    },
+    /// Serves the solvers over HTTP, e.g. POST /days/9/parts/1 with the input as the body, answering with JSON
+    Serve {
+        /// Address to listen on
+        #[structopt(long, default_value = "127.0.0.1:8020")]
+        address: String,
+    },
+    /// Creates the solver, tests and inputs of a new day, and registers it
+    NewDay {
+        /// Day to create
+        day: usize,
+    },
+}
+
+// How `run` and `all` solve and report days
+struct RunOptions {
+    // Records the answers in the ledger instead of checking them
+    record: bool,
+    format: Format,
+    budget: Budget,
+    config: Config,
+}
+
+// Returns whether all parts ran successfully and match the ledger
+fn run_day(day: usize, puzzle: &dyn Day, part: Option<usize>, source: &Source, ledger: &mut Ledger, options: &RunOptions) -> bool {
+    let parts = match part {
+        Some(1) => vec![Part::One],
+        Some(2) => vec![Part::Two],
+        _ => vec![Part::One, Part::Two],
+    };
+    let solution = source.lines().and_then(|lines| puzzle.solve_within(lines, &parts, &options.config.params(day), &options.budget));
+    print_solution(day, &parts, source, solution, ledger, options)
+}
+
+fn print_solution(
+    day: usize,
+    parts: &[Part],
+    source: &Source,
+    solution: error::Result<Solution>,
+    ledger: &mut Ledger,
+    options: &RunOptions,
+) -> bool {
+    let record = options.record;
+    // The ledger only holds the answers of the puzzle itself, so answers with other parameters aren't checked
+    let checked = options.config.params(day).is_empty();
+    if options.format == Format::Json {
+        return report_day(day, parts, source, solution, ledger, record, checked)
+    }
+    let solution = match solution {
+        Ok(solution) => solution,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false
+        },
+    };
+    // Answers are only recorded for files, since other sources can't be run again
+    let input = match source {
+        Source::File(_) if checked => Some(source.name()),
+        _ => None,
+    };
+    let mut success = true;
+    for answer in solution.answers {
+        match (answer.result, &input) {
+            (Ok(result), Some(input)) if record => {
+                ledger.record(day, answer.part, input, &result);
+                println!("Day {}, part {}: {} (recorded)", day, answer.part.number(), result);
+            },
+            (Ok(result), Some(input)) => {
+                match ledger.check(day, answer.part, input, &result) {
+                    Check::Match => println!("Day {}, part {}: {} (confirmed)", day, answer.part.number(), result),
+                    Check::Mismatch { expected } => {
+                        println!("Day {}, part {}: {}", day, answer.part.number(), result);
+                        eprintln!("error: expected {} according to {}", expected, ledger.path().display());
+                        success = false;
+                    },
+                    Check::Unknown => println!("Day {}, part {}: {}", day, answer.part.number(), result),
+                }
+            },
+            (Ok(result), None) => println!("Day {}, part {}: {}", day, answer.part.number(), result),
+            (Err(e), _) => {
+                eprintln!("Day {}, part {}: {}", day, answer.part.number(), e);
+                success = false;
+            },
+        }
+    }
+    success
+}
+
+// Same as print_solution, but prints a JSON record per part
+fn report_day(
+    day: usize,
+    parts: &[Part],
+    source: &Source,
+    solution: error::Result<Solution>,
+    ledger: &mut Ledger,
+    record: bool,
+    checked: bool,
+) -> bool {
+    let is_file = matches!(source, Source::File(_)) && checked;
+    let input = source.name();
+    let mut success = true;
+    for mut result in report::records(day, &input, parts, &solution) {
+        let part = if result.part == 1 { Part::One } else { Part::Two };
+        match &result.answer {
+            Some(answer) if is_file && record => {
+                ledger.record(day, part, &input, answer);
+                result.ledger = Some("recorded");
+            },
+            Some(answer) if is_file => {
+                let check = ledger.check(day, part, &input, answer);
+                success &= !matches!(check, Check::Mismatch { .. });
+                result.set_check(check);
+            },
+            Some(_) => (),
+            None => success = false,
+        }
+        println!("{}", result.to_json());
+    }
+    success
+}
+
+// Prints every day in order once they are all done, with progress on stderr as each one finishes;
+// returns whether all of them ran successfully and match the ledger
+fn run_all_parallel(workers: usize, ledger: &mut Ledger, options: &RunOptions) -> bool {
+    let jobs = DAYS.iter()
+        .map(|(day, puzzle)| Job { day: *day, puzzle: *puzzle, source: Source::for_day(*day), params: options.config.params(*day) })
+        .collect();
+    let run = parallel::run_jobs(jobs, workers, &options.budget, |finished| {
+        let status = if finished.solution.is_ok() { "finished" } else { "failed" };
+        eprintln!("Day {} {} in {:?}", finished.day, status, finished.time);
+    });
+    let summary = match run.critical_path() {
+        Some(slowest) => format!(
+            "Ran {} days on {} workers in {:?}; the slowest was day {} with {:?}, and {:?} in sequence",
+            run.finished.len(), run.workers, run.wall_time, slowest.day, slowest.time, run.sequential_time(),
+        ),
+        None => String::from("No days to run"),
+    };
+    let mut success = true;
+    for finished in run.finished {
+        success &= print_solution(finished.day, &[Part::One, Part::Two], &finished.source, finished.solution, ledger, options);
+    }
+    // Keeps stdout to one record per line in JSON mode
+    match options.format {
+        Format::Text => println!("{}", summary),
+        Format::Json => eprintln!("{}", summary),
+    }
+    success
+}
+
+// Only stops when interrupted
+fn run_watch(day: usize, puzzle: &dyn Day, files: BTreeMap<PathBuf, Table>, interval: Duration, options: &RunOptions) -> ! {
+    let paths: Vec<PathBuf> = files.keys().cloned().collect();
+    let mut watcher = Watcher::new(&paths);
+    let watched: Vec<String> = watcher.paths().map(|path| path.display().to_string()).collect();
+    eprintln!("Watching {}; press Ctrl-C to stop", watched.join(", "));
+    let parts = [Part::One, Part::Two];
+    let mut previous: BTreeMap<PathBuf, Vec<report::Record>> = BTreeMap::new();
+    loop {
+        let changed = watcher.changed();
+        if !changed.is_empty() && options.format == Format::Text {
+            println!();
+        }
+        for path in changed {
+            let source = Source::File(path.clone());
+            let solution = source.lines().and_then(|lines| puzzle.solve_within(lines, &parts, &files[&path], &options.budget));
+            let records = report::records(day, &source.name(), &parts, &solution);
+            match options.format {
+                Format::Text => {
+                    for line in watch::diff(previous.get(&path).map_or(&[], |r| r.as_slice()), &records) {
+                        println!("{}", line);
+                    }
+                },
+                Format::Json => {
+                    for record in records.iter() {
+                        println!("{}", record.to_json());
+                    }
+                },
+            }
+            previous.insert(path, records);
+        }
+        thread::sleep(interval);
+    }
+}
 
-mod day20;
+// Returns whether every input could be solved
+fn run_batch(dir: &Path, days: &[usize], workers: usize, options: &RunOptions) -> bool {
+    if let Some(day) = days.iter().find(|day| get_day(**day).is_none()) {
+        eprintln!("Day {} is not available; see the `list` command", day);
+        return false
+    }
+    let matrix = batch::run_dir(dir, days, &options.config, workers, &options.budget, |finished| {
+        if options.format == Format::Json {
+            let parts = [Part::One, Part::Two];
+            for result in report::records(finished.day, &finished.source.name(), &parts, &finished.solution) {
+                println!("{}", result.to_json());
+            }
+        }
+    });
+    match matrix {
+        Ok(matrix) => {
+            if options.format == Format::Text {
+                print!("{}", matrix.report());
+            }
+            matrix.failures().is_empty()
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        },
+    }
+}
+
+// Returns whether every recorded answer still matches
+fn run_verify(days: &[usize], ledger: &Ledger, budget: &Budget) -> bool {
+    let mut puzzles = Vec::new();
+    for day in days.iter() {
+        match get_day(*day) {
+            Some(puzzle) => puzzles.push((*day, puzzle)),
+            None => {
+                eprintln!("Day {} is not available; see the `list` command", day);
+                return false
+            },
+        }
+    }
+    let verification = ledger::verify(&puzzles, ledger, budget);
+    print!("{}", verification);
+    verification.passed()
+}
+
+// Returns whether every input is available
+fn run_fetch(days: &[usize], client: &Client) -> bool {
+    let mut success = true;
+    for day in days.iter() {
+        match client::fetch_input_cached(client, *day, "inputs") {
+            Ok(Fetched::Cached(path)) => println!("Day {}: already cached in {}", day, path.display()),
+            Ok(Fetched::Downloaded(path)) => println!("Day {}: downloaded to {}", day, path.display()),
+            Err(e) => {
+                eprintln!("{}", e);
+                success = false;
+            },
+        }
+    }
+    success
+}
+
+fn run_new_day(day: usize) -> bool {
+    if day == 0 || day > 25 {
+        eprintln!("Days go from 1 to 25");
+        return false
+    }
+    if get_day(day).is_some() {
+        eprintln!("Day {} already exists", day);
+        return false
+    }
+    match scaffold::new_day(".", day) {
+        Ok(created) => {
+            for path in created.iter() {
+                println!("Created {}", path.display());
+            }
+            println!("Day {} is registered in src/lib.rs; try it with `run {}`", day, day);
+            true
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        },
+    }
+}
+
+// Returns whether the answer is right
+fn run_submit(day: usize, part: Part, answer: Option<String>, source: &Source, client: &Client, ledger: &mut Ledger) -> bool {
+    let puzzle = match get_day(day) {
+        Some(puzzle) => puzzle,
+        None => {
+            eprintln!("Day {} is not available; see the `list` command", day);
+            return false
+        },
+    };
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solution = source.lines().and_then(|lines| puzzle.solve(lines, &[part]));
+            match solution.and_then(|mut solution| solution.answers.remove(0).result) {
+                Ok(answer) => answer,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return false
+                },
+            }
+        },
+    };
+    match client::submit_and_record(client, ledger, day, part, &source.name(), &answer) {
+        Ok(Submission::AlreadyConfirmed(recorded)) => {
+            println!("Day {}, part {}: {} was already confirmed in {}", day, part.number(), recorded, ledger.path().display());
+            if recorded != answer {
+                eprintln!("error: {} doesn't match the confirmed answer", answer);
+                return false
+            }
+            true
+        },
+        Ok(Submission::Judged(verdict)) => {
+            println!("Day {}, part {}: {} ({})", day, part.number(), answer, verdict);
+            if verdict.is_right() {
+                println!("Answer recorded in {}", ledger.path().display());
+            }
+            verdict.is_right()
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        },
+    }
+}
+
+// Returns whether every day could be benchmarked
+fn run_bench(days: &[usize], iterations: usize, output: &Path, config: &Config) -> bool {
+    let mut success = true;
+    let mut results = Vec::new();
+    for day in days.iter() {
+        let puzzle = match get_day(*day) {
+            Some(puzzle) => puzzle,
+            None => {
+                eprintln!("Day {} is not available; see the `list` command", day);
+                success = false;
+                continue
+            },
+        };
+        match Source::for_day(*day).lines().and_then(|lines| bench::bench_day(*day, puzzle, &lines, &config.params(*day), iterations)) {
+            Ok(result) => {
+                for (phase, stats) in result.phases() {
+                    println!(
+                        "Day {}, {}: min {:?}, median {:?}, max {:?}",
+                        day, phase, stats.min, stats.median, stats.max,
+                    );
+                }
+                results.push(result);
+            },
+            Err(e) => {
+                eprintln!("{}", e);
+                success = false;
+            },
+        }
+    }
+    if let Err(e) = bench::write_results(output, &results) {
+        eprintln!("{}", e);
+        return false
+    }
+    println!("Results written to {}", output.display());
+    success
+}
+
+// The input goes to stdout, so that it can be redirected to a file
+fn run_generate(day: usize, seed: u64) -> bool {
+    let generated = match generate::generate(day, seed) {
+        Some(generated) => generated,
+        None => {
+            eprintln!("Day {} has no input generator", day);
+            return false
+        },
+    };
+    print!("{}", generated.text);
+    for part in [Part::One, Part::Two].iter() {
+        eprintln!("Part {}: {}", part.number(), generated.answer(*part).unwrap_or("unknown"));
+    }
+    true
+}
+
+// Failures show their seed, to generate the input again
+fn run_generate_check(day: usize, puzzle: &dyn Day, seeds: Range<u64>, budget: &Budget) -> bool {
+    let checked = generate::check(day, puzzle, seeds, budget, |seed, mismatch| {
+        eprintln!("Day {}, seed {}, {}", day, seed, mismatch);
+    });
+    match checked {
+        Some(checked) => {
+            println!("{}", checked);
+            checked.passed()
+        },
+        None => {
+            eprintln!("Day {} has no input generator", day);
+            false
+        },
+    }
+}
+
+fn save_ledger(ledger: &Ledger, format: Format) {
+    match ledger.save() {
+        Ok(()) if format == Format::Json => (),
+        Ok(()) => println!("Answers recorded in {}", ledger.path().display()),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        },
+    }
+}
+
+// Only warnings and errors are logged by default; other crates never log below warnings unless AOC_LOG says so
+fn init_logging(verbose: u8) {
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(LevelFilter::Warn).format_timestamp(None);
+    match verbose {
+        0 => {
+            if let Ok(filters) = env::var("AOC_LOG") {
+                builder.parse_filters(&filters);
+            }
+        },
+        1 => { builder.filter_module("rust_aoc_2020", LevelFilter::Info); },
+        2 => { builder.filter_module("rust_aoc_2020", LevelFilter::Debug); },
+        _ => { builder.filter_module("rust_aoc_2020", LevelFilter::Trace); },
+    }
+    builder.init();
+}
 
 fn main() {
-    day20::main()
+    let Options { answers, base_url, session, output, verbose, timeout, params, sets, command } = Options::from_args();
+    init_logging(verbose);
+    let budget = Budget::with_timeout(timeout.map(Duration::from_secs));
+    let config = Config::load(&params).and_then(|mut config| {
+        for assignment in sets.iter() {
+            config.set(assignment)?;
+        }
+        Ok(config)
+    });
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        },
+    };
+    let mut ledger = match Ledger::load(&answers) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        },
+    };
+    let all_days = || DAYS.iter().map(|(day, _)| *day).collect::<Vec<usize>>();
+    let client = || match &session {
+        Some(session) => Client::new(&base_url, session),
+        None => {
+            eprintln!("error: a session token is required; use --session or set AOC_SESSION");
+            std::process::exit(1);
+        },
+    };
+    match command {
+        Command::List => {
+            for (day, _) in DAYS.iter() {
+                println!("Day {}", day);
+            }
+        },
+        Command::Run { day, part, input, record } => {
+            let source = match input {
+                Some(path) => Source::from_path(path),
+                None => Source::for_day(day),
+            };
+            match get_day(day) {
+                Some(puzzle) => {
+                    let success = run_day(day, puzzle, part, &source, &mut ledger, &RunOptions { record, format: output, budget: budget.clone(), config: config.clone() });
+                    if record {
+                        save_ledger(&ledger, output);
+                    }
+                    if !success {
+                        std::process::exit(1);
+                    }
+                },
+                None => {
+                    eprintln!("Day {} is not available; see the `list` command", day);
+                    std::process::exit(1);
+                },
+            }
+        },
+        Command::All { record, jobs } => {
+            let options = RunOptions { record, format: output, budget: budget.clone(), config: config.clone() };
+            let mut success = true;
+            match jobs {
+                Some(0) => success = run_all_parallel(parallel::default_workers(), &mut ledger, &options),
+                Some(workers) => success = run_all_parallel(workers, &mut ledger, &options),
+                None => {
+                    for (day, puzzle) in DAYS.iter() {
+                        success &= run_day(*day, *puzzle, None, &Source::for_day(*day), &mut ledger, &options);
+                    }
+                },
+            }
+            if record {
+                save_ledger(&ledger, output);
+            }
+            if !success {
+                std::process::exit(1);
+            }
+        },
+        Command::Watch { day, input, interval } => {
+            let puzzle = match get_day(day) {
+                Some(puzzle) => puzzle,
+                None => {
+                    eprintln!("Day {} is not available; see the `list` command", day);
+                    std::process::exit(1);
+                },
+            };
+            let input = input.unwrap_or_else(|| PathBuf::from(Source::for_day(day).name()));
+            let files = match watch::watched_files(day, input, &config, "inputs/examples") {
+                Ok(files) => files,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                },
+            };
+            let options = RunOptions { record: false, format: output, budget: budget.clone(), config: config.clone() };
+            run_watch(day, puzzle, files, Duration::from_millis(interval), &options);
+        },
+        Command::Batch { dir, days, jobs } => {
+            let days = if days.is_empty() { all_days() } else { days };
+            let options = RunOptions { record: false, format: output, budget: budget.clone(), config: config.clone() };
+            if !run_batch(&dir, &days, jobs.unwrap_or_else(parallel::default_workers), &options) {
+                std::process::exit(1);
+            }
+        },
+        Command::Verify { days } => {
+            let days = if days.is_empty() { all_days() } else { days };
+            if !run_verify(&days, &ledger, &budget) {
+                std::process::exit(1);
+            }
+        },
+        Command::Fetch { days } => {
+            let days = if days.is_empty() { all_days() } else { days };
+            if !run_fetch(&days, &client()) {
+                std::process::exit(1);
+            }
+        },
+        Command::Submit { day, part, answer, input } => {
+            let source = match input {
+                Some(path) => Source::from_path(path),
+                None => Source::for_day(day),
+            };
+            let part = if part == 1 { Part::One } else { Part::Two };
+            if !run_submit(day, part, answer, &source, &client(), &mut ledger) {
+                std::process::exit(1);
+            }
+        },
+        Command::Bench { days, iterations, results } => {
+            let days = if days.is_empty() { all_days() } else { days };
+            if !run_bench(&days, iterations, &results, &config) {
+                std::process::exit(1);
+            }
+        },
+        Command::Generate { day, seed, check } => {
+            let success = match (get_day(day), check) {
+                (None, _) => {
+                    eprintln!("Day {} is not available; see the `list` command", day);
+                    false
+                },
+                (Some(_), None) => run_generate(day, seed),
+                (Some(puzzle), Some(count)) => run_generate_check(day, puzzle, seed..seed + count, &budget),
+            };
+            if !success {
+                std::process::exit(1);
+            }
+        },
+        Command::Shell { day, input } => {
+            if !repl::SHELL_DAYS.contains(&day) {
+                eprintln!("Day {} has no shell; try days {:?}", day, repl::SHELL_DAYS);
+                std::process::exit(1);
+            }
+            let source = match input {
+                Some(path) => Source::from_path(path),
+                None => Source::for_day(day),
+            };
+            let shell = source.lines().and_then(|lines| repl::open(day, lines)).and_then(|session| {
+                eprintln!("Loaded {}; type `help` for the commands of day {}", source.name(), day);
+                repl::run_shell(session, Path::new(".shell_history"))
+            });
+            if let Err(e) = shell {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        Command::Serve { address } => {
+            let server = match Server::bind(&address) {
+                Ok(server) => server,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                },
+            };
+            eprintln!("Listening on http://{}", server.address());
+            server.run(&config, &budget);
+        },
+        Command::NewDay { day } => {
+            if !run_new_day(day) {
+                std::process::exit(1);
+            }
+        },
+    }
 }
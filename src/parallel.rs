@@ -0,0 +1,102 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use toml::value::Table;
+use super::{
+    error::Result,
+    file::Source,
+    progress::Budget,
+    solver::{Day, Part, Solution},
+};
+
+// A day to run, the input to run it on, and its parameters
+pub struct Job<'a> {
+    pub day: usize,
+    pub puzzle: &'a dyn Day,
+    pub source: Source,
+    pub params: Table,
+}
+
+#[derive(Debug)]
+pub struct Finished {
+    pub day: usize,
+    pub source: Source,
+    pub solution: Result<Solution>,
+    // Reading the input, parsing and solving every part
+    pub time: Duration,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    // In the same order as the jobs
+    pub finished: Vec<Finished>,
+    pub workers: usize,
+    pub wall_time: Duration,
+}
+
+impl Run {
+    // Time the days would take one after another
+    pub fn sequential_time(&self) -> Duration {
+        self.finished.iter().map(|f| f.time).sum()
+    }
+
+    // The slowest day, which no number of workers can finish any sooner
+    pub fn critical_path(&self) -> Option<&Finished> {
+        self.finished.iter().max_by_key(|f| f.time)
+    }
+}
+
+// One worker per available CPU
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+// Runs both parts of every job, taking the next job in order whenever a worker is free.
+// `on_finish` is called as soon as each day is done, in whichever order they finish
+pub fn run_jobs<F: FnMut(&Finished)>(jobs: Vec<Job>, workers: usize, budget: &Budget, mut on_finish: F) -> Run {
+    let workers = workers.clamp(1, jobs.len().max(1));
+    let parts = [Part::One, Part::Two];
+    let next_job = AtomicUsize::new(0);
+    let start = Instant::now();
+    let mut finished: Vec<Option<Finished>> = jobs.iter().map(|_| None).collect();
+    info!("Running {} jobs on {} workers", jobs.len(), workers);
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel::<(usize, Finished)>();
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (jobs, next_job) = (&jobs, &next_job);
+            scope.spawn(move || {
+                loop {
+                    let i = next_job.fetch_add(1, Ordering::SeqCst);
+                    let job = match jobs.get(i) {
+                        Some(job) => job,
+                        None => break,
+                    };
+                    debug!("Day {}: started on {:?}", job.day, thread::current().id());
+                    let start = Instant::now();
+                    let solution = job.source.lines().and_then(|lines| job.puzzle.solve_within(lines, &parts, &job.params, budget));
+                    let result = Finished { day: job.day, source: job.source.clone(), solution, time: start.elapsed() };
+                    if sender.send((i, result)).is_err() {
+                        break
+                    }
+                }
+            });
+        }
+        // Only the workers can send now, so the loop ends once they are all done
+        drop(sender);
+        for (i, result) in receiver {
+            on_finish(&result);
+            finished[i] = Some(result);
+        }
+    });
+    Run {
+        finished: finished.into_iter().map(|f| f.expect("every job sends a result")).collect(),
+        workers,
+        wall_time: start.elapsed(),
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    cell::Cell,
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use super::error::{Error, Result};
+
+// Stops solvers from another thread; every clone shares the same flag
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    pub fn new() -> Self {
+        CancelHandle::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// Limits shared by every part of a run; each part gets the whole timeout for itself
+#[derive(Debug, Clone)]
+pub struct Budget {
+    pub timeout: Option<Duration>,
+    pub cancel: CancelHandle,
+    // Minimum time between two progress reports of the same part
+    pub report_every: Duration,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget { timeout: None, cancel: CancelHandle::new(), report_every: Duration::from_secs(1) }
+    }
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Budget::default()
+    }
+
+    pub fn with_timeout(timeout: Option<Duration>) -> Self {
+        Budget { timeout, ..Budget::default() }
+    }
+
+    // Starts the clock for a part, e.g. "Day15 part 2"
+    pub fn start(&self, label: &str) -> Progress {
+        let now = Instant::now();
+        Progress {
+            label: String::from(label),
+            start: now,
+            deadline: self.timeout.map(|timeout| now + timeout),
+            timeout: self.timeout,
+            cancel: self.cancel.clone(),
+            report_every: self.report_every,
+            last_report: Cell::new(now),
+        }
+    }
+}
+
+// How far along a part is: out of a known total, like the turns of day 15, or just counting iterations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Update {
+    pub done: usize,
+    pub total: Option<usize>,
+}
+
+impl fmt::Display for Update {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.total {
+            Some(total) if total > 0 => write!(f, "{}% ({} of {})", self.done * 100 / total, self.done, total),
+            _ => write!(f, "iteration {}", self.done),
+        }
+    }
+}
+
+// Handed to long-running parts, which check it regularly. Checking reads the clock, so tight loops should
+// only check every few thousand iterations
+#[derive(Debug)]
+pub struct Progress {
+    label: String,
+    start: Instant,
+    deadline: Option<Instant>,
+    timeout: Option<Duration>,
+    cancel: CancelHandle,
+    report_every: Duration,
+    last_report: Cell<Instant>,
+}
+
+impl Progress {
+    // Never times out, and can't be cancelled; for parts called directly, e.g. in tests
+    pub fn unlimited() -> Self {
+        Budget::unlimited().start("unlimited")
+    }
+
+    // Fails once the part is cancelled or out of time, and otherwise logs the update from time to time
+    pub fn check(&self, done: usize, total: Option<usize>) -> Result<()> {
+        if self.cancel.is_cancelled() {
+            info!("{}: cancelled at {}", self.label, Update { done, total });
+            return Err(Error::Cancelled)
+        }
+        let now = Instant::now();
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.timeout) {
+            if now >= deadline {
+                info!("{}: timed out at {}", self.label, Update { done, total });
+                return Err(Error::Timeout(timeout))
+            }
+        }
+        if now.duration_since(self.last_report.get()) >= self.report_every {
+            self.last_report.set(now);
+            info!("{}: {} after {:?}", self.label, Update { done, total }, now.duration_since(self.start));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,494 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+    result,
+};
+use rustyline::{
+    completion::Completer,
+    error::ReadlineError,
+    highlight::Highlighter,
+    hint::Hinter,
+    validate::Validator,
+    Context,
+    Editor,
+    Helper,
+};
+use super::{
+    day17::{self, Day17, Dimension},
+    day20::{self, Camera, Day20},
+    day7::{self, BagRules, Day7},
+    day8::{Day8, Instruction, Machine},
+    error::{Error, Result},
+    file::Lines,
+    grid::Grid,
+    solver::Solver,
+};
+
+// Days with a shell
+pub const SHELL_DAYS: &[usize] = &[7, 8, 17, 20];
+
+// How to call a command, and what it does, e.g. ("step [count]", "runs the next instructions")
+pub type Usage = (&'static str, &'static str);
+
+// An input loaded into the shell, with the queries and steps it understands. Commands fail with a message,
+// which is printed instead of their output
+pub trait Session {
+    fn usage(&self) -> &'static [Usage];
+
+    // Whatever can follow a command, like bag colors or "layer z=0", for tab completion
+    fn completions(&self, command: &str) -> Vec<String>;
+
+    fn run(&mut self, command: &str, args: &[String]) -> result::Result<String, String>;
+}
+
+pub fn open(day: usize, lines: Lines) -> Result<Box<dyn Session>> {
+    match day {
+        7 => Ok(Box::new(BagSession { rules: Day7::parse(lines)? })),
+        8 => Ok(Box::new(HandheldSession::new(Day8::parse(lines)?))),
+        17 => Ok(Box::new(CubeSession::new(Day17::parse(lines)?, false))),
+        20 => Ok(Box::new(TileSession::new(Day20::parse(lines)?))),
+        _ => Err(Error::solve(format!("day {} has no shell, only days {:?} do", day, SHELL_DAYS))),
+    }
+}
+
+// Words are separated by spaces, unless they're in double quotes, e.g. `contains "shiny gold"`
+pub fn split_words(line: &str) -> result::Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                word.get_or_insert_with(String::new);
+            },
+            c if c.is_whitespace() && !quoted => words.extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quoted {
+        return Err(String::from("missing closing quote"))
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+// Runs a line of input; None if it's empty
+pub fn execute(session: &mut dyn Session, line: &str) -> Option<result::Result<String, String>> {
+    let words = match split_words(line) {
+        Ok(words) => words,
+        Err(e) => return Some(Err(e)),
+    };
+    let (command, args) = words.split_first()?;
+    if command == "help" {
+        let width = session.usage().iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+        let lines: Vec<String> = session.usage().iter().chain(&[("help", "shows this list"), ("quit", "leaves the shell")])
+            .map(|(usage, description)| format!("{:<width$}  {}", usage, description, width = width))
+            .collect();
+        return Some(Ok(lines.join("\n")))
+    }
+    if !session.usage().iter().any(|(usage, _)| usage.split(' ').next() == Some(command.as_str())) {
+        return Some(Err(format!("unknown command {:?}; see `help`", command)))
+    }
+    Some(session.run(command, args))
+}
+
+// Reads commands until "quit" or end of input, keeping the history in the given file
+pub fn run_shell(mut session: Box<dyn Session>, history: &Path) -> Result<()> {
+    let mut editor = Editor::<ShellHelper>::new();
+    // There is no history the first time
+    let _ = editor.load_history(history);
+    loop {
+        editor.set_helper(Some(ShellHelper::new(&*session)));
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(Error::Io { source: String::from("the terminal"), reason: e.to_string() }),
+        };
+        editor.add_history_entry(line.as_str());
+        if matches!(line.trim(), "quit" | "exit") {
+            break
+        }
+        match execute(&mut *session, &line) {
+            Some(Ok(output)) if !output.is_empty() => println!("{}", output),
+            Some(Err(e)) => println!("error: {}", e),
+            _ => (),
+        }
+    }
+    if let Err(e) = editor.save_history(history) {
+        warn!("Couldn't save the history to {}: {}", history.display(), e);
+    }
+    Ok(())
+}
+
+// Completes the command, then what follows it as a whole, so that colors with spaces complete too
+struct ShellHelper {
+    commands: Vec<String>,
+    completions: HashMap<String, Vec<String>>,
+}
+
+impl ShellHelper {
+    fn new(session: &dyn Session) -> Self {
+        let mut commands: Vec<String> = session.usage().iter()
+            .filter_map(|(usage, _)| usage.split(' ').next().map(String::from))
+            .collect();
+        commands.dedup();
+        let completions = commands.iter().map(|command| (command.clone(), session.completions(command))).collect();
+        commands.extend(["help", "quit"].iter().map(|command| String::from(*command)));
+        ShellHelper { commands, completions }
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let (command, rest) = match line.split_once(' ') {
+            Some(split) => split,
+            None => return Ok((0, self.commands.iter().filter(|c| c.starts_with(line)).cloned().collect())),
+        };
+        let start = pos - rest.len();
+        let (prefix, quoted) = match rest.strip_prefix('"') {
+            Some(prefix) => (prefix, true),
+            None => (rest, false),
+        };
+        let candidates = self.completions.get(command).map(|completions| completions.iter()
+            .filter(|completion| completion.starts_with(prefix))
+            .map(|completion| if quoted { format!("\"{}\"", completion) } else { completion.clone() })
+            .collect());
+        Ok((start, candidates.unwrap_or_default()))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+fn parse_arg<T: std::str::FromStr>(arg: &str, what: &str) -> result::Result<T, String> {
+    arg.parse().map_err(|_| format!("invalid {} {:?}", what, arg))
+}
+
+// Errors of the solvers, without the "error: " that the shell adds
+fn message(e: Error) -> String {
+    match e {
+        Error::Solve(reason) => reason,
+        e => e.to_string(),
+    }
+}
+
+// Day 7: the bag rules
+struct BagSession {
+    rules: BagRules,
+}
+
+impl Session for BagSession {
+    fn usage(&self) -> &'static [Usage] {
+        &[
+            ("contains <color>", "lists the bags that can eventually contain a bag of this color"),
+            ("inside <color>", "shows what a bag of this color contains, and how many bags in total"),
+        ]
+    }
+
+    fn completions(&self, _command: &str) -> Vec<String> {
+        let mut colors: Vec<String> = self.rules.nodes().map(|id| self.rules.label(id).clone()).collect();
+        colors.sort();
+        colors
+    }
+
+    // Colors can also be given without quotes, as several words
+    fn run(&mut self, command: &str, args: &[String]) -> result::Result<String, String> {
+        let color = args.join(" ");
+        if color.is_empty() {
+            return Err(String::from("expected a color, e.g. \"shiny gold\""))
+        }
+        match command {
+            "contains" => {
+                let mut containers = day7::get_colors_containing(&self.rules, &color).map_err(message)?;
+                containers.sort();
+                Ok(format!("{} colors can contain {} bags: {}", containers.len(), color, containers.join(", ")))
+            },
+            _ => {
+                let id = self.rules.id(&color).ok_or_else(|| format!("there is no rule for {} bags", color))?;
+                let contents: Vec<String> = self.rules.successors(id).iter()
+                    .map(|(inside, count)| format!("{} {}", count, self.rules.label(*inside)))
+                    .collect();
+                let total = day7::get_total_bags_inside(&self.rules, &color).map_err(message)?;
+                let contents = if contents.is_empty() { String::from("no other bags") } else { contents.join(", ") };
+                Ok(format!("{} bags contain {}; {} bags in total", color, contents, total))
+            },
+        }
+    }
+}
+
+// Day 8: the handheld, one instruction at a time
+struct HandheldSession {
+    code: Vec<Instruction>,
+    machine: Machine,
+    visited: HashSet<usize>,
+    steps: usize,
+}
+
+impl HandheldSession {
+    fn new(code: Vec<Instruction>) -> Self {
+        HandheldSession { code, machine: Machine::default(), visited: HashSet::new(), steps: 0 }
+    }
+
+    // Why the code can't go on, if it can't
+    fn stopped(&self) -> Option<String> {
+        if self.machine.is_terminated(&self.code) {
+            Some(format!("terminated with {} in the accumulator", self.machine.acc))
+        } else if self.visited.contains(&self.machine.head) {
+            Some(format!("instruction {} runs again: loop detected with {} in the accumulator", self.machine.head + 1, self.machine.acc))
+        } else {
+            None
+        }
+    }
+
+    // The line of a step, e.g. "   3: acc +1   acc 1"
+    fn step(&mut self) -> String {
+        let head = self.machine.head;
+        self.visited.insert(head);
+        self.machine.step(&self.code);
+        self.steps += 1;
+        format!("{:>4}: {:<8} acc {}", head + 1, show_instruction(&self.code[head]), self.machine.acc)
+    }
+}
+
+fn show_instruction(instruction: &Instruction) -> String {
+    match instruction {
+        Instruction::Accumulator(arg) => format!("acc {:+}", arg),
+        Instruction::Jump(arg) => format!("jmp {:+}", arg),
+        Instruction::NoOp(arg) => format!("nop {:+}", arg),
+    }
+}
+
+impl Session for HandheldSession {
+    fn usage(&self) -> &'static [Usage] {
+        &[
+            ("step [count]", "runs the next instruction, or the next few, stopping at a loop"),
+            ("run", "runs until the code loops or terminates"),
+            ("state", "shows the next instruction and the accumulator"),
+            ("reset", "starts over from the first instruction"),
+        ]
+    }
+
+    fn completions(&self, _command: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn run(&mut self, command: &str, args: &[String]) -> result::Result<String, String> {
+        match command {
+            "step" => {
+                let count = args.first().map(|arg| parse_arg::<usize>(arg, "count")).transpose()?.unwrap_or(1);
+                let mut lines = Vec::new();
+                for _ in 0..count {
+                    if let Some(reason) = self.stopped() {
+                        lines.push(reason);
+                        break
+                    }
+                    lines.push(self.step());
+                }
+                lines.extend(self.stopped().filter(|reason| lines.last() != Some(reason)));
+                Ok(lines.join("\n"))
+            },
+            "run" => {
+                while self.stopped().is_none() {
+                    self.step();
+                }
+                Ok(format!("{} after {} steps", self.stopped().expect("the loop only ends once stopped"), self.steps))
+            },
+            "state" => Ok(match self.code.get(self.machine.head) {
+                Some(next) => format!(
+                    "next: {} at instruction {} of {}, acc {}, {} steps so far",
+                    show_instruction(next), self.machine.head + 1, self.code.len(), self.machine.acc, self.steps,
+                ),
+                None => format!("terminated with {} in the accumulator after {} steps", self.machine.acc, self.steps),
+            }),
+            _ => {
+                *self = HandheldSession::new(self.code.clone());
+                Ok(String::new())
+            },
+        }
+    }
+}
+
+// Day 17: the pocket dimension, one cycle at a time
+struct CubeSession {
+    initial_slice: Grid<bool>,
+    dimension: Dimension,
+    cycles: usize,
+}
+
+impl CubeSession {
+    fn new(initial_slice: Grid<bool>, four_dimensional: bool) -> Self {
+        let dimension = day17::get_initial_state(&initial_slice, four_dimensional);
+        CubeSession { initial_slice, dimension, cycles: 0 }
+    }
+}
+
+impl Session for CubeSession {
+    fn usage(&self) -> &'static [Usage] {
+        &[
+            ("cycle [count]", "runs the next cycle, or the next few"),
+            ("show layer z=<z> [w=<w>]", "shows the active cubes of a layer"),
+            ("count", "counts the active cubes"),
+            ("reset [3d|4d]", "starts over from the initial slice, in 3 or 4 dimensions"),
+        ]
+    }
+
+    fn completions(&self, command: &str) -> Vec<String> {
+        let (min, max) = self.dimension.get_bounds();
+        match command {
+            "show" => (min.2..=max.2).flat_map(|z| (min.3..=max.3).map(move |w| match w {
+                0 => format!("layer z={}", z),
+                w => format!("layer z={} w={}", z, w),
+            })).collect(),
+            "reset" => vec![String::from("3d"), String::from("4d")],
+            _ => Vec::new(),
+        }
+    }
+
+    fn run(&mut self, command: &str, args: &[String]) -> result::Result<String, String> {
+        match command {
+            "cycle" => {
+                let count = args.first().map(|arg| parse_arg::<usize>(arg, "count")).transpose()?.unwrap_or(1);
+                for _ in 0..count {
+                    self.dimension.run_cycle();
+                    self.cycles += 1;
+                }
+                Ok(format!("After cycle {}: {} active cubes", self.cycles, self.dimension.active_cube_count()))
+            },
+            "show" => {
+                if args.first().map(String::as_str) != Some("layer") {
+                    return Err(String::from("expected `show layer z=<z>`"))
+                }
+                let (mut z, mut w) = (None, 0);
+                for arg in args[1..].iter() {
+                    match arg.split_once('=') {
+                        Some(("z", value)) => z = Some(parse_arg::<isize>(value, "z")?),
+                        Some(("w", value)) => w = parse_arg::<isize>(value, "w")?,
+                        _ => return Err(format!("expected z=<z> or w=<w>, found {:?}", arg)),
+                    }
+                }
+                let z = z.ok_or_else(|| String::from("expected z=<z>"))?;
+                let heading = if w == 0 { format!("z={}", z) } else { format!("z={}, w={}", z, w) };
+                Ok(format!("{}\n{}", heading, self.dimension.get_layer(z, w)))
+            },
+            "count" => Ok(format!("{} active cubes after {} cycles", self.dimension.active_cube_count(), self.cycles)),
+            _ => {
+                let four_dimensional = match args.first().map(String::as_str) {
+                    None | Some("3d") => false,
+                    Some("4d") => true,
+                    Some(other) => return Err(format!("expected 3d or 4d, found {:?}", other)),
+                };
+                *self = CubeSession::new(self.initial_slice.clone(), four_dimensional);
+                Ok(format!("{} active cubes in {} dimensions", self.dimension.active_cube_count(), if four_dimensional { 4 } else { 3 }))
+            },
+        }
+    }
+}
+
+// Day 20: the camera tiles
+struct TileSession {
+    cameras: BTreeMap<usize, Camera>,
+    // Cameras sharing each border, by border key
+    matching_borders: HashMap<usize, HashSet<usize>>,
+}
+
+impl TileSession {
+    fn new(cameras: Vec<Camera>) -> Self {
+        let matching_borders = day20::get_matching_borders(&cameras);
+        TileSession { cameras: cameras.into_iter().map(|camera| (camera.id, camera)).collect(), matching_borders }
+    }
+
+    fn neighbors(&self, camera: &Camera) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = [&camera.north, &camera.east, &camera.south, &camera.west].iter()
+            .filter_map(|border| self.matching_borders.get(&border.key()))
+            .flatten()
+            .filter(|id| **id != camera.id)
+            .copied()
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+}
+
+// Grids next to each other, with their titles above them
+fn side_by_side(grids: &[(String, Grid<bool>)]) -> String {
+    let width = grids.iter().map(|(title, grid)| title.len().max(grid.width())).max().unwrap_or(0);
+    let height = grids.iter().map(|(_, grid)| grid.height()).max().unwrap_or(0);
+    let rows: Vec<Vec<String>> = grids.iter().map(|(_, grid)| grid.to_string().lines().map(String::from).collect()).collect();
+    let mut lines = vec![grids.iter().map(|(title, _)| format!("{:<width$}", title, width = width)).collect::<Vec<_>>().join("  ")];
+    for y in 0..height {
+        let row: Vec<String> = rows.iter().map(|rows| format!("{:<width$}", rows.get(y).map_or("", String::as_str), width = width)).collect();
+        lines.push(row.join("  "));
+    }
+    lines.iter().map(|line| line.trim_end()).collect::<Vec<_>>().join("\n")
+}
+
+impl Session for TileSession {
+    fn usage(&self) -> &'static [Usage] {
+        &[
+            ("tiles", "lists the tiles, with the tiles they share a border with"),
+            ("tile <id> [orientations]", "shows a tile and its borders, or all 8 of its rotations and flips"),
+            ("corners", "shows the corner tiles and their product"),
+        ]
+    }
+
+    fn completions(&self, command: &str) -> Vec<String> {
+        match command {
+            "tile" => self.cameras.keys().flat_map(|id| vec![id.to_string(), format!("{} orientations", id)]).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn run(&mut self, command: &str, args: &[String]) -> result::Result<String, String> {
+        match command {
+            "tiles" => Ok(self.cameras.values().map(|camera| {
+                let neighbors: Vec<String> = self.neighbors(camera).iter().map(|id| id.to_string()).collect();
+                format!("{}: next to {}", camera.id, neighbors.join(", "))
+            }).collect::<Vec<_>>().join("\n")),
+            "tile" => {
+                let id = args.first().ok_or_else(|| String::from("expected a tile ID"))?;
+                let camera = self.cameras.get(&parse_arg::<usize>(id, "tile ID")?).ok_or_else(|| format!("there is no tile {}", id))?;
+                match args.get(1).map(String::as_str) {
+                    None => Ok(format!(
+                        "Tile {}:\n{}\nborders: north {}, east {}, south {}, west {}",
+                        camera.id, camera.photo, camera.north.hash, camera.east.hash, camera.south.hash, camera.west.hash,
+                    )),
+                    Some("orientations") => {
+                        let orientations: Vec<(String, Grid<bool>)> = camera.photo.orientations().into_iter().enumerate().map(|(i, photo)| {
+                            let title = match i {
+                                0 => String::from("original"),
+                                4 => String::from("flipped"),
+                                i if i < 4 => format!("cw {}", i * 90),
+                                i => format!("flipped cw {}", (i - 4) * 90),
+                            };
+                            (title, photo)
+                        }).collect();
+                        Ok(format!("{}\n\n{}", side_by_side(&orientations[..4]), side_by_side(&orientations[4..])))
+                    },
+                    Some(other) => Err(format!("expected `orientations`, found {:?}", other)),
+                }
+            },
+            _ => {
+                let cameras: Vec<Camera> = self.cameras.values().cloned().collect();
+                let corners: Vec<String> = cameras.iter()
+                    .filter(|camera| day20::is_corner(camera, &self.matching_borders))
+                    .map(|camera| camera.id.to_string())
+                    .collect();
+                let product = day20::get_product_of_corners(&cameras).map_err(message)?;
+                Ok(format!("corners: {}; product {}", corners.join(", "), product))
+            },
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    collections::BTreeMap,
+};
+use serde::Serialize;
+use serde_json::Value;
+use super::{
+    error::Result,
+    ledger::Check,
+    solver::{Part, Solution},
+};
+
+// Times in microseconds, like the benchmark results; parsing is missing if the input couldn't be read or parsed
+#[derive(Debug, Clone, Serialize)]
+pub struct Timings {
+    pub parse_us: Option<u128>,
+    pub solve_us: Option<u128>,
+}
+
+// One day and part of a run, as written by `--output json`
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    pub day: usize,
+    pub part: usize,
+    pub input: String,
+    pub answer: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    // How the answer compares to the ledger: "confirmed", "mismatch", "unrecorded" or "recorded"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ledger: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    pub timings: Timings,
+    pub extra: BTreeMap<&'static str, Value>,
+}
+
+// A record per part; if solving failed as a whole, every part gets the same error
+pub fn records(day: usize, input: &str, parts: &[Part], solution: &Result<Solution>) -> Vec<Record> {
+    match solution {
+        Ok(solution) => solution.answers.iter().map(|answer| Record {
+            day,
+            part: answer.part.number(),
+            input: String::from(input),
+            answer: answer.result.as_ref().ok().cloned(),
+            kind: Some(answer.kind),
+            error: answer.result.as_ref().err().map(|e| e.to_string()),
+            ledger: None,
+            expected: None,
+            timings: Timings { parse_us: Some(solution.parse_time.as_micros()), solve_us: Some(answer.time.as_micros()) },
+            extra: answer.extra.iter().cloned().collect(),
+        }).collect(),
+        Err(e) => parts.iter().map(|part| Record {
+            day,
+            part: part.number(),
+            input: String::from(input),
+            answer: None,
+            kind: None,
+            error: Some(e.to_string()),
+            ledger: None,
+            expected: None,
+            timings: Timings { parse_us: None, solve_us: None },
+            extra: BTreeMap::new(),
+        }).collect(),
+    }
+}
+
+impl Record {
+    pub fn set_check(&mut self, check: Check) {
+        match check {
+            Check::Match => self.ledger = Some("confirmed"),
+            Check::Mismatch { expected } => {
+                self.ledger = Some("mismatch");
+                self.expected = Some(expected);
+            },
+            Check::Unknown => self.ledger = Some("unrecorded"),
+        }
+    }
+
+    // A single line of JSON, so that a run can be read as JSON Lines
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records are always serializable")
+    }
+}
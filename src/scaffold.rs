@@ -0,0 +1,133 @@
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+use regex::Regex;
+use super::error::{Error, Result};
+
+// Placeholders are replaced by the day number; the solver only splits the input into lines,
+// and both parts fail until they're written
+static MODULE_TEMPLATE: &str = "use super::{
+    error::{Error, Result},
+    file::Lines,
+    solver::{NoParams, Solver},
+};
+
+pub struct Day{N};
+
+impl Solver for Day{N} {
+    type Input = Vec<String>;
+    type Params = NoParams;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(lines: Lines) -> Result<Vec<String>> {
+        Ok(lines.map(|line| line.into_text()).collect())
+    }
+
+    fn part1(_input: &Vec<String>) -> Result<usize> {
+        Err(Error::solve(\"part 1 is not solved yet\"))
+    }
+
+    fn part2(_input: &Vec<String>) -> Result<usize> {
+        Err(Error::solve(\"part 2 is not solved yet\"))
+    }
+}
+";
+
+// Ignored until the example from the puzzle text and its answers are filled in
+static TEST_TEMPLATE: &str = "mod common;
+
+use rust_aoc_2020::{day{N}::Day{N}, solver::Solver};
+
+#[test]
+#[ignore]
+fn part1_example() {
+    let input = common::parse::<Day{N}>(\"day{N}\");
+    assert_eq!(Day{N}::part1(&input).unwrap(), 0);
+}
+
+#[test]
+#[ignore]
+fn part2_example() {
+    let input = common::parse::<Day{N}>(\"day{N}\");
+    assert_eq!(Day{N}::part2(&input).unwrap(), 0);
+}
+";
+
+fn io_error(path: &Path) -> impl Fn(io::Error) -> Error + '_ {
+    move |e| Error::Io { source: path.display().to_string(), reason: e.to_string() }
+}
+
+// Adds a line among the lines matching `pattern`, keeping them sorted by the day number it captures
+fn insert_sorted(lines: &mut Vec<String>, pattern: &Regex, day: usize, new_line: String) -> Option<()> {
+    let days: Vec<(usize, usize)> = lines.iter().enumerate()
+        .filter_map(|(i, line)| pattern.captures(line).map(|caps| (i, caps[1].parse().unwrap())))
+        .collect();
+    let index = match days.iter().rev().find(|(_, d)| *d < day) {
+        Some((i, _)) => i + 1,
+        None => days.first()?.0,
+    };
+    lines.insert(index, new_line);
+    Some(())
+}
+
+// Adds the module declaration and the `DAYS` entry of a day to the source of lib.rs
+pub fn register_day(lib: &str, day: usize) -> std::result::Result<String, String> {
+    lazy_static! {
+        static ref MODULE_RE: Regex = Regex::new(r"^pub mod day(\d+);$").expect("invalid module regex");
+        static ref ENTRY_RE: Regex = Regex::new(r"^\s*\((\d+), &Puzzle::<").expect("invalid entry regex");
+    }
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    let registered = lines.iter().filter_map(|line| MODULE_RE.captures(line)).any(|caps| caps[1] == day.to_string());
+    if registered {
+        return Err(format!("day {} is already registered", day))
+    }
+    // Entries come after the modules, so adding them first keeps the module positions valid
+    insert_sorted(&mut lines, &ENTRY_RE, day, format!("    ({}, &Puzzle::<day{}::Day{}>::new()),", day, day, day))
+        .ok_or("couldn't find the entries of DAYS")?;
+    insert_sorted(&mut lines, &MODULE_RE, day, format!("pub mod day{};", day))
+        .ok_or("couldn't find the day modules")?;
+    Ok(lines.join("\n") + "\n")
+}
+
+// Creates the solver, tests and inputs of a new day in the repository at `root`, and registers it in lib.rs.
+// Existing inputs are kept, e.g. if they were fetched first; returns the created files
+pub fn new_day<P: AsRef<Path>>(root: P, day: usize) -> Result<Vec<PathBuf>> {
+    let root = root.as_ref();
+    let lib_path = root.join("src").join("lib.rs");
+    let module_path = root.join("src").join(format!("day{}.rs", day));
+    let test_path = root.join("tests").join(format!("day{}.rs", day));
+    let input_path = root.join("inputs").join(format!("day{}.txt", day));
+    let example_path = root.join("inputs").join("examples").join(format!("day{}.txt", day));
+
+    // Everything is checked before writing anything, so that a failure leaves the repository untouched
+    let lib = fs::read_to_string(&lib_path).map_err(io_error(&lib_path))?;
+    let lib = register_day(&lib, day).map_err(|reason| Error::Io { source: lib_path.display().to_string(), reason })?;
+    for path in [&module_path, &test_path].iter() {
+        if path.exists() {
+            return Err(Error::Io { source: path.display().to_string(), reason: String::from("file already exists") })
+        }
+    }
+
+    let day_number = day.to_string();
+    let mut created = Vec::new();
+    for (path, contents) in [
+        (&module_path, MODULE_TEMPLATE.replace("{N}", &day_number)),
+        (&test_path, TEST_TEMPLATE.replace("{N}", &day_number)),
+        (&input_path, String::new()),
+        (&example_path, String::new()),
+    ].iter() {
+        if path.exists() {
+            continue
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error(path))?;
+        }
+        fs::write(path, contents).map_err(io_error(path))?;
+        created.push(path.to_path_buf());
+    }
+    fs::write(&lib_path, lib).map_err(io_error(&lib_path))?;
+    Ok(created)
+}
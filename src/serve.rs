@@ -0,0 +1,189 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+};
+use serde_json::{json, Map, Value};
+use tiny_http::{Header, Response};
+use super::{
+    config::Config,
+    error::{Error, Result},
+    file::Source,
+    progress::Budget,
+    solver::Part,
+    get_day,
+    DAYS,
+};
+
+// Serves the solvers over HTTP, with JSON responses:
+//
+// GET /days                  Available days
+// POST /days/{n}/parts/{p}   Solves a part for the input in the body; the query sets parameters of the day,
+//                            like --set, e.g. POST /days/9/parts/1?preamble=5
+//
+// Every clone shares the same listener, so that another thread can stop it
+#[derive(Clone)]
+pub struct Server {
+    server: Arc<tiny_http::Server>,
+}
+
+impl Server {
+    // e.g. "127.0.0.1:8020", or port 0 for any free port
+    pub fn bind(address: &str) -> Result<Self> {
+        let server = tiny_http::Server::http(address)
+            .map_err(|e| Error::Serve { address: String::from(address), reason: e.to_string() })?;
+        Ok(Server { server: Arc::new(server) })
+    }
+
+    pub fn address(&self) -> String {
+        self.server.server_addr().to_string()
+    }
+
+    // Makes `run` return once the requests that already came in are answered
+    pub fn stop(&self) {
+        self.server.unblock();
+    }
+
+    // Answers every request on its own thread until stopped; the config applies to every request,
+    // and the budget to every part. A solver that panics fails its own request, not the server
+    pub fn run(&self, config: &Config, budget: &Budget) {
+        thread::scope(|scope| {
+            while let Ok(mut request) = self.server.recv() {
+                scope.spawn(move || {
+                    let mut body = String::new();
+                    let (status, response) = match request.as_reader().read_to_string(&mut body) {
+                        Ok(_) => panic::catch_unwind(AssertUnwindSafe(|| handle(request.method().as_str(), request.url(), &body, config, budget)))
+                            .unwrap_or_else(|payload| (500, error_response("internal", &format!("error: the solver panicked: {}", panic_message(&*payload))))),
+                        Err(e) => (400, error_response("bad_request", &format!("error: couldn't read the body: {}", e))),
+                    };
+                    info!("{} {}: {}", request.method(), request.url(), status);
+                    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                        .expect("the header is valid");
+                    let response = Response::from_string(response.to_string()).with_status_code(status).with_header(content_type);
+                    if let Err(e) = request.respond(response) {
+                        warn!("Couldn't respond: {}", e);
+                    }
+                });
+            }
+        });
+    }
+}
+
+// Routes a request, returning the status code and the JSON to respond with
+pub fn handle(method: &str, url: &str, body: &str, config: &Config, budget: &Budget) -> (u16, Value) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => (200, json!({ "days": DAYS.iter().map(|(day, _)| *day).collect::<Vec<usize>>() })),
+        ("POST", ["days", day, "parts", part]) => solve(day, part, query, body, config, budget),
+        (_, ["days"]) | (_, ["days", _, "parts", _]) => {
+            (405, error_response("method_not_allowed", &format!("error: {} is not allowed on {}", method, path)))
+        },
+        _ => (404, error_response("not_found", &format!("error: no such endpoint {}", path))),
+    }
+}
+
+fn solve(day: &str, part: &str, query: &str, body: &str, config: &Config, budget: &Budget) -> (u16, Value) {
+    let (day, puzzle) = match day.parse::<usize>().ok().and_then(|day| Some((day, get_day(day)?))) {
+        Some(found) => found,
+        None => return (404, error_response("not_found", &format!("error: there is no day {}", day))),
+    };
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return (404, error_response("not_found", &format!("error: there is no part {}", part))),
+    };
+    let mut config = config.clone();
+    for assignment in query.split('&').filter(|assignment| !assignment.is_empty()) {
+        if let Err(Error::Config { reason, .. }) = config.set(&format!("day{}.{}", day, decode(assignment))) {
+            let e = Error::Config { source: format!("the query {:?}", assignment), reason };
+            return (400, json!({ "day": day, "part": part.number(), "error": error_json(&e) }))
+        }
+    }
+    let solution = Source::text("request", body).lines()
+        .and_then(|lines| puzzle.solve_within(lines, &[part], &config.params(day), budget));
+    let mut solution = match solution {
+        Ok(solution) => solution,
+        Err(e) => return (status(&e), json!({ "day": day, "part": part.number(), "error": error_json(&e) })),
+    };
+    let answer = solution.answers.remove(0);
+    let timings = json!({ "parse_us": solution.parse_time.as_micros(), "solve_us": answer.time.as_micros() });
+    match answer.result {
+        Ok(result) => (200, json!({
+            "day": day,
+            "part": part.number(),
+            "answer": result,
+            "type": answer.kind,
+            "timings": timings,
+            "extra": answer.extra.into_iter().map(|(key, value)| (String::from(key), value)).collect::<Map<String, Value>>(),
+        })),
+        Err(e) => (status(&e), json!({ "day": day, "part": part.number(), "error": error_json(&e), "timings": timings })),
+    }
+}
+
+// Errors of the input are the client's, while timeouts and cancellations are the server's
+fn status(e: &Error) -> u16 {
+    match e {
+        Error::Parse { .. } | Error::Solve(_) => 422,
+        Error::Config { .. } => 400,
+        Error::Timeout(_) => 504,
+        Error::Cancelled => 503,
+        Error::Io { .. } | Error::Http { .. } | Error::Serve { .. } => 500,
+    }
+}
+
+// The message is formatted like on the command line; parse errors also have their position in the body
+fn error_json(e: &Error) -> Value {
+    let kind = match e {
+        Error::Io { .. } => "io",
+        Error::Parse { .. } => "parse",
+        Error::Solve(_) => "solve",
+        Error::Http { .. } => "http",
+        Error::Config { .. } => "config",
+        Error::Serve { .. } => "serve",
+        Error::Timeout(_) => "timeout",
+        Error::Cancelled => "cancelled",
+    };
+    let mut error = json!({ "kind": kind, "message": e.to_string() });
+    if let Error::Parse { line, column, text, reason, .. } = e {
+        error["line"] = json!(line);
+        error["column"] = json!(column);
+        error["text"] = json!(text);
+        error["reason"] = json!(reason);
+    }
+    error
+}
+
+fn error_response(kind: &str, message: &str) -> Value {
+    json!({ "error": { "kind": kind, "message": message } })
+}
+
+// Panics mostly carry a formatted message, or a literal one
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload.downcast_ref::<String>().map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or("no message")
+}
+
+// Query strings are percent-encoded, with "+" for spaces
+fn decode(text: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail.get(..2)
+            .filter(|_| byte == b'%')
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            },
+            None => {
+                bytes.push(if byte == b'+' { b' ' } else { byte });
+                rest = tail;
+            },
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
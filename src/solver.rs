@@ -0,0 +1,178 @@
+use std::{
+    any,
+    fmt::Display,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use toml::value::Table;
+use super::{
+    error::{Error, Result},
+    file::Lines,
+    progress::{Budget, Progress},
+};
+
+// Every day parses its input once, then solves both parts from the parsed value
+pub trait Solver {
+    type Input;
+    // Values from the puzzle text that can be changed in the config file, with the puzzle's values as defaults;
+    // NoParams for days without any
+    type Params: DeserializeOwned + Default;
+    type Output1: Output;
+    type Output2: Output;
+
+    fn parse(lines: Lines) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Output1>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2>;
+
+    // Parts with parameters override these, and so do long-running parts, to report their progress and stop
+    // when they run out of time; other parts always run to completion with the puzzle's values
+    fn part1_with(input: &Self::Input, _params: &Self::Params, _progress: &Progress) -> Result<Self::Output1> {
+        Self::part1(input)
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Self::Params, _progress: &Progress) -> Result<Self::Output2> {
+        Self::part2(input)
+    }
+}
+
+// Rejects every key, e.g. "unknown field `preamble`, there are no fields"
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoParams {}
+
+// An answer is displayed as text, and may carry values found along the way that are worth reporting
+pub trait Output: Display {
+    // Type of the answer in machine-readable output
+    const KIND: &'static str;
+
+    fn extra(&self) -> Vec<(&'static str, Value)> {
+        Vec::new()
+    }
+}
+
+macro_rules! integer_output {
+    ($($t:ty),*) => {
+        $(impl Output for $t {
+            const KIND: &'static str = "integer";
+        })*
+    };
+}
+
+integer_output!(i32, i64, isize, u32, u64, usize);
+
+impl Output for String {
+    const KIND: &'static str = "string";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Answer {
+    pub part: Part,
+    pub result: Result<String>,
+    pub kind: &'static str,
+    // Empty if the part failed
+    pub extra: Vec<(&'static str, Value)>,
+    pub time: Duration,
+}
+
+// Answers for the requested parts, with the time spent on each phase
+#[derive(Debug)]
+pub struct Solution {
+    pub parse_time: Duration,
+    pub answers: Vec<Answer>,
+}
+
+// Type-erased wrapper, so that days with different input and output types can live in the same list
+pub trait Day: Sync {
+    // Fails if the input can't be parsed; otherwise each part fails or succeeds on its own
+    fn solve(&self, lines: Lines, parts: &[Part]) -> Result<Solution> {
+        self.solve_within(lines, parts, &Table::new(), &Budget::unlimited())
+    }
+
+    // Same, but with parameters from the config file, and long-running parts fail with a timeout or cancellation
+    // when they exceed the budget
+    fn solve_within(&self, lines: Lines, parts: &[Part], params: &Table, budget: &Budget) -> Result<Solution>;
+
+    // Whether the parameters are valid for this day, e.g. with no unknown keys
+    fn check_params(&self, params: &Table) -> std::result::Result<(), String>;
+}
+
+pub struct Puzzle<S: Solver>(PhantomData<S>);
+
+impl<S: Solver> Puzzle<S> {
+    pub const fn new() -> Self {
+        Puzzle(PhantomData)
+    }
+}
+
+impl<S: Solver> Default for Puzzle<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn describe<O: Output>(result: Result<O>) -> (Result<String>, &'static str, Vec<(&'static str, Value)>) {
+    match result {
+        Ok(answer) => {
+            let extra = answer.extra();
+            (Ok(answer.to_string()), O::KIND, extra)
+        },
+        Err(e) => (Err(e), O::KIND, Vec::new()),
+    }
+}
+
+// Days without configured parameters use their defaults, even if they have no parameters at all
+fn read_params<P: DeserializeOwned + Default>(params: &Table) -> std::result::Result<P, String> {
+    if params.is_empty() {
+        return Ok(P::default())
+    }
+    toml::Value::Table(params.clone()).try_into().map_err(|e| e.to_string())
+}
+
+impl<S: Solver + Sync> Day for Puzzle<S> {
+    fn solve_within(&self, lines: Lines, parts: &[Part], params: &Table, budget: &Budget) -> Result<Solution> {
+        // E.g. "Day7", from "rust_aoc_2020::day7::Day7"
+        let name = any::type_name::<S>().rsplit("::").next().unwrap_or("solver");
+        let params: S::Params = read_params(params).map_err(|reason| Error::Config { source: format!("the parameters of {}", name), reason })?;
+        info!("{}: parsing {}", name, lines.source());
+        let start = Instant::now();
+        let input = S::parse(lines)?;
+        let parse_time = start.elapsed();
+        info!("{}: parsed in {:?}", name, parse_time);
+        let answers = parts.iter().map(|part| {
+            let progress = budget.start(&format!("{} part {}", name, part.number()));
+            let start = Instant::now();
+            let (result, kind, extra) = match part {
+                Part::One => describe(S::part1_with(&input, &params, &progress)),
+                Part::Two => describe(S::part2_with(&input, &params, &progress)),
+            };
+            let time = start.elapsed();
+            match &result {
+                Ok(_) => info!("{}: solved part {} in {:?}", name, part.number(), time),
+                Err(_) => info!("{}: part {} failed after {:?}", name, part.number(), time),
+            }
+            Answer { part: *part, result, kind, extra, time }
+        }).collect();
+        Ok(Solution { parse_time, answers })
+    }
+
+    fn check_params(&self, params: &Table) -> std::result::Result<(), String> {
+        read_params::<S::Params>(params).map(|_| ())
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use toml::value::Table;
+use super::{
+    config::Config,
+    error::Result,
+    report::Record,
+};
+
+// The checked in examples of a day, e.g. inputs/examples/day10.txt and inputs/examples/day10-larger.txt
+pub fn example_paths<P: AsRef<Path>>(examples_dir: P, day: usize) -> Vec<PathBuf> {
+    let (exact, prefix) = (format!("day{}.txt", day), format!("day{}-", day));
+    let mut paths: Vec<PathBuf> = match fs::read_dir(examples_dir) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect(),
+        Err(_) => Vec::new(),
+    };
+    paths.retain(|path| match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => name == exact || (name.starts_with(&prefix) && name.ends_with(".txt")),
+        None => false,
+    });
+    paths.sort();
+    paths
+}
+
+// Files to watch for a day, with the parameters each one is solved with: the input uses those of `config`,
+// and the examples those of the params.toml next to them, since e.g. the example of day 9 has a preamble of 5
+pub fn watched_files<P: AsRef<Path>>(day: usize, input: PathBuf, config: &Config, examples_dir: P) -> Result<BTreeMap<PathBuf, Table>> {
+    let examples_config = Config::load(examples_dir.as_ref().join("params.toml"))?;
+    let mut files = BTreeMap::new();
+    files.insert(input, config.params(day));
+    for path in example_paths(&examples_dir, day) {
+        files.insert(path, examples_config.params(day));
+    }
+    Ok(files)
+}
+
+// Modification time and size, which changes even when the file system only keeps whole seconds;
+// None while the file doesn't exist
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+// Polls files for changes; there is no file system notification, so calling `changed` is what checks them
+#[derive(Debug)]
+pub struct Watcher {
+    stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+impl Watcher {
+    // Every file counts as changed on the first check
+    pub fn new(paths: &[PathBuf]) -> Self {
+        Watcher { stamps: paths.iter().map(|path| (path.clone(), None)).collect() }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item=&PathBuf> {
+        self.stamps.keys()
+    }
+
+    // Files that changed since the last check; files that were deleted aren't reported until they're back
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in self.stamps.iter_mut() {
+            let current = stamp(path);
+            if current != *last {
+                debug!("{} changed from {:?} to {:?}", path.display(), last, current);
+                *last = current;
+                if current.is_some() {
+                    changed.push(path.clone());
+                }
+            }
+        }
+        changed
+    }
+}
+
+// A line per part of an input, comparing the records of the latest run with those of the previous run of
+// the same input, if any. Answers only show what they were if they changed, and timings show how much
+// faster or slower they got, e.g. "inputs/day9.txt, part 1: 127 (was 130), parse 212µs (-4%), solve 31µs (+10%)"
+pub fn diff(previous: &[Record], current: &[Record]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (i, record) in current.iter().enumerate() {
+        // Inputs that can't be read or parsed fail every part the same way
+        let same_as = current[..i].iter().find(|r| r.input == record.input && r.answer.is_none() && r.error == record.error);
+        let before = previous.iter().find(|r| r.input == record.input && r.part == record.part);
+        let mut line = format!("{}, part {}: ", record.input, record.part);
+        // Errors take several lines with their context, so they go after the timings, unless they're the same
+        let mut error = None;
+        match &record.answer {
+            Some(answer) => {
+                line += answer;
+                match before.map(|r| &r.answer) {
+                    Some(Some(was)) if was != answer => line += &format!(" (was {})", was),
+                    Some(None) => line += " (fixed)",
+                    _ => (),
+                }
+            },
+            None if before.is_some_and(|r| r.answer.is_none() && r.error == record.error) => line += "same error",
+            None if same_as.is_some() => line += &format!("same error as part {}", same_as.expect("just checked").part),
+            None => {
+                line += "ERROR";
+                error = record.error.clone();
+            },
+        }
+        let timing = |name: &str, now: Option<u128>, was: Option<u128>| match (now, was) {
+            (Some(now), Some(was)) if was > 0 => {
+                let change = (now as f64 - was as f64) * 100.0 / was as f64;
+                format!(", {} {}µs ({:+.0}%)", name, now, change)
+            },
+            (Some(now), _) => format!(", {} {}µs", name, now),
+            (None, _) => String::new(),
+        };
+        line += &timing("parse", record.timings.parse_us, before.and_then(|r| r.timings.parse_us));
+        line += &timing("solve", record.timings.solve_us, before.and_then(|r| r.timings.solve_us));
+        lines.push(line);
+        lines.extend(error);
+    }
+    lines
+}
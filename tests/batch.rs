@@ -0,0 +1,95 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+use rust_aoc_2020::{
+    batch::{self, Cell},
+    config::Config,
+    progress::Budget,
+    solver::Part,
+};
+
+// inputs/day1.txt, inputs/alice/day7.txt, inputs/bob/day7.txt and inputs/bob/day16.txt, from the examples
+fn input_dir(name: &str) -> PathBuf {
+    let dir = common::temp_dir(&format!("batch-{}", name)).join("inputs");
+    let copy = |example: &str, to: &str| {
+        let to = dir.join(to);
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::copy(PathBuf::from(common::example(example).name()), to).unwrap();
+    };
+    copy("day1", "day1.txt");
+    copy("day7", "alice/day7.txt");
+    copy("day7-part2", "bob/day7.txt");
+    copy("day16", "bob/day16.txt");
+    fs::write(dir.join("bob").join("notes.txt"), "not an input").unwrap();
+    fs::create_dir_all(dir.join("carol")).unwrap();
+    dir
+}
+
+#[test]
+fn sets_are_found_in_subdirectories() {
+    let dir = input_dir("find");
+    let sets = batch::find_input_sets(&dir, &[1, 7, 16]).unwrap();
+    let only_day7 = batch::find_input_sets(&dir, &[7]).unwrap();
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    assert_eq!(sets.iter().map(|set| set.name.as_str()).collect::<Vec<_>>(), vec!["inputs", "alice", "bob"]);
+    assert_eq!(sets[2].inputs.keys().copied().collect::<Vec<_>>(), vec![7, 16]);
+    assert_eq!(only_day7.iter().map(|set| set.name.as_str()).collect::<Vec<_>>(), vec!["alice", "bob"]);
+}
+
+#[test]
+fn answers_are_side_by_side() {
+    let dir = input_dir("run");
+    let sets = batch::find_input_sets(&dir, &[1, 7, 16]).unwrap();
+    let mut finished = 0;
+    let matrix = batch::run(&sets, &[1, 7, 16], &Config::default(), 2, &Budget::unlimited(), |_| finished += 1);
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    assert_eq!(finished, 4);
+    assert_eq!(matrix.get(7, Part::Two, "alice"), Some(&Cell::Answer(String::from("32"))));
+    assert_eq!(matrix.get(7, Part::Two, "bob"), Some(&Cell::Answer(String::from("126"))));
+    assert_eq!(matrix.get(7, Part::One, "inputs"), Some(&Cell::Missing));
+    assert_eq!(matrix.get(1, Part::One, "inputs"), Some(&Cell::Answer(String::from("514579"))));
+    assert_eq!(matrix.get(9, Part::One, "inputs"), None);
+    // The first example of day 16 has no departure fields
+    let failures = matrix.failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!((failures[0].0, failures[0].1, failures[0].2), (16, Part::Two, "bob"));
+    assert_eq!(matrix.to_string().lines().next(), Some("Day  Part  inputs     alice  bob"));
+    assert!(matrix.to_string().contains("\n 16     2  -          -      ERROR\n"), "{}", matrix);
+    let report = matrix.report();
+    assert!(report.starts_with(&matrix.to_string()), "{}", report);
+    assert!(report.ends_with(&format!("\n\nDay 16, part 2 (bob):\n{}\n", failures[0].3)), "{}", report);
+}
+
+#[test]
+fn directories_without_inputs_are_an_error() {
+    let dir = input_dir("empty");
+    let only_bob = batch::run_dir(&dir, &[16], &Config::default(), 1, &Budget::unlimited(), |_| ()).unwrap();
+    let empty = batch::run_dir(dir.join("carol"), &[1, 7, 16], &Config::default(), 1, &Budget::unlimited(), |_| ());
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    assert_eq!(only_bob.sets, vec!["bob"]);
+    let e = empty.unwrap_err().to_string();
+    assert!(e.starts_with("error: no inputs for the given days in "), "{}", e);
+    assert!(e.ends_with("carol"), "{}", e);
+}
+
+// The example of day 16 has no departure fields, so it would fail as a set
+#[test]
+fn examples_are_not_an_input_set() {
+    let dir = common::temp_dir("batch-examples").join("inputs");
+    for (example, to) in [("day7", "day7.txt"), ("day7-part2", "alice/day7.txt"), ("day16", "examples/day16.txt"), ("day7", "examples/day7.txt")].iter() {
+        let to = dir.join(to);
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::copy(PathBuf::from(common::example(example).name()), to).unwrap();
+    }
+    let matrix = batch::run_dir(&dir, &[7, 16], &Config::default(), 2, &Budget::unlimited(), |_| ()).unwrap();
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    assert_eq!(matrix.sets, vec!["inputs", "alice"]);
+    assert!(matrix.failures().is_empty(), "{}", matrix.report());
+    assert_eq!(matrix.get(7, Part::Two, "alice"), Some(&Cell::Answer(String::from("126"))));
+}
+
+#[test]
+fn missing_directories_are_reported() {
+    let e = batch::find_input_sets(common::temp_dir("batch-missing"), &[1]).unwrap_err();
+    assert!(e.to_string().contains("rust-aoc-2020-batch-missing"), "{}", e);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::fs;
+use rust_aoc_2020::{bench, config::Config, get_day, solver::Part};
+use toml::value::Table;
+
+#[test]
+fn bench_day_times_every_phase() {
+    let lines = common::example("day8").lines().unwrap();
+    let result = bench::bench_day(8, get_day(8).unwrap(), &lines, &Table::new(), 3).unwrap();
+    assert_eq!(result.iterations, 3);
+    assert_eq!(result.parts.iter().map(|(part, _)| *part).collect::<Vec<_>>(), vec![Part::One, Part::Two]);
+    for (_, stats) in result.phases() {
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+    }
+}
+
+#[test]
+fn bench_day_fails_on_unsolvable_input() {
+    // No two entries sum to 2020
+    let lines = common::lines("1\n2\n3");
+    assert!(bench::bench_day(1, get_day(1).unwrap(), &lines, &Table::new(), 1).is_err());
+}
+
+// The example of day 9 only has a solution with a preamble of 5
+#[test]
+fn bench_day_uses_the_parameters() {
+    let lines = common::example("day9").lines().unwrap();
+    let mut config = Config::default();
+    assert!(bench::bench_day(9, get_day(9).unwrap(), &lines, &config.params(9), 1).is_err());
+    config.set("day9.preamble=5").unwrap();
+    assert!(bench::bench_day(9, get_day(9).unwrap(), &lines, &config.params(9), 1).is_ok());
+}
+
+#[test]
+fn results_file_has_a_row_per_phase() {
+    let lines = common::example("day6").lines().unwrap();
+    let result = bench::bench_day(6, get_day(6).unwrap(), &lines, &Table::new(), 1).unwrap();
+    let path = common::temp_path("bench", "txt");
+    bench::write_results(&path, &[result]).unwrap();
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    let rows: Vec<&str> = contents.lines().collect();
+    assert_eq!(rows[0], "day\tphase\titerations\tmin_us\tmedian_us\tmax_us");
+    assert!(rows[1].starts_with("6\tparse\t1\t"));
+    assert!(rows[2].starts_with("6\tpart1\t1\t"));
+    assert!(rows[3].starts_with("6\tpart2\t1\t"));
+}
@@ -0,0 +1,66 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+use tiny_http::{Response, Server};
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+// Local stand-in for the puzzle server, which answers every request with the given handler until dropped
+pub struct MockServer {
+    pub base_url: String,
+    server: Arc<Server>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start<F>(handler: F) -> Self where
+        F: Fn(&RecordedRequest) -> (u16, String) + Send + 'static
+    {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handle = {
+            let server = Arc::clone(&server);
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                while let Ok(mut request) = server.recv() {
+                    let mut body = String::new();
+                    request.as_reader().read_to_string(&mut body).unwrap();
+                    let recorded = RecordedRequest {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        cookie: request.headers().iter()
+                            .find(|h| h.field.equiv("Cookie"))
+                            .map(|h| h.value.to_string()),
+                        body,
+                    };
+                    let (status, response) = handler(&recorded);
+                    requests.lock().unwrap().push(recorded);
+                    let _ = request.respond(Response::from_string(response).with_status_code(status));
+                }
+            })
+        };
+        MockServer { base_url, server, requests, handle: Some(handle) }
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
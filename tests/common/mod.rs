@@ -0,0 +1,36 @@
+#![allow(dead_code)]
+
+pub mod mock_server;
+
+use std::{env, fs, path::PathBuf, process};
+use rust_aoc_2020::{
+    file::{Lines, Source},
+    solver::Solver,
+};
+
+// Puzzle examples are checked in as inputs/examples/dayN.txt, with a suffix for additional examples
+pub fn example(name: &str) -> Source {
+    Source::File(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("inputs").join("examples").join(format!("{}.txt", name)))
+}
+
+pub fn parse<S: Solver>(name: &str) -> S::Input {
+    let lines = example(name).lines().unwrap_or_else(|e| panic!("{}", e));
+    S::parse(lines).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// Files written by tests go to the system's temporary directory, named after this process so that runs don't clash
+pub fn temp_path(name: &str, extension: &str) -> PathBuf {
+    env::temp_dir().join(format!("rust-aoc-2020-{}-{}.{}", name, process::id(), extension))
+}
+
+// Doesn't exist yet, even if an earlier run left it behind
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("rust-aoc-2020-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+// For examples that are given inline in the puzzle text
+pub fn lines(text: &str) -> Lines {
+    Source::text("example", text).lines().unwrap()
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use std::fs;
+use rust_aoc_2020::{
+    config::Config,
+    day16::{self, Day16},
+    day17::{self, Day17},
+    day7::{self, Day7},
+    error::Error,
+    get_day,
+    progress::{Budget, Progress},
+    solver::{Part, Solver},
+};
+
+fn load(name: &str, text: &str) -> Result<Config, Error> {
+    let path = common::temp_path(&format!("params-{}", name), "toml");
+    fs::write(&path, text).unwrap();
+    let config = Config::load(&path);
+    fs::remove_file(&path).unwrap();
+    config
+}
+
+fn answers(config: &Config, day: usize) -> Vec<String> {
+    let lines = common::example(&format!("day{}", day)).lines().unwrap();
+    let solution = get_day(day).unwrap().solve_within(lines, &[Part::One, Part::Two], &config.params(day), &Budget::unlimited()).unwrap();
+    solution.answers.into_iter().map(|answer| answer.result.unwrap()).collect()
+}
+
+#[test]
+fn missing_file_keeps_the_defaults() {
+    let config = Config::load(common::temp_path("params-missing", "toml")).unwrap();
+    assert!(config.params(9).is_empty());
+}
+
+// The example uses a preamble of 5 numbers instead of 25
+#[test]
+fn parameters_come_from_the_file() {
+    let config = load("file", "[day9]\npreamble = 5\n").unwrap();
+    assert_eq!(answers(&config, 9), vec!["127", "62"]);
+}
+
+#[test]
+fn set_overrides_the_file() {
+    let mut config = load("set", "[day1]\ntarget = 1000\n").unwrap();
+    config.set("day1.target=2020").unwrap();
+    config.set("day9.preamble = 5").unwrap();
+    assert_eq!(answers(&config, 1), vec!["514579", "241861950"]);
+    assert_eq!(answers(&config, 9), vec!["127", "62"]);
+}
+
+// Values that aren't valid TOML are read as strings
+#[test]
+fn set_falls_back_to_strings() {
+    let mut config = Config::default();
+    config.set("day7.color=dark olive").unwrap();
+    config.set("day7.color=\"dark olive\"").unwrap();
+    assert_eq!(config.params(7)["color"].as_str(), Some("dark olive"));
+    let input = common::parse::<Day7>("day7");
+    let params = day7::Params { color: String::from("dark olive") };
+    assert_eq!(Day7::part2_with(&input, &params, &Progress::unlimited()).unwrap(), 7);
+}
+
+#[test]
+fn unknown_parameters_are_rejected() {
+    let e = load("unknown", "[day9]\npreamble = 5\nwindow = 3\n").unwrap_err();
+    assert!(e.to_string().contains("day9: unknown field `window`"), "{}", e);
+    let e = Config::default().set("day9.preamble=five").unwrap_err();
+    assert!(e.to_string().starts_with("error: invalid parameters in --set day9.preamble=five: day9:"), "{}", e);
+    let e = Config::default().set("day2.policy=1").unwrap_err();
+    assert!(e.to_string().contains("day2: unknown field `policy`"), "{}", e);
+}
+
+#[test]
+fn unknown_days_are_rejected() {
+    let e = load("days", "[day30]\ncycles = 1\n").unwrap_err();
+    assert!(e.to_string().ends_with("there is no day 30"), "{}", e);
+    let e = load("tables", "[nine]\npreamble = 5\n").unwrap_err();
+    assert!(e.to_string().ends_with("invalid table [nine], expected a day like [day1]"), "{}", e);
+    let e = Config::default().set("day9=5").unwrap_err();
+    assert!(e.to_string().contains("expected a key like \"day9.preamble\""), "{}", e);
+}
+
+#[test]
+fn other_magic_numbers() {
+    let input = common::parse::<Day17>("day17");
+    assert_eq!(Day17::part1_with(&input, &day17::Params { cycles: 0 }, &Progress::unlimited()).unwrap(), 5);
+    let input = common::parse::<Day16>("day16-part2");
+    let params = day16::Params { field_prefix: String::from("ro"), field_count: 1 };
+    assert_eq!(Day16::part2_with(&input, &params, &Progress::unlimited()).unwrap(), 11);
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use rust_aoc_2020::{day1::Day1, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day1>("day1");
+    assert_eq!(Day1::part1(&input).unwrap(), 514579);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day1>("day1");
+    assert_eq!(Day1::part2(&input).unwrap(), 241861950);
+}
+
+#[test]
+fn part2_does_not_reuse_entries() {
+    // 1000 + 1000 + 20 would sum to 2020 if the same entry could be used twice
+    let input = common::parse::<Day1>("day1-repeated-entry");
+    assert_eq!(Day1::part2(&input).unwrap(), 1000 * 3 * 1017);
+}
+
+#[test]
+fn too_few_entries_are_reported() {
+    for text in &["", "5"] {
+        let input = Day1::parse(common::lines(text)).unwrap();
+        assert_eq!(Day1::part1(&input).unwrap_err().to_string(), "error: no two entries sum to 2020");
+        assert_eq!(Day1::part2(&input).unwrap_err().to_string(), "error: no three entries sum to 2020");
+    }
+}
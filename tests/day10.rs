@@ -0,0 +1,27 @@
+mod common;
+
+use rust_aoc_2020::{day10::Day10, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day10>("day10");
+    assert_eq!(Day10::part1(&input).unwrap(), 7 * 5);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day10>("day10");
+    assert_eq!(Day10::part2(&input).unwrap(), 8);
+}
+
+#[test]
+fn part1_larger_example() {
+    let input = common::parse::<Day10>("day10-larger");
+    assert_eq!(Day10::part1(&input).unwrap(), 22 * 10);
+}
+
+#[test]
+fn part2_larger_example() {
+    let input = common::parse::<Day10>("day10-larger");
+    assert_eq!(Day10::part2(&input).unwrap(), 19208);
+}
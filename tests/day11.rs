@@ -0,0 +1,19 @@
+mod common;
+
+use rust_aoc_2020::{day11::Day11, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day11>("day11");
+    let stable = Day11::part1(&input).unwrap();
+    assert_eq!(stable.occupied_seats, 37);
+    assert_eq!(stable.iterations, 5);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day11>("day11");
+    let stable = Day11::part2(&input).unwrap();
+    assert_eq!(stable.occupied_seats, 26);
+    assert_eq!(stable.iterations, 6);
+}
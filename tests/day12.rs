@@ -0,0 +1,15 @@
+mod common;
+
+use rust_aoc_2020::{day12::Day12, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day12>("day12");
+    assert_eq!(Day12::part1(&input).unwrap(), 25);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day12>("day12");
+    assert_eq!(Day12::part2(&input).unwrap(), 286);
+}
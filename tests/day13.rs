@@ -0,0 +1,38 @@
+mod common;
+
+use rust_aoc_2020::{day13::{self, Day13}, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day13>("day13");
+    assert_eq!(Day13::part1(&input).unwrap(), 295);
+}
+
+// The examples' answers are smaller than the 100000000000000 lower bound used for the real input
+#[test]
+fn part2_example() {
+    let (_, bus_id_list) = common::parse::<Day13>("day13");
+    assert_eq!(day13::find_sequential_bus_offsets(&bus_id_list).unwrap(), 1068781);
+}
+
+#[test]
+fn part2_other_examples() {
+    let examples = [
+        ("17,x,13,19", 3417),
+        ("67,7,59,61", 754018),
+        ("67,x,7,59,61", 779210),
+        ("67,7,x,59,61", 1261476),
+        ("1789,37,47,1889", 1202161486),
+    ];
+    for (bus_ids, expected) in examples.iter() {
+        let (_, bus_id_list) = Day13::parse(common::lines(&format!("0\n{}", bus_ids))).unwrap();
+        assert_eq!(day13::find_sequential_bus_offsets(&bus_id_list).unwrap(), *expected, "{}", bus_ids);
+    }
+}
+
+// The bus with the largest ID sits further into the schedule than its own ID
+#[test]
+fn part2_late_bus_from_zero() {
+    let (_, bus_id_list) = Day13::parse(common::lines("0\nx,x,x,x,x,x,x,2")).unwrap();
+    assert_eq!(day13::find_sequential_bus_offsets(&bus_id_list).unwrap(), 1);
+}
@@ -0,0 +1,16 @@
+mod common;
+
+use rust_aoc_2020::{day14::Day14, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day14>("day14");
+    assert_eq!(Day14::part1(&input).unwrap(), 165);
+}
+
+// The first example has too many floating bits for part 2
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day14>("day14-part2");
+    assert_eq!(Day14::part2(&input).unwrap(), 208);
+}
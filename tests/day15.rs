@@ -0,0 +1,31 @@
+mod common;
+
+use rust_aoc_2020::{day15::Day15, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day15>("day15");
+    assert_eq!(Day15::part1(&input).unwrap(), 436);
+}
+
+#[test]
+fn part1_other_examples() {
+    let examples = [
+        ("1,3,2", 1),
+        ("2,1,3", 10),
+        ("1,2,3", 27),
+        ("2,3,1", 78),
+        ("3,2,1", 438),
+        ("3,1,2", 1836),
+    ];
+    for (starting_numbers, expected) in examples.iter() {
+        let input = Day15::parse(common::lines(starting_numbers)).unwrap();
+        assert_eq!(Day15::part1(&input).unwrap(), *expected, "{}", starting_numbers);
+    }
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day15>("day15");
+    assert_eq!(Day15::part2(&input).unwrap(), 175594);
+}
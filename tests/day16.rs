@@ -0,0 +1,22 @@
+mod common;
+
+use rust_aoc_2020::{day16::{self, Day16}, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day16>("day16");
+    assert_eq!(Day16::part1(&input).unwrap(), 71);
+}
+
+// The example has no departure fields, so check the field order instead
+#[test]
+fn part2_example() {
+    let (fields, own_ticket, nearby_tickets) = common::parse::<Day16>("day16-part2");
+    let valid_tickets = day16::get_valid_tickets(&fields, &nearby_tickets);
+    let field_order: Vec<(&str, usize)> = day16::get_correct_field_order(&fields, &valid_tickets).unwrap()
+        .iter()
+        .zip(own_ticket.iter())
+        .map(|(field, value)| (field.name.as_str(), *value))
+        .collect();
+    assert_eq!(field_order, vec![("row", 11), ("class", 12), ("seat", 13)]);
+}
@@ -0,0 +1,15 @@
+mod common;
+
+use rust_aoc_2020::{day17::Day17, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day17>("day17");
+    assert_eq!(Day17::part1(&input).unwrap(), 112);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day17>("day17");
+    assert_eq!(Day17::part2(&input).unwrap(), 848);
+}
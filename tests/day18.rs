@@ -0,0 +1,24 @@
+mod common;
+
+use rust_aoc_2020::{day18::{self, Day18, Precedence}, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day18>("day18");
+    assert_eq!(Day18::part1(&input).unwrap(), 71 + 51 + 26 + 437 + 12240 + 13632);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day18>("day18");
+    assert_eq!(Day18::part2(&input).unwrap(), 231 + 51 + 46 + 1445 + 669060 + 23340);
+}
+
+#[test]
+fn each_expression() {
+    let input = common::parse::<Day18>("day18");
+    let left_to_right: Vec<usize> = input.iter().map(|e| day18::evaluate_expression(e, &Precedence::LeftToRight).unwrap()).collect();
+    let addition_first: Vec<usize> = input.iter().map(|e| day18::evaluate_expression(e, &Precedence::AdditionFirst).unwrap()).collect();
+    assert_eq!(left_to_right, vec![71, 51, 26, 437, 12240, 13632]);
+    assert_eq!(addition_first, vec![231, 51, 46, 1445, 669060, 23340]);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use rust_aoc_2020::{day19::Day19, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day19>("day19");
+    assert_eq!(Day19::part1(&input).unwrap(), 2);
+}
+
+// The first example has no rules 42 and 31 to loop over
+#[test]
+fn part1_looping_example() {
+    let input = common::parse::<Day19>("day19-part2");
+    assert_eq!(Day19::part1(&input).unwrap(), 3);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day19>("day19-part2");
+    assert_eq!(Day19::part2(&input).unwrap(), 12);
+}
+
+#[test]
+fn part1_self_referencing_rule_is_reported() {
+    let input = Day19::parse(common::lines("0: 1 0 | 1\n1: \"a\"\n\naa")).unwrap();
+    assert_eq!(Day19::part1(&input).unwrap_err().to_string(), "error: rule 0 refers back to itself, which part 1 can't match");
+}
+
+// Rule 3 refers to a single rule, which the CYK check of part 2 can't use
+#[test]
+fn part2_rule_that_is_not_normalized_is_reported() {
+    let rules = "0: 8 11\n1: \"a\"\n2: \"b\"\n3: 1\n8: 42\n11: 42 31\n31: 2 1 | 1 2\n42: 1 2 | 2 1";
+    let input = Day19::parse(common::lines(&format!("{}\n\nabab", rules))).unwrap();
+    assert_eq!(Day19::part2(&input).unwrap_err().to_string(), "error: rule 3 is not a pair of rules or a character after normalization");
+}
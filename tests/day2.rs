@@ -0,0 +1,15 @@
+mod common;
+
+use rust_aoc_2020::{day2::Day2, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day2>("day2");
+    assert_eq!(Day2::part1(&input).unwrap(), 2);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day2>("day2");
+    assert_eq!(Day2::part2(&input).unwrap(), 1);
+}
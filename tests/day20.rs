@@ -0,0 +1,21 @@
+mod common;
+
+use rust_aoc_2020::{day20::Day20, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day20>("day20");
+    assert_eq!(Day20::part1(&input).unwrap(), 1951 * 3079 * 2971 * 1171);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day20>("day20");
+    assert_eq!(Day20::part2(&input).unwrap(), 273);
+}
+
+#[test]
+fn empty_input_is_reported() {
+    let input = Day20::parse(common::lines("")).unwrap();
+    assert_eq!(Day20::part2(&input).unwrap_err().to_string(), "error: there are no cameras to arrange");
+}
@@ -0,0 +1,22 @@
+mod common;
+
+use rust_aoc_2020::{day3::{self, Day3}, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day3>("day3");
+    assert_eq!(Day3::part1(&input).unwrap(), 7);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day3>("day3");
+    assert_eq!(Day3::part2(&input).unwrap(), 336);
+}
+
+#[test]
+fn trees_per_slope() {
+    let input = common::parse::<Day3>("day3");
+    let trees: Vec<usize> = day3::SLOPE_LIST.iter().map(|(right, down)| day3::count_trees(&input, *right, *down)).collect();
+    assert_eq!(trees, vec![2, 7, 3, 4, 2]);
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use rust_aoc_2020::{day4::Day4, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day4>("day4");
+    assert_eq!(Day4::part1(&input).unwrap(), 2);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day4>("day4");
+    assert_eq!(Day4::part2(&input).unwrap(), 2);
+}
+
+#[test]
+fn part2_invalid_passports() {
+    let input = common::parse::<Day4>("day4-invalid");
+    assert_eq!(Day4::part2(&input).unwrap(), 0);
+}
+
+#[test]
+fn part2_valid_passports() {
+    let input = common::parse::<Day4>("day4-valid");
+    assert_eq!(Day4::part2(&input).unwrap(), 4);
+}
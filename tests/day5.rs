@@ -0,0 +1,25 @@
+mod common;
+
+use std::convert::TryFrom;
+use rust_aoc_2020::{day5::{Day5, Seat}, solver::Solver};
+
+#[test]
+fn seat_positions() {
+    let seats: Vec<(usize, usize, usize)> = common::lines("BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL")
+        .map(|l| Seat::try_from(&l).unwrap())
+        .map(|s| (s.row(), s.column(), s.id))
+        .collect();
+    assert_eq!(seats, vec![(70, 7, 567), (14, 7, 119), (102, 4, 820)]);
+}
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day5>("day5");
+    assert_eq!(Day5::part1(&input).unwrap(), 820);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day5>("day5-part2");
+    assert_eq!(Day5::part2(&input).unwrap(), 2);
+}
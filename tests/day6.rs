@@ -0,0 +1,15 @@
+mod common;
+
+use rust_aoc_2020::{day6::Day6, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day6>("day6");
+    assert_eq!(Day6::part1(&input).unwrap(), 11);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day6>("day6");
+    assert_eq!(Day6::part2(&input).unwrap(), 6);
+}
@@ -0,0 +1,21 @@
+mod common;
+
+use rust_aoc_2020::{day7::Day7, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day7>("day7");
+    assert_eq!(Day7::part1(&input).unwrap(), 4);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day7>("day7");
+    assert_eq!(Day7::part2(&input).unwrap(), 32);
+}
+
+#[test]
+fn part2_nested_example() {
+    let input = common::parse::<Day7>("day7-part2");
+    assert_eq!(Day7::part2(&input).unwrap(), 126);
+}
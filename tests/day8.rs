@@ -0,0 +1,15 @@
+mod common;
+
+use rust_aoc_2020::{day8::Day8, solver::Solver};
+
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day8>("day8");
+    assert_eq!(Day8::part1(&input).unwrap(), 5);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day8>("day8");
+    assert_eq!(Day8::part2(&input).unwrap(), 8);
+}
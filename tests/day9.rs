@@ -0,0 +1,22 @@
+mod common;
+
+use rust_aoc_2020::{day9::{self, Day9}};
+
+// The example uses a preamble of 5 numbers instead of 25
+#[test]
+fn part1_example() {
+    let input = common::parse::<Day9>("day9");
+    assert_eq!(day9::find_attack_number(&input, 5).unwrap(), 127);
+}
+
+#[test]
+fn part2_example() {
+    let input = common::parse::<Day9>("day9");
+    assert_eq!(day9::find_encryption_weakness(&input, 127).unwrap(), (15, 47));
+}
+
+// The last 3 is 1 + 2, but 1 left the window before it, so only 2 + 3 counts
+#[test]
+fn pairs_must_be_in_the_preamble() {
+    assert_eq!(day9::find_attack_number(&[1, 2, 3, 3], 2).unwrap(), 3);
+}
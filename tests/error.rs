@@ -0,0 +1,45 @@
+mod common;
+
+use rust_aoc_2020::error::Error;
+
+#[test]
+fn parse_errors_point_at_the_column() {
+    let mut lines = common::lines("nop +0\nacc +1x");
+    lines.next();
+    let line = lines.next().unwrap();
+    let argument = line.split(' ').nth(1).unwrap();
+    let error = line.parse_at::<i32>(argument, "argument").unwrap_err();
+    assert_eq!(error.to_string(), concat!(
+        "error: invalid argument \"+1x\"\n",
+        " --> example:2:5\n",
+        "  |\n",
+        "2 | acc +1x\n",
+        "  |     ^",
+    ));
+}
+
+#[test]
+fn parse_errors_pad_the_gutter_to_the_line_number() {
+    let text = format!("{}light red bags", "\n".repeat(11));
+    let line = common::lines(&text).nth(11).unwrap();
+    let error = line.error_at(&line[6..], "unknown color");
+    assert_eq!(error.to_string(), concat!(
+        "error: unknown color\n",
+        "  --> example:12:7\n",
+        "   |\n",
+        "12 | light red bags\n",
+        "   |       ^",
+    ));
+}
+
+#[test]
+fn slices_from_elsewhere_point_at_the_start_of_the_line() {
+    let line = common::lines("abc").next().unwrap();
+    let error = line.error_at("abc", "not a slice of the line");
+    assert!(error.to_string().contains("example:1:1"), "{}", error);
+}
+
+#[test]
+fn solve_errors_have_no_position() {
+    assert_eq!(Error::solve("no solution").to_string(), "error: no solution");
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::fs;
+use rust_aoc_2020::client::{self, Client, Fetched};
+use common::mock_server::MockServer;
+
+fn input_server() -> MockServer {
+    MockServer::start(|request| {
+        match (request.cookie.as_deref(), request.url.as_str()) {
+            (Some("session=secret"), "/2020/day/1/input") => (200, String::from("1721\n979\n366\n")),
+            (Some("session=secret"), _) => (404, String::from("Not Found")),
+            _ => (400, String::from("Puzzle inputs differ by user.  Please log in to get your puzzle input.")),
+        }
+    })
+}
+
+#[test]
+fn downloads_input_with_session() {
+    let server = input_server();
+    let client = Client::new(&server.base_url, "secret");
+    assert_eq!(client.fetch_input(1).unwrap(), "1721\n979\n366\n");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].url, "/2020/day/1/input");
+}
+
+#[test]
+fn cached_input_is_never_downloaded_again() {
+    let server = input_server();
+    let client = Client::new(&format!("{}/", server.base_url), "secret");
+    let dir = common::temp_dir("fetch-cache");
+    let path = dir.join("day1.txt");
+    assert_eq!(client::fetch_input_cached(&client, 1, &dir).unwrap(), Fetched::Downloaded(path.clone()));
+    assert_eq!(client::fetch_input_cached(&client, 1, &dir).unwrap(), Fetched::Cached(path.clone()));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n366\n");
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn unavailable_input_is_not_cached() {
+    let server = input_server();
+    let client = Client::new(&server.base_url, "secret");
+    let dir = common::temp_dir("fetch-unavailable");
+    let error = client::fetch_input_cached(&client, 25, &dir).unwrap_err();
+    assert!(error.to_string().contains("not available"), "{}", error);
+    assert!(!dir.join("day25.txt").exists());
+}
+
+#[test]
+fn invalid_session_is_reported() {
+    let server = input_server();
+    let client = Client::new(&server.base_url, "wrong");
+    let error = client.fetch_input(1).unwrap_err();
+    assert!(error.to_string().contains("status 400"), "{}", error);
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+use rust_aoc_2020::{
+    generate::{self, Checked},
+    get_day,
+    progress::Budget,
+    solver::Part,
+    DAYS,
+};
+
+// Failures name their seed, so that `generate <day> --seed <seed>` prints the same input again
+const SEEDS: u64 = 10;
+
+// Parts whose answers aren't known are skipped, so those inputs only have to parse
+#[test]
+fn known_answers_match_the_solvers() {
+    for (day, puzzle) in DAYS.iter().filter(|(day, _)| generate::has_generator(*day)) {
+        for seed in 0..SEEDS {
+            let generated = generate::generate(*day, seed).unwrap();
+            let parts: Vec<Part> = [Part::One, Part::Two].iter().copied().filter(|part| generated.answer(*part).is_some()).collect();
+            let solution = generated.source(*day, seed).lines()
+                .and_then(|lines| puzzle.solve(lines, &parts))
+                .unwrap_or_else(|e| panic!("day {}, seed {}: {}", day, seed, e));
+            let mismatches = generated.mismatches(&solution);
+            assert!(mismatches.is_empty(), "day {}, seed {}: {:?}", day, seed, mismatches);
+        }
+    }
+}
+
+#[test]
+fn seeds_reproduce_inputs() {
+    for (day, _) in DAYS.iter().filter(|(day, _)| generate::has_generator(*day)) {
+        assert_eq!(generate::generate(*day, 7), generate::generate(*day, 7), "day {}", day);
+        assert_ne!(generate::generate(*day, 7).unwrap().text, generate::generate(*day, 8).unwrap().text, "day {}", day);
+    }
+    assert!(!generate::has_generator(30));
+    assert_eq!(generate::generate(30, 7), None);
+}
+
+#[test]
+fn wrong_answers_are_reported() {
+    let mut generated = generate::generate(9, 3).unwrap();
+    let source = generated.source(9, 3);
+    assert_eq!(source.name(), "day9-seed3");
+    generated.part2 = Some(String::from("0"));
+    let solution = get_day(9).unwrap().solve(source.lines().unwrap(), &[Part::One, Part::Two]).unwrap();
+    let mismatches = generated.mismatches(&solution);
+    assert_eq!(mismatches.len(), 1);
+    assert!(mismatches[0].starts_with("part 2: expected 0, found "), "{:?}", mismatches);
+}
+
+#[test]
+fn checks_count_the_inputs_that_passed() {
+    let mut failures = Vec::new();
+    let checked = generate::check(9, get_day(9).unwrap(), 3..6, &Budget::unlimited(), |seed, _| failures.push(seed)).unwrap();
+    assert_eq!((checked.day, checked.count, checked.failed), (9, 3, 0));
+    assert!(checked.passed());
+    assert!(failures.is_empty(), "{:?}", failures);
+
+    // Day 1 can't parse the inputs of day 20
+    let checked = generate::check(20, get_day(1).unwrap(), 0..2, &Budget::unlimited(), |seed, _| failures.push(seed)).unwrap();
+    assert_eq!((checked.count, checked.failed), (2, 2));
+    assert!(!checked.passed());
+    assert_eq!(failures, vec![0, 1]);
+    assert!(generate::check(30, get_day(1).unwrap(), 0..2, &Budget::unlimited(), |_, _| ()).is_none());
+}
+
+#[test]
+fn checks_show_a_summary() {
+    let checked = Checked { day: 9, count: 500, failed: 2, parse_time: Duration::from_millis(3), solve_time: Duration::from_millis(40) };
+    assert_eq!(checked.to_string(), "Day 9: 498 of 500 inputs passed; parse 3ms, solve 40ms in all");
+}
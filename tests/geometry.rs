@@ -0,0 +1,42 @@
+use rust_aoc_2020::geometry::{Direction, Turn, Vec2};
+
+#[test]
+fn vector_arithmetic() {
+    let mut v = Vec2::new(3, -4);
+    v += Vec2::new(1, 1);
+    assert_eq!(v, Vec2::new(4, -3));
+    assert_eq!(v - Vec2::new(4, 0), Vec2::new(0, -3));
+    assert_eq!(v * 2, Vec2::new(8, -6));
+    assert_eq!(-v, Vec2::new(-4, 3));
+    assert_eq!(Vec2::from((2usize, 5usize)), Vec2::new(2, 5));
+}
+
+#[test]
+fn manhattan_distance() {
+    assert_eq!(Vec2::new(17, -8).manhattan(), 25);
+    assert_eq!(Vec2::new(1, 1).manhattan_distance(Vec2::new(-2, 5)), 7);
+    assert_eq!(Vec2::ZERO.manhattan(), 0);
+}
+
+#[test]
+fn rotations_about_the_origin() {
+    // 10 east and 4 north, turned right: 4 east and 10 south
+    let waypoint = Vec2::new(10, -4);
+    assert_eq!(waypoint.rotate(Turn::Right, 1), Vec2::new(4, 10));
+    assert_eq!(waypoint.rotate(Turn::Left, 1), Vec2::new(-4, -10));
+    assert_eq!(waypoint.rotate(Turn::Right, 2), -waypoint);
+    assert_eq!(waypoint.rotate(Turn::Left, 3), waypoint.rotate(Turn::Right, 1));
+    assert_eq!(waypoint.rotate(Turn::Right, 4), waypoint);
+}
+
+#[test]
+fn direction_turns() {
+    assert_eq!(Direction::East.turn(Turn::Right, 1), Direction::South);
+    assert_eq!(Direction::East.turn(Turn::Left, 1), Direction::North);
+    assert_eq!(Direction::North.turn(Turn::Left, 3), Direction::East);
+    assert_eq!(Direction::West.turn(Turn::Right, 6), Direction::East);
+    assert_eq!(Direction::South.opposite(), Direction::North);
+    for direction in Direction::ALL.iter() {
+        assert_eq!(direction.turn(Turn::Right, 1).vector(), direction.vector().rotate(Turn::Right, 1));
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use rust_aoc_2020::{
+    day7::{self, Day7},
+    graph::{Cycle, Graph},
+    solver::Solver,
+};
+
+fn graph(edges: &[(&'static str, &'static str)]) -> Graph<&'static str> {
+    let mut graph = Graph::new();
+    for (from, to) in edges.iter() {
+        graph.add_labeled_edge(*from, *to, ());
+    }
+    graph
+}
+
+fn labels(graph: &Graph<&'static str>, ids: Vec<usize>) -> Vec<&'static str> {
+    ids.into_iter().map(|id| *graph.label(id)).collect()
+}
+
+#[test]
+fn labels_are_interned() {
+    let mut graph = graph(&[("a", "b"), ("b", "c"), ("a", "c")]);
+    assert_eq!(graph.len(), 3);
+    let a = graph.id(&"a").unwrap();
+    assert_eq!(graph.intern("a"), a);
+    assert_eq!(graph.successors(a).len(), 2);
+    assert_eq!(graph.predecessors(graph.id(&"c").unwrap()).len(), 2);
+    assert_eq!(graph.id(&"d"), None);
+}
+
+#[test]
+fn reachability() {
+    let graph = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "e"), ("e", "a")]);
+    let a = graph.id(&"a").unwrap();
+    assert_eq!(labels(&graph, graph.reachable_bfs(a)), vec!["b", "c", "d", "e", "a"]);
+    assert_eq!(labels(&graph, graph.reachable_dfs(a)), vec!["b", "d", "c", "e", "a"]);
+    let d = graph.id(&"d").unwrap();
+    assert!(graph.reachable_bfs(d).is_empty());
+    assert_eq!(labels(&graph, graph.reversed().reachable_bfs(d)), vec!["b", "a", "e", "c"]);
+}
+
+#[test]
+fn topological_sort() {
+    let dag = graph(&[("shirt", "tie"), ("tie", "jacket"), ("pants", "shoes"), ("pants", "belt"), ("belt", "jacket")]);
+    let order = labels(&dag, dag.topological_sort().unwrap());
+    let position = |label: &str| order.iter().position(|l| *l == label).unwrap();
+    assert_eq!(order.len(), 6);
+    assert!(position("shirt") < position("tie") && position("tie") < position("jacket"));
+    assert!(position("pants") < position("belt") && position("belt") < position("jacket"));
+    assert!(position("pants") < position("shoes"));
+
+    let cyclic = graph(&[("start", "a"), ("a", "b"), ("b", "c"), ("c", "a")]);
+    let Cycle(cycle) = cyclic.topological_sort().unwrap_err();
+    let cycle = labels(&cyclic, cycle);
+    assert_eq!(cycle.len(), 3);
+    for (i, label) in cycle.iter().enumerate() {
+        let next = cycle[(i + 1) % cycle.len()];
+        assert!(cyclic.successors(cyclic.id(label).unwrap()).iter().any(|(id, _)| *cyclic.label(*id) == next));
+    }
+}
+
+#[test]
+fn path_counting() {
+    let diamonds = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e"), ("d", "f"), ("e", "g"), ("f", "g")]);
+    let (a, d, g) = (diamonds.id(&"a").unwrap(), diamonds.id(&"d").unwrap(), diamonds.id(&"g").unwrap());
+    assert_eq!(diamonds.count_paths(a, g), Ok(4));
+    assert_eq!(diamonds.count_paths(d, g), Ok(2));
+    assert_eq!(diamonds.count_paths(g, a), Ok(0));
+
+    let cyclic = graph(&[("a", "b"), ("b", "a")]);
+    let a = cyclic.id(&"a").unwrap();
+    assert_eq!(labels(&cyclic, cyclic.count_paths(a, a).unwrap_err().0), vec!["a", "b"]);
+}
+
+#[test]
+fn bipartite_matching() {
+    // x can only take 2, which forces y onto 1 and z onto 3
+    let graph = graph(&[("x", "2"), ("y", "1"), ("y", "2"), ("z", "1"), ("z", "2"), ("z", "3")]);
+    let left: Vec<usize> = ["x", "y", "z"].iter().map(|l| graph.id(l).unwrap()).collect();
+    let matching: Vec<&str> = graph.bipartite_matching(&left).into_iter().map(|id| *graph.label(id.unwrap())).collect();
+    assert_eq!(matching, vec!["2", "1", "3"]);
+
+    let crowded = self::graph(&[("x", "1"), ("y", "1")]);
+    let left: Vec<usize> = ["x", "y"].iter().map(|l| crowded.id(l).unwrap()).collect();
+    assert_eq!(crowded.bipartite_matching(&left).iter().filter(|m| m.is_none()).count(), 1);
+}
+
+#[test]
+fn weighted_bag_rules() {
+    let rules = common::parse::<Day7>("day7");
+    let mut containing = day7::get_colors_containing(&rules, "shiny gold").unwrap();
+    containing.sort();
+    assert_eq!(containing, vec!["bright white", "dark orange", "light red", "muted yellow"]);
+    assert_eq!(day7::get_total_bags_inside(&rules, "faded blue").unwrap(), 0);
+    assert_eq!(day7::get_total_bags_inside(&rules, "dark olive").unwrap(), 7);
+}
+
+#[test]
+fn bag_rule_errors() {
+    let missing = Day7::parse(common::lines("light red bags contain 1 bright white bag.")).unwrap_err().to_string();
+    assert!(missing.contains("there is no rule for bright white bags"), "{}", missing);
+    assert!(missing.contains("example:1:26"), "{}", missing);
+    let cyclic = Day7::parse(common::lines("shiny gold bags contain 2 dark red bags.\ndark red bags contain 1 shiny gold bag.")).unwrap();
+    let error = Day7::part2(&cyclic).unwrap_err().to_string();
+    assert!(error.contains("bags contain themselves: shiny gold -> dark red -> shiny gold"), "{}", error);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use std::collections::HashSet;
+use rust_aoc_2020::{geometry::Vec2, grid::Grid};
+
+fn grid(text: &str) -> Grid<bool> {
+    Grid::parse(&mut common::lines(text), "a map").unwrap()
+}
+
+#[test]
+fn parses_and_displays_character_maps() {
+    let map = grid("#..\n.#.");
+    assert_eq!((map.width(), map.height()), (3, 2));
+    assert!(map[(0, 0)] && map[(1, 1)] && !map[(2, 1)]);
+    assert_eq!(map.to_string(), "#..\n.#.");
+}
+
+#[test]
+fn parsing_stops_at_an_empty_line() {
+    let mut lines = common::lines("##\n..\n\n#");
+    let map: Grid<bool> = Grid::parse(&mut lines, "a map").unwrap();
+    assert_eq!(map.height(), 2);
+    assert_eq!(lines.next().unwrap().text(), "#");
+}
+
+#[test]
+fn parse_errors_point_to_the_cell() {
+    let invalid = Grid::<bool>::parse(&mut common::lines("#.\n.x"), "a map").unwrap_err().to_string();
+    assert!(invalid.contains("invalid character 'x', expected '#' or '.'"), "{}", invalid);
+    assert!(invalid.contains("example:2:2"), "{}", invalid);
+    let ragged = Grid::<bool>::parse(&mut common::lines("#.\n.#."), "a map").unwrap_err().to_string();
+    assert!(ragged.contains("expected 2 cells in every row"), "{}", ragged);
+    let empty = Grid::<bool>::parse(&mut common::lines(""), "a map").unwrap_err().to_string();
+    assert!(empty.contains("expected a map"), "{}", empty);
+}
+
+#[test]
+fn bounds_checked_and_wrapping_access() {
+    let map = grid("#..\n.#.");
+    assert_eq!(map.get(Vec2::new(1, 1)), Some(&true));
+    assert_eq!(map.get(Vec2::new(-1, 0)), None);
+    assert_eq!(map.get(Vec2::new(3, 0)), None);
+    assert!(*map.get_wrapping(Vec2::new(4, 3)));
+    assert!(*map.get_wrapping(Vec2::new(-3, -2)));
+}
+
+#[test]
+fn neighbors_stay_inside_of_the_grid() {
+    let map = grid("...\n...\n...");
+    assert_eq!(map.neighbors4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+    assert_eq!(map.neighbors8((0, 0)).count(), 3);
+    assert_eq!(map.neighbors8((1, 1)).count(), 8);
+    assert_eq!(map.neighbors8((2, 1)).count(), 5);
+}
+
+#[test]
+fn rays_go_until_the_edge() {
+    let map = grid("....\n....\n....\n....");
+    assert_eq!(map.ray((0, 0), Vec2::new(1, 1)).collect::<Vec<_>>(), vec![(1, 1), (2, 2), (3, 3)]);
+    assert_eq!(map.ray((1, 2), Vec2::new(0, -1)).collect::<Vec<_>>(), vec![(1, 1), (1, 0)]);
+    assert_eq!(map.ray((3, 0), Vec2::new(1, 0)).count(), 0);
+}
+
+#[test]
+fn rotations_and_flips() {
+    let map = grid("#..\n##.");
+    assert_eq!(map.rotate_cw().to_string(), "##\n#.\n..");
+    assert_eq!(map.flip_horizontal().to_string(), "..#\n.##");
+    assert_eq!(map.flip_vertical().to_string(), "##.\n#..");
+    assert_eq!(map.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), map);
+    let orientations = map.orientations();
+    assert_eq!(orientations.len(), 8);
+    assert_eq!(orientations.iter().collect::<HashSet<_>>().len(), 8);
+    assert!(orientations.contains(&map.flip_vertical()));
+}
+
+#[test]
+fn sub_grids() {
+    let map = grid("#...\n.##.\n.#..");
+    assert_eq!(map.sub_grid(1, 1, 2, 2).to_string(), "##\n#.");
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use std::fs;
+use rust_aoc_2020::{
+    get_day,
+    ledger::{self, Check, Ledger, Status},
+    progress::Budget,
+    solver::Part,
+};
+
+#[test]
+fn missing_file_is_empty() {
+    let ledger = Ledger::load(common::temp_path("missing", "toml")).unwrap();
+    assert_eq!(ledger.get(1, Part::One, "inputs/day1.txt"), None);
+    assert!(ledger.inputs(1).is_empty());
+}
+
+#[test]
+fn check_against_recorded_answers() {
+    let mut ledger = Ledger::load(common::temp_path("check", "toml")).unwrap();
+    ledger.record(12, Part::Two, "inputs/day12.txt", "286");
+    assert_eq!(ledger.check(12, Part::Two, "inputs/day12.txt", "286"), Check::Match);
+    assert_eq!(ledger.check(12, Part::Two, "inputs/day12.txt", "285"), Check::Mismatch { expected: String::from("286") });
+    assert_eq!(ledger.check(12, Part::One, "inputs/day12.txt", "25"), Check::Unknown);
+    assert_eq!(ledger.check(12, Part::Two, "other.txt", "286"), Check::Unknown);
+}
+
+#[test]
+fn saved_answers_are_loaded_back_in_day_order() {
+    let path = common::temp_path("roundtrip", "toml");
+    let mut ledger = Ledger::load(&path).unwrap();
+    ledger.record(10, Part::One, "inputs/day10.txt", "35");
+    ledger.record(9, Part::One, "inputs/day9.txt", "127");
+    ledger.record(9, Part::Two, "inputs/day9.txt", "62");
+    ledger.save().unwrap();
+
+    let text = fs::read_to_string(&path).unwrap();
+    let loaded = Ledger::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(text.find("[day9.").unwrap() < text.find("[day10.").unwrap());
+    assert_eq!(loaded.get(9, Part::Two, "inputs/day9.txt"), Some("62"));
+    assert_eq!(loaded.get(10, Part::One, "inputs/day10.txt"), Some("35"));
+    assert_eq!(loaded.get(10, Part::Two, "inputs/day10.txt"), None);
+    assert_eq!(loaded.inputs(9), vec!["inputs/day9.txt"]);
+}
+
+#[test]
+fn invalid_day_table_is_an_error() {
+    let path = common::temp_path("invalid", "toml");
+    fs::write(&path, "[dayone.\"inputs/day1.txt\"]\npart1 = '1'\n").unwrap();
+    let result = Ledger::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn verify_shows_each_part_and_what_went_wrong() {
+    let example = common::example("day1").name();
+    let missing = common::temp_path("verify-missing", "txt").display().to_string();
+    let mut ledger = Ledger::load(common::temp_path("verify", "toml")).unwrap();
+    ledger.record(1, Part::One, &example, "514579");
+    ledger.record(1, Part::Two, &example, "1");
+    ledger.record(1, Part::One, &missing, "1");
+    let verification = ledger::verify(&[(1, get_day(1).unwrap())], &ledger, &Budget::unlimited());
+    assert!(!verification.passed());
+
+    let statuses: Vec<(&str, Part, Status)> = verification.rows.iter().map(|(_, part, input, status)| (input.as_str(), *part, *status)).collect();
+    let mut expected = vec![
+        (example.as_str(), Part::One, Status::Pass),
+        (example.as_str(), Part::Two, Status::Fail),
+        (missing.as_str(), Part::One, Status::Error),
+        (missing.as_str(), Part::Two, Status::Error),
+    ];
+    expected.sort_by_key(|(input, _, _)| *input);
+    assert_eq!(statuses, expected);
+
+    let text = verification.to_string();
+    let width = example.len().max(missing.len());
+    assert!(text.starts_with(&format!("Day  Part  {:<width$}  Result\n", "Input", width = width)), "{}", text);
+    assert!(text.contains(&format!("  1     2  {:<width$}  FAIL\n", example, width = width)), "{}", text);
+    assert!(text.contains(&format!("\nDay 1, part 2 ({}):\n- 1\n+ 241861950\n", example)), "{}", text);
+    assert!(text.contains(&format!("\nDay 1, part 1 ({}):\nerror: couldn't read {}", missing, missing)), "{}", text);
+}
+
+#[test]
+fn verify_passes_when_every_answer_matches() {
+    let example = common::example("day1").name();
+    let mut ledger = Ledger::load(common::temp_path("verify-pass", "toml")).unwrap();
+    ledger.record(1, Part::One, &example, "514579");
+    let verification = ledger::verify(&[(1, get_day(1).unwrap())], &ledger, &Budget::unlimited());
+    assert!(verification.passed(), "{}", verification);
+    assert_eq!(verification.rows[1].3, Status::Unrecorded);
+    assert!(!verification.to_string().contains("\n\n"), "{}", verification);
+}
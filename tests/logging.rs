@@ -0,0 +1,53 @@
+mod common;
+
+use std::sync::Mutex;
+use log::{LevelFilter, Log, Metadata, Record};
+use rust_aoc_2020::{day17::Day17, day8::Day8, solver::Solver};
+
+// Keeps every message, with its level and target
+struct Capture(Mutex<Vec<String>>);
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push(format!("{} {} {}", record.level(), record.target(), record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+// The logger can only be set once; whichever test comes first sets it
+fn capture() {
+    let _ = log::set_logger(&CAPTURE);
+    log::set_max_level(LevelFilter::Trace);
+}
+
+// Tests run in parallel, so each one only looks at the messages of its own day
+fn messages(target: &str) -> Vec<String> {
+    CAPTURE.0.lock().unwrap().iter().filter(|m| m.contains(target)).cloned().collect()
+}
+
+#[test]
+fn intermediate_results_are_logged() {
+    capture();
+    let input = common::parse::<Day17>("day17");
+    Day17::part1(&input).unwrap();
+    let logged = messages("rust_aoc_2020::day17");
+    assert!(logged.contains(&String::from("DEBUG rust_aoc_2020::day17 Initial state: 5 active cubes")), "{:?}", logged);
+    assert!(logged.contains(&String::from("DEBUG rust_aoc_2020::day17 After cycle 6: 112 active cubes")), "{:?}", logged);
+}
+
+#[test]
+fn steps_are_traced() {
+    capture();
+    let input = common::parse::<Day8>("day8");
+    Day8::part2(&input).unwrap();
+    let logged = messages("rust_aoc_2020::day8");
+    assert!(logged.contains(&String::from("TRACE rust_aoc_2020::day8 Terminated at instruction 10, with 8 in the accumulator")), "{:?}", logged);
+    assert!(logged.contains(&String::from("DEBUG rust_aoc_2020::day8 Fixed by changing instruction 8 to NoOp(-4)")), "{:?}", logged);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use toml::value::Table;
+use rust_aoc_2020::{
+    file::Source,
+    get_day,
+    parallel::{self, Job},
+    progress::Budget,
+};
+
+fn example_jobs(days: &[usize]) -> Vec<Job<'static>> {
+    days.iter().map(|day| Job { day: *day, puzzle: get_day(*day).unwrap(), source: common::example(&format!("day{}", day)), params: Table::new() }).collect()
+}
+
+#[test]
+fn results_are_in_job_order() {
+    let days = [11, 1, 2, 3, 5, 6, 8, 10, 12];
+    let mut finish_order = Vec::new();
+    let run = parallel::run_jobs(example_jobs(&days), 3, &Budget::unlimited(), |finished| finish_order.push(finished.day));
+    assert_eq!(run.workers, 3);
+    assert_eq!(run.finished.iter().map(|f| f.day).collect::<Vec<_>>(), days.to_vec());
+    finish_order.sort_unstable();
+    let mut sorted_days = days.to_vec();
+    sorted_days.sort_unstable();
+    assert_eq!(finish_order, sorted_days);
+    let day11 = run.finished[0].solution.as_ref().unwrap();
+    assert_eq!(day11.answers[0].result.as_ref().unwrap(), "37");
+    assert_eq!(day11.answers[1].result.as_ref().unwrap(), "26");
+}
+
+#[test]
+fn critical_path_is_the_slowest_day() {
+    let run = parallel::run_jobs(example_jobs(&[1, 11, 12]), 2, &Budget::unlimited(), |_| ());
+    let slowest = run.critical_path().unwrap();
+    assert!(run.finished.iter().all(|f| f.time <= slowest.time));
+    assert_eq!(run.sequential_time(), run.finished.iter().map(|f| f.time).sum());
+    assert!(run.wall_time >= slowest.time);
+}
+
+#[test]
+fn failures_are_kept_with_their_day() {
+    let jobs = vec![
+        Job { day: 1, puzzle: get_day(1).unwrap(), source: Source::text("broken", "1721\nx"), params: Table::new() },
+        Job { day: 2, puzzle: get_day(2).unwrap(), source: common::example("day2"), params: Table::new() },
+    ];
+    let run = parallel::run_jobs(jobs, 4, &Budget::unlimited(), |_| ());
+    // There's no point in more workers than days
+    assert_eq!(run.workers, 2);
+    assert!(run.finished[0].solution.is_err());
+    assert!(run.finished[1].solution.is_ok());
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::time::Duration;
+use toml::value::Table;
+use rust_aoc_2020::{
+    day13,
+    error::Error,
+    get_day,
+    progress::{Budget, Update},
+    solver::Part,
+};
+
+#[test]
+fn updates_show_percent_or_iterations() {
+    assert_eq!(Update { done: 15, total: Some(60) }.to_string(), "25% (15 of 60)");
+    assert_eq!(Update { done: 15, total: None }.to_string(), "iteration 15");
+}
+
+#[test]
+fn long_parts_time_out() {
+    let budget = Budget::with_timeout(Some(Duration::ZERO));
+    let lines = common::example("day15").lines().unwrap();
+    let solution = get_day(15).unwrap().solve_within(lines, &[Part::Two], &Table::new(), &budget).unwrap();
+    assert_eq!(solution.answers[0].result, Err(Error::Timeout(Duration::ZERO)));
+    assert_eq!(Error::Timeout(Duration::from_secs(2)).to_string(), "error: timed out after 2s");
+}
+
+// IDs that aren't coprime never line up, so this would run forever
+#[test]
+fn endless_searches_time_out() {
+    let progress = Budget::with_timeout(Some(Duration::from_millis(50))).start("day 13");
+    let result = day13::find_sequential_bus_offsets_with_initial_offset(&[Some(2), Some(4)], &0, &progress);
+    assert_eq!(result, Err(Error::Timeout(Duration::from_millis(50))));
+}
+
+#[test]
+fn cancelling_only_stops_long_parts() {
+    let budget = Budget::unlimited();
+    budget.cancel.cancel();
+    let parts = [Part::One, Part::Two];
+    let lines = common::example("day11").lines().unwrap();
+    let solution = get_day(11).unwrap().solve_within(lines, &parts, &Table::new(), &budget).unwrap();
+    assert!(solution.answers.iter().all(|answer| answer.result == Err(Error::Cancelled)));
+    let lines = common::example("day1").lines().unwrap();
+    let solution = get_day(1).unwrap().solve_within(lines, &parts, &Table::new(), &budget).unwrap();
+    assert!(solution.answers.iter().all(|answer| answer.result.is_ok()));
+}
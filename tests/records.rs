@@ -0,0 +1,46 @@
+mod common;
+
+use rust_aoc_2020::{day16::Day16, file::Lines, solver::Solver};
+
+fn texts(lines: Lines) -> Vec<String> {
+    lines.map(|line| line.into_text()).collect()
+}
+
+#[test]
+fn records_are_split_on_empty_lines() {
+    let records: Vec<Vec<String>> = common::lines("a\nb\n\nc\n\n\n\nd\n").records().map(texts).collect();
+    assert_eq!(records, vec![vec!["a", "b"], vec!["c"], vec!["d"]]);
+    assert_eq!(common::lines("\n\n").records().count(), 0);
+}
+
+#[test]
+fn records_keep_line_numbers() {
+    let mut lines = common::lines("a\n\n\nb\nc");
+    let first = lines.next_record().unwrap();
+    assert_eq!(first.map(|line| line.number()).collect::<Vec<usize>>(), vec![1]);
+    let mut second = lines.next_record().unwrap();
+    assert_eq!(second.next().unwrap().number(), 4);
+    second.next();
+    let error = second.end_of_input("another line").to_string();
+    assert!(error.contains("example:6:1"), "{}", error);
+    assert!(lines.next_record().is_none());
+}
+
+#[test]
+fn sections_check_their_header() {
+    let mut lines = common::lines("your ticket:\n1,2\n\nnearby:\n3,4");
+    assert_eq!(texts(lines.section("your ticket:").unwrap()), vec!["1,2"]);
+    let wrong = lines.section("nearby tickets:").unwrap_err().to_string();
+    assert!(wrong.contains("expected \"nearby tickets:\", found \"nearby:\""), "{}", wrong);
+    assert!(wrong.contains("example:4:1"), "{}", wrong);
+    let missing = lines.section("nearby tickets:").unwrap_err().to_string();
+    assert!(missing.contains("unexpected end of input"), "{}", missing);
+}
+
+#[test]
+fn day16_reports_unexpected_headers() {
+    let input = "class: 1-3 or 5-7\n\nmy ticket:\n7,1,14\n\nnearby tickets:\n7,3,47";
+    let error = Day16::parse(common::lines(input)).unwrap_err().to_string();
+    assert!(error.contains("expected \"your ticket:\", found \"my ticket:\""), "{}", error);
+    assert!(error.contains("example:3:1"), "{}", error);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use rust_aoc_2020::repl::{self, Session};
+
+fn open(day: usize, name: &str) -> Box<dyn Session> {
+    repl::open(day, common::example(name).lines().unwrap()).unwrap_or_else(|e| panic!("{}", e))
+}
+
+fn run(session: &mut Box<dyn Session>, line: &str) -> Result<String, String> {
+    repl::execute(&mut **session, line).expect("the line isn't empty")
+}
+
+#[test]
+fn words_can_be_quoted() {
+    assert_eq!(repl::split_words("contains \"shiny gold\"  x").unwrap(), vec!["contains", "shiny gold", "x"]);
+    assert_eq!(repl::split_words("tile \"\"").unwrap(), vec!["tile", ""]);
+    assert!(repl::split_words("   ").unwrap().is_empty());
+    assert_eq!(repl::split_words("contains \"shiny gold"), Err(String::from("missing closing quote")));
+}
+
+#[test]
+fn handheld_steps() {
+    let mut session = open(8, "day8");
+    assert_eq!(run(&mut session, "step 3").unwrap(), "   1: nop +0   acc 0\n   2: acc +1   acc 1\n   3: jmp +4   acc 1");
+    assert_eq!(run(&mut session, "state").unwrap(), "next: acc +1 at instruction 7 of 9, acc 1, 3 steps so far");
+    assert_eq!(run(&mut session, "run").unwrap(), "instruction 2 runs again: loop detected with 5 in the accumulator after 7 steps");
+    assert_eq!(run(&mut session, "step").unwrap(), "instruction 2 runs again: loop detected with 5 in the accumulator");
+    run(&mut session, "reset").unwrap();
+    assert!(run(&mut session, "state").unwrap().starts_with("next: nop +0 at instruction 1 of 9"));
+    assert_eq!(run(&mut session, "step x"), Err(String::from("invalid count \"x\"")));
+    assert!(repl::execute(&mut *session, "").is_none());
+}
+
+#[test]
+fn cube_layers() {
+    let mut session = open(17, "day17");
+    assert_eq!(run(&mut session, "show layer z=0").unwrap(), "z=0\n.#.\n..#\n###");
+    assert_eq!(run(&mut session, "cycle").unwrap(), "After cycle 1: 11 active cubes");
+    assert_eq!(run(&mut session, "show layer z=-1").unwrap(), "z=-1\n...\n#..\n..#\n.#.");
+    assert!(session.completions("show").contains(&String::from("layer z=-1")));
+    assert_eq!(run(&mut session, "reset 4d").unwrap(), "5 active cubes in 4 dimensions");
+    assert_eq!(run(&mut session, "cycle 6").unwrap(), "After cycle 6: 848 active cubes");
+    assert!(session.completions("show").contains(&String::from("layer z=0 w=-1")));
+    assert_eq!(run(&mut session, "show z=0"), Err(String::from("expected `show layer z=<z>`")));
+}
+
+#[test]
+fn bag_queries() {
+    let mut session = open(7, "day7");
+    assert_eq!(
+        run(&mut session, "contains \"shiny gold\"").unwrap(),
+        "4 colors can contain shiny gold bags: bright white, dark orange, light red, muted yellow",
+    );
+    assert_eq!(run(&mut session, "inside shiny gold").unwrap(), "shiny gold bags contain 1 dark olive, 2 vibrant plum; 32 bags in total");
+    assert_eq!(run(&mut session, "inside faded blue").unwrap(), "faded blue bags contain no other bags; 0 bags in total");
+    assert_eq!(run(&mut session, "contains purple"), Err(String::from("there is no rule for purple bags")));
+    assert!(session.completions("contains").contains(&String::from("shiny gold")));
+}
+
+#[test]
+fn tile_orientations() {
+    let mut session = open(20, "day20");
+    let orientations = run(&mut session, "tile 2311 orientations").unwrap();
+    let lines: Vec<&str> = orientations.lines().collect();
+    assert_eq!(lines.len(), 23);
+    assert_eq!(lines[0], "original    cw 90       cw 180      cw 270");
+    assert_eq!(lines[1], "..##.#..#.  .#..#####.  ###..###..  ...#.##..#");
+    assert!(lines[12].starts_with("flipped "));
+    assert!(run(&mut session, "tile 2311").unwrap().ends_with("borders: north 210, east 89, south 231, west 498"));
+    assert_eq!(run(&mut session, "corners").unwrap(), "corners: 1171, 1951, 2971, 3079; product 20899048083289");
+    assert_eq!(run(&mut session, "tile 9"), Err(String::from("there is no tile 9")));
+    assert!(run(&mut session, "help").unwrap().contains("tile <id> [orientations]"));
+    assert_eq!(run(&mut session, "step"), Err(String::from("unknown command \"step\"; see `help`")));
+}
+
+#[test]
+fn only_some_days_have_a_shell() {
+    assert!(repl::open(1, common::example("day1").lines().unwrap()).is_err());
+    assert!(repl::open(8, common::example("day7").lines().unwrap()).is_err());
+}
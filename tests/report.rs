@@ -0,0 +1,70 @@
+mod common;
+
+use serde_json::{json, Value};
+use rust_aoc_2020::{
+    get_day,
+    ledger::Check,
+    report,
+    solver::Part,
+};
+
+fn solve_example(day: usize, name: &str) -> Vec<report::Record> {
+    let parts = [Part::One, Part::Two];
+    let solution = common::example(name).lines().and_then(|lines| get_day(day).unwrap().solve(lines, &parts));
+    report::records(day, name, &parts, &solution)
+}
+
+#[test]
+fn records_have_answers_types_and_extra_data() {
+    let records = solve_example(11, "day11");
+    let json: Vec<Value> = records.iter().map(|r| serde_json::from_str(&r.to_json()).unwrap()).collect();
+    assert_eq!(json.len(), 2);
+    assert_eq!(json[0]["day"], 11);
+    assert_eq!(json[0]["part"], 1);
+    assert_eq!(json[0]["input"], "day11");
+    assert_eq!(json[0]["answer"], "37");
+    assert_eq!(json[0]["type"], "integer");
+    assert_eq!(json[0]["extra"], json!({ "iterations": 5 }));
+    assert_eq!(json[1]["answer"], "26");
+    assert_eq!(json[1]["extra"], json!({ "iterations": 6 }));
+    assert!(json[0]["timings"]["parse_us"].is_u64());
+    assert!(json[0]["timings"]["solve_us"].is_u64());
+    assert!(json[0].get("error").is_none());
+    assert!(json[0].get("ledger").is_none());
+}
+
+#[test]
+fn parse_errors_are_reported_for_every_part() {
+    let parts = [Part::One, Part::Two];
+    let solution = get_day(1).unwrap().solve(common::lines("1721\nx"), &parts);
+    let records = report::records(1, "example", &parts, &solution);
+    assert_eq!(records.len(), 2);
+    for record in records.iter() {
+        let json: Value = serde_json::from_str(&record.to_json()).unwrap();
+        assert_eq!(json["answer"], Value::Null);
+        assert_eq!(json["type"], Value::Null);
+        assert_eq!(json["timings"], json!({ "parse_us": null, "solve_us": null }));
+        assert!(json["error"].as_str().unwrap().contains("invalid entry \"x\""));
+    }
+}
+
+#[test]
+fn part_errors_only_affect_their_part() {
+    // Both parts fail on the example, since it has a preamble of 5 numbers instead of 25
+    let records = solve_example(9, "day9");
+    assert!(records.iter().all(|r| r.answer.is_none() && r.error.is_some() && r.kind == Some("integer")));
+    assert!(records.iter().all(|r| r.timings.parse_us.is_some()));
+}
+
+#[test]
+fn ledger_mismatches_include_the_expected_answer() {
+    let mut records = solve_example(11, "day11");
+    records[0].set_check(Check::Match);
+    records[1].set_check(Check::Mismatch { expected: String::from("25") });
+    let first: Value = serde_json::from_str(&records[0].to_json()).unwrap();
+    let second: Value = serde_json::from_str(&records[1].to_json()).unwrap();
+    assert_eq!(first["ledger"], "confirmed");
+    assert!(first.get("expected").is_none());
+    assert_eq!(second["ledger"], "mismatch");
+    assert_eq!(second["expected"], "25");
+}
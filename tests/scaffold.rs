@@ -0,0 +1,63 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+use rust_aoc_2020::scaffold;
+
+static LIB: &str = "pub mod day1;
+pub mod day3;
+
+pub static DAYS: &[(usize, &dyn Day)] = &[
+    (1, &Puzzle::<day1::Day1>::new()),
+    (3, &Puzzle::<day3::Day3>::new()),
+];
+";
+
+fn temp_repository(name: &str) -> PathBuf {
+    let dir = common::temp_dir(name);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src").join("lib.rs"), LIB).unwrap();
+    dir
+}
+
+#[test]
+fn days_are_registered_in_order() {
+    let lib = scaffold::register_day(LIB, 2).unwrap();
+    assert!(lib.contains("pub mod day1;\npub mod day2;\npub mod day3;\n"), "{}", lib);
+    assert!(lib.contains("day1::Day1>::new()),\n    (2, &Puzzle::<day2::Day2>::new()),\n    (3,"), "{}", lib);
+    let lib = scaffold::register_day(LIB, 25).unwrap();
+    assert!(lib.contains("pub mod day3;\npub mod day25;\n"), "{}", lib);
+    assert!(lib.contains("    (25, &Puzzle::<day25::Day25>::new()),\n];"), "{}", lib);
+    assert_eq!(scaffold::register_day(LIB, 3).unwrap_err(), "day 3 is already registered");
+    assert!(scaffold::register_day("", 2).is_err());
+}
+
+#[test]
+fn new_day_creates_every_file() {
+    let dir = temp_repository("scaffold");
+    fs::create_dir_all(dir.join("inputs")).unwrap();
+    fs::write(dir.join("inputs").join("day2.txt"), "fetched\n").unwrap();
+    let created = scaffold::new_day(&dir, 2).unwrap();
+    assert_eq!(created, vec![
+        dir.join("src").join("day2.rs"),
+        dir.join("tests").join("day2.rs"),
+        dir.join("inputs").join("examples").join("day2.txt"),
+    ]);
+    let module = fs::read_to_string(dir.join("src").join("day2.rs")).unwrap();
+    assert!(module.contains("impl Solver for Day2 {"), "{}", module);
+    let test = fs::read_to_string(dir.join("tests").join("day2.rs")).unwrap();
+    assert!(test.contains("common::parse::<Day2>(\"day2\")"), "{}", test);
+    assert_eq!(fs::read_to_string(dir.join("inputs").join("day2.txt")).unwrap(), "fetched\n");
+    assert!(fs::read_to_string(dir.join("src").join("lib.rs")).unwrap().contains("pub mod day2;"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn existing_days_are_left_untouched() {
+    let dir = temp_repository("scaffold-existing");
+    fs::write(dir.join("src").join("day2.rs"), "// handwritten\n").unwrap();
+    let error = scaffold::new_day(&dir, 2).unwrap_err().to_string();
+    assert!(error.contains("file already exists"), "{}", error);
+    assert_eq!(fs::read_to_string(dir.join("src").join("lib.rs")).unwrap(), LIB);
+    assert!(!dir.join("tests").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
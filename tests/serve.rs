@@ -0,0 +1,120 @@
+mod common;
+
+use std::{fs, thread, time::Duration};
+use serde_json::{json, Value};
+use rust_aoc_2020::{
+    config::Config,
+    progress::Budget,
+    serve::{self, Server},
+};
+
+fn example(name: &str) -> String {
+    fs::read_to_string(common::example(name).name()).unwrap()
+}
+
+fn post(url: &str, body: &str) -> (u16, Value) {
+    serve::handle("POST", url, body, &Config::default(), &Budget::unlimited())
+}
+
+#[test]
+fn parts_are_solved_from_the_body() {
+    let (status, response) = post("/days/11/parts/1", &example("day11"));
+    assert_eq!(status, 200);
+    assert_eq!(response["day"], 11);
+    assert_eq!(response["part"], 1);
+    assert_eq!(response["answer"], "37");
+    assert_eq!(response["type"], "integer");
+    assert_eq!(response["extra"], json!({ "iterations": 5 }));
+    assert!(response["timings"]["parse_us"].is_u64());
+    assert!(response["timings"]["solve_us"].is_u64());
+}
+
+#[test]
+fn query_sets_parameters() {
+    let (status, response) = post("/days/9/parts/1?preamble=5", &example("day9"));
+    assert_eq!((status, &response["answer"]), (200, &json!("127")));
+    let (status, response) = post("/days/7/parts/2?color=dark%20olive", &example("day7"));
+    assert_eq!((status, &response["answer"]), (200, &json!("7")));
+    let (status, response) = post("/days/9/parts/1?window=3", &example("day9"));
+    assert_eq!(status, 400);
+    assert_eq!(response["error"]["kind"], "config");
+    assert!(response["error"]["message"].as_str().unwrap().contains("the query \"window=3\""), "{}", response);
+}
+
+#[test]
+fn parse_errors_have_their_position() {
+    let (status, response) = post("/days/8/parts/1", "nop +0\nacc x1\n");
+    assert_eq!(status, 422);
+    let error = &response["error"];
+    assert_eq!(error["kind"], "parse");
+    assert_eq!((&error["line"], &error["column"]), (&json!(2), &json!(5)));
+    assert_eq!(error["text"], "acc x1");
+    assert_eq!(error["reason"], "invalid acc argument \"x1\"");
+    assert!(error["message"].as_str().unwrap().contains("--> request:2:5"), "{}", response);
+}
+
+#[test]
+fn failed_parts_and_timeouts() {
+    let (status, response) = post("/days/1/parts/1", "1\n2\n");
+    assert_eq!(status, 422);
+    assert_eq!(response["error"]["kind"], "solve");
+    let budget = Budget::with_timeout(Some(Duration::ZERO));
+    let (status, response) = serve::handle("POST", "/days/15/parts/2", "0,3,6", &Config::default(), &budget);
+    assert_eq!(status, 504);
+    assert_eq!(response["error"]["kind"], "timeout");
+}
+
+#[test]
+fn unknown_routes() {
+    assert_eq!(post("/days/30/parts/1", "").0, 404);
+    assert_eq!(post("/days/1/parts/3", "").0, 404);
+    assert_eq!(post("/answers", "").0, 404);
+    assert_eq!(serve::handle("GET", "/days/1/parts/1", "", &Config::default(), &Budget::unlimited()).0, 405);
+    let (status, response) = serve::handle("GET", "/days", "", &Config::default(), &Budget::unlimited());
+    assert_eq!(status, 200);
+    assert_eq!(response["days"][0], 1);
+}
+
+#[test]
+fn serves_on_localhost() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/days/8/parts/2", server.address());
+    let handle = {
+        let server = server.clone();
+        thread::spawn(move || server.run(&Config::default(), &Budget::unlimited()))
+    };
+    let response = ureq::post(&url).send_string(&example("day8")).unwrap();
+    assert_eq!(response.header("Content-Type"), Some("application/json"));
+    let response: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(response["answer"], "8");
+    match ureq::post(&url).send_string("jmp") {
+        Err(ureq::Error::Status(status, _)) => assert_eq!(status, 422),
+        other => panic!("expected a parse error, got {:?}", other.map(|r| r.status())),
+    }
+    server.stop();
+    handle.join().unwrap();
+}
+
+// Entries whose product overflows an i32 panic in day 1, as tests are built with overflow checks
+#[test]
+fn panicking_requests_do_not_stop_the_server() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/days/1/parts/1", server.address());
+    let handle = {
+        let server = server.clone();
+        thread::spawn(move || server.run(&Config::default(), &Budget::unlimited()))
+    };
+    match ureq::post(&url).send_string("1000000\n-997980") {
+        Err(ureq::Error::Status(status, response)) => {
+            assert_eq!(status, 500);
+            let response: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+            assert_eq!(response["error"]["kind"], "internal");
+            assert!(response["error"]["message"].as_str().unwrap().contains("overflow"), "{}", response);
+        },
+        other => panic!("expected an internal error, got {:?}", other.map(|r| r.status())),
+    }
+    let response: Value = serde_json::from_str(&ureq::post(&url).send_string(&example("day1")).unwrap().into_string().unwrap()).unwrap();
+    assert_eq!(response["answer"], "514579");
+    server.stop();
+    handle.join().unwrap();
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::fs;
+use rust_aoc_2020::{
+    client::{self, Client, Submission, Verdict},
+    ledger::Ledger,
+    solver::Part,
+};
+use common::mock_server::MockServer;
+
+fn page(message: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>", message)
+}
+
+// Accepts 26457 for day 18 part 1 and judges anything else as too low
+fn answer_server() -> MockServer {
+    MockServer::start(|request| {
+        match (request.url.as_str(), request.body.as_str()) {
+            ("/2020/day/18/answer", "level=1&answer=26457") => (200, page("That's the right answer! You are one gold star closer to saving your vacation.")),
+            ("/2020/day/18/answer", _) => (200, page("That's not the right answer; your answer is too low. Please wait one minute before trying again.")),
+            _ => (404, String::from("Not Found")),
+        }
+    })
+}
+
+#[test]
+fn verdicts() {
+    let examples = [
+        ("That's the right answer! You are one gold star closer to saving your vacation. <a href=\"/2020/day/18#part2\">[Continue to Part Two]</a>", Verdict::Right),
+        ("That's not the right answer. If you're stuck, make sure you're using the full input data.", Verdict::Wrong),
+        ("That's not the right answer; your answer is too high. If you're stuck, try the <a href=\"/2020/about\">subreddit</a>.", Verdict::TooHigh),
+        ("That's not the right answer; your answer is too low. Please wait one minute before trying again.", Verdict::TooLow),
+        ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait. <a href=\"/2020/day/18\">[Return to Day 18]</a>", Verdict::RateLimited { wait: Some(String::from("4m 32s")) }),
+        ("You don't seem to be solving the right level.  Did you already complete it?", Verdict::WrongLevel),
+        ("Something <em>else</em> happened.", Verdict::Unrecognized(String::from("Something else happened."))),
+    ];
+    for (message, verdict) in examples.iter() {
+        assert_eq!(&client::parse_verdict(&page(message)), verdict, "{}", message);
+    }
+}
+
+#[test]
+fn posts_level_and_answer() {
+    let server = answer_server();
+    let client = Client::new(&server.base_url, "secret");
+    assert_eq!(client.submit_answer(18, Part::One, "26457").unwrap(), Verdict::Right);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].url, "/2020/day/18/answer");
+    assert_eq!(requests[0].cookie.as_deref(), Some("session=secret"));
+    assert_eq!(requests[0].body, "level=1&answer=26457");
+}
+
+#[test]
+fn right_answers_are_recorded() {
+    let server = answer_server();
+    let client = Client::new(&server.base_url, "secret");
+    let path = common::temp_path("submit-right", "toml");
+    let mut ledger = Ledger::load(&path).unwrap();
+    let submission = client::submit_and_record(&client, &mut ledger, 18, Part::One, "inputs/day18.txt", "26457").unwrap();
+    let saved = Ledger::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(submission, Submission::Judged(Verdict::Right));
+    assert_eq!(saved.get(18, Part::One, "inputs/day18.txt"), Some("26457"));
+}
+
+#[test]
+fn wrong_answers_are_not_recorded() {
+    let server = answer_server();
+    let client = Client::new(&server.base_url, "secret");
+    let path = common::temp_path("submit-wrong", "toml");
+    let mut ledger = Ledger::load(&path).unwrap();
+    let submission = client::submit_and_record(&client, &mut ledger, 18, Part::One, "inputs/day18.txt", "100").unwrap();
+    assert_eq!(submission, Submission::Judged(Verdict::TooLow));
+    assert_eq!(ledger.get(18, Part::One, "inputs/day18.txt"), None);
+    assert!(!path.exists());
+}
+
+#[test]
+fn confirmed_answers_are_not_submitted_again() {
+    let server = answer_server();
+    let client = Client::new(&server.base_url, "secret");
+    let mut ledger = Ledger::load(common::temp_path("submit-confirmed", "toml")).unwrap();
+    ledger.record(18, Part::One, "inputs/day18.txt", "26457");
+    let submission = client::submit_and_record(&client, &mut ledger, 18, Part::One, "inputs/day18.txt", "26457").unwrap();
+    assert_eq!(submission, Submission::AlreadyConfirmed(String::from("26457")));
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+use rust_aoc_2020::{
+    config::Config,
+    file::Source,
+    get_day,
+    progress::Budget,
+    report::{self, Record},
+    solver::Part,
+    watch::{self, Watcher},
+};
+
+fn solve(day: usize, input: &str, text: &str) -> Vec<Record> {
+    let parts = [Part::One, Part::Two];
+    let solution = Source::text(input, text).lines().and_then(|lines| get_day(day).unwrap().solve(lines, &parts));
+    report::records(day, input, &parts, &solution)
+}
+
+#[test]
+fn examples_of_a_day() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("inputs").join("examples");
+    let names = |day| watch::example_paths(&dir, day).iter().map(|p| p.file_name().unwrap().to_string_lossy().into_owned()).collect::<Vec<_>>();
+    assert_eq!(names(1), vec!["day1-repeated-entry.txt", "day1.txt"]);
+    assert_eq!(names(10), vec!["day10-larger.txt", "day10.txt"]);
+    assert!(names(25).is_empty());
+}
+
+// With the parameters of the real input, the example of day 9 has a preamble of 25 and no answer
+#[test]
+fn examples_are_solved_with_their_own_parameters() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("inputs").join("examples");
+    let mut config = Config::default();
+    config.set("day9.preamble=20").unwrap();
+    let (input, example) = (PathBuf::from("inputs/day9.txt"), dir.join("day9.txt"));
+    let files = watch::watched_files(9, input.clone(), &config, &dir).unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[&input], config.params(9));
+
+    let lines = Source::File(example.clone()).lines().unwrap();
+    let solution = get_day(9).unwrap().solve_within(lines, &[Part::One, Part::Two], &files[&example], &Budget::unlimited()).unwrap();
+    let answers: Vec<String> = solution.answers.into_iter().map(|answer| answer.result.unwrap()).collect();
+    assert_eq!(answers, vec!["127", "62"]);
+}
+
+#[test]
+fn changes_are_seen_once() {
+    let path = common::temp_path("watch-changes", "txt");
+    fs::write(&path, "1721\n979\n").unwrap();
+    let mut watcher = Watcher::new(std::slice::from_ref(&path));
+    assert_eq!(watcher.changed(), vec![path.clone()]);
+    assert!(watcher.changed().is_empty());
+    fs::write(&path, "1721\n979\n366\n").unwrap();
+    assert_eq!(watcher.changed(), vec![path.clone()]);
+    fs::remove_file(&path).unwrap();
+    assert!(watcher.changed().is_empty());
+    fs::write(&path, "1721\n").unwrap();
+    assert_eq!(watcher.changed(), vec![path.clone()]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn answers_are_compared_with_the_previous_run() {
+    let first = solve(1, "day1", "1721\n979\n366\n299\n675\n1456");
+    let lines = watch::diff(&[], &first);
+    assert!(lines[0].starts_with("day1, part 1: 514579, parse "), "{:?}", lines);
+    assert!(!lines[0].contains('%'), "{:?}", lines);
+
+    let second = solve(1, "day1", "1720\n979\n366\n300\n675\n1456");
+    let lines = watch::diff(&first, &second);
+    assert!(lines[0].starts_with("day1, part 1: 516000 (was 514579), parse "), "{:?}", lines);
+    assert!(lines[0].contains("%)"), "{:?}", lines);
+    assert!(lines[1].starts_with("day1, part 2: 241861950, parse "), "{:?}", lines);
+
+    let broken = solve(1, "day1", "1721\nx");
+    let lines = watch::diff(&second, &broken);
+    assert_eq!(lines[0], "day1, part 1: ERROR");
+    assert!(lines[1].starts_with("error: invalid entry \"x\""), "{:?}", lines);
+    assert_eq!(lines.last().unwrap(), "day1, part 2: same error as part 1");
+    assert_eq!(watch::diff(&broken, &broken), vec!["day1, part 1: same error", "day1, part 2: same error"]);
+
+    let fixed = solve(1, "day1", "1000\n1020\n1\n999");
+    let lines = watch::diff(&broken, &fixed);
+    assert!(lines[0].starts_with("day1, part 1: 1020000 (fixed), parse "), "{:?}", lines);
+    assert!(lines[1].starts_with("day1, part 2: 1018980 (fixed), parse "), "{:?}", lines);
+}